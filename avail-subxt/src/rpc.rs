@@ -20,6 +20,87 @@ pub struct Health {
 	pub should_have_peers: bool,
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Blob {
+	pub block_hash: H256,
+	pub block_number: u32,
+	pub transaction_index: u32,
+	pub data: Vec<u8>,
+	pub proof: Option<ProofResponse>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AssembledBlob {
+	pub blob_root: H256,
+	pub manifest_block_hash: H256,
+	/// SS58 address of the account which submitted the chunks.
+	pub owner: String,
+	pub data: Vec<u8>,
+	pub chunks: Vec<BlobChunk>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobChunk {
+	pub block_hash: H256,
+	pub block_number: u32,
+	pub transaction_index: u32,
+	pub data_hash: H256,
+	pub len: u32,
+	pub proof: Option<ProofResponse>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtrinsicProof {
+	pub block_hash: H256,
+	pub extrinsics_root: H256,
+	pub transaction_index: u32,
+	pub extrinsic: Vec<u8>,
+	pub proof: Vec<Vec<u8>>,
+	/// SS58 address of the signer.
+	pub signer: Option<String>,
+	pub app_id: Option<AppId>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Row {
+	pub row_index: u32,
+	pub scalars: Vec<Vec<u8>>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RowsV2 {
+	pub version: u8,
+	pub rows: Vec<Row>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CellPosition {
+	pub row: u32,
+	pub col: u32,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CellProof {
+	pub cell: CellPosition,
+	pub proof: Vec<u8>,
+	pub scalar: Vec<u8>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofsV2 {
+	pub version: u8,
+	pub proofs: Vec<CellProof>,
+}
+
 #[rpc(client, namespace = "system")]
 pub trait Rpc {
 	#[method(name = "health")]
@@ -31,9 +112,15 @@ pub trait KateRpc {
 	#[method(name = "queryRows")]
 	async fn query_rows(&self, rows: Rows, block: H256) -> Result<Vec<Vec<u8>>>;
 
+	#[method(name = "queryRowsV2")]
+	async fn query_rows_v2(&self, rows: Rows, block: H256) -> Result<RowsV2>;
+
 	#[method(name = "queryProof")]
 	async fn query_proof(&self, cells: Cells, block: H256) -> Result<Vec<u8>>;
 
+	#[method(name = "queryProofV2")]
+	async fn query_proof_v2(&self, cells: Cells, block: H256) -> Result<ProofsV2>;
+
 	#[method(name = "queryAppData")]
 	async fn query_app_data(&self, app_id: AppId, block: H256) -> Result<Vec<Option<Vec<u8>>>>;
 
@@ -49,4 +136,24 @@ pub trait KateRpc {
 		transaction_index: u32,
 		block: H256,
 	) -> Result<ProofResponse>;
+
+	#[method(name = "queryAllDataProofsV2")]
+	async fn query_all_data_proofs_v2(&self, block: H256) -> Result<Vec<(u32, ProofResponse)>>;
+
+	#[method(name = "getBlobByHash")]
+	async fn get_blob_by_hash(&self, data_hash: H256, block: Option<H256>) -> Result<Blob>;
+
+	#[method(name = "getAssembledBlob")]
+	async fn get_assembled_blob(
+		&self,
+		blob_root: H256,
+		block: Option<H256>,
+	) -> Result<AssembledBlob>;
+
+	#[method(name = "queryExtrinsicProof")]
+	async fn query_extrinsic_proof(
+		&self,
+		transaction_index: u32,
+		block: H256,
+	) -> Result<ExtrinsicProof>;
 }
@@ -1,6 +1,9 @@
 use core::time::Duration;
 
-use substrate_prometheus_endpoint::{Histogram, PrometheusError, Registry};
+use substrate_prometheus_endpoint::{
+	register, CounterVec, GaugeVec, Histogram, HistogramOpts, HistogramVec, Opts, PrometheusError,
+	Registry, U64,
+};
 
 use crate::metrics::LOG_TARGET;
 
@@ -144,11 +147,38 @@ impl HeaderExtensionBuilderMetrics {
 
 pub struct KateRpcMetrics {
 	pub query_rows_execution_time: Histogram,
+	pub query_columns_execution_time: Histogram,
 	pub query_app_data_execution_time: Histogram,
 	pub query_proof_execution_time: Histogram,
+	pub query_multiproof_execution_time: Histogram,
+	pub reconstruct_app_rows_execution_time: Histogram,
 	pub query_block_length_execution_time: Histogram,
+	pub query_block_da_info_execution_time: Histogram,
 	pub query_data_proof_execution_time: Histogram,
 	pub query_data_proof_v2_execution_time: Histogram,
+	pub query_all_data_proofs_v2_execution_time: Histogram,
+	pub get_blob_by_hash_execution_time: Histogram,
+	pub get_assembled_blob_execution_time: Histogram,
+	pub query_extrinsic_proof_execution_time: Histogram,
+	pub subscribe_app_data_execution_time: Histogram,
+	/// Tokens charged by the rate limiter, per method.
+	pub rate_limit_charged_tokens: CounterVec<U64>,
+	/// Calls rejected by the rate limiter, per method and bucket scope.
+	pub rate_limit_rejected_calls: CounterVec<U64>,
+	/// Buckets tracked by the rate limiter, per scope.
+	pub rate_limit_buckets: GaugeVec<U64>,
+	/// Grid cache hits, per cache.
+	pub grid_cache_hits: CounterVec<U64>,
+	/// Grid cache misses, per cache.
+	pub grid_cache_misses: CounterVec<U64>,
+	/// Grid cache entries evicted by size, per cache.
+	pub grid_cache_evictions: CounterVec<U64>,
+	/// Build time of the grids after a cache miss, per cache.
+	pub grid_build_time: HistogramVec,
+	/// Weighted size of the grid caches in bytes, per cache.
+	pub grid_cache_weighted_size: GaugeVec<U64>,
+	/// Entries of the grid caches, per cache.
+	pub grid_cache_entries: GaugeVec<U64>,
 }
 
 impl KateRpcMetrics {
@@ -165,6 +195,18 @@ impl KateRpcMetrics {
 			buckets.to_vec(),
 		)?;
 
+		let buckets = [
+			1000.0, 5000.0, 10000.0, 25000.0, //  1ms, 5ms, 10ms, 25ms
+			50000.0, 75000.0, 100000.0, 150000.0, // 50ms, 75ms, 100ms, 150ms
+			200000.0, 300000.0, 400000.0, 500000.0, // 200ms, 300ms, 400ms, 500ms
+		];
+		let query_columns_execution_time = custom_histogram(
+			registry,
+			"avail_kate_rpc_query_columns_execution_time",
+			"Kate RPC - Query Columns Time in microseconds",
+			buckets.to_vec(),
+		)?;
+
 		let buckets = [
 			1000.0, 10000.0, 25000.0, 50000.0, // 1ms, 10ms, 25ms, 50ms
 			75000.0, 100000.0, 150000.0, 200000.0, // 75ms, 100ms, 150ms, 200ms
@@ -189,6 +231,24 @@ impl KateRpcMetrics {
 			buckets.to_vec(),
 		)?;
 
+		let buckets = [
+			100000.0, 250000.0, 500000.0, 1000000.0, //  100ms, 250ms, 500ms, 1s
+			2500000.0, 5000000.0, 7500000.0, 10000000.0, // 2.5s, 5s, 7.5s, 10s
+			12500000.0, 15000000.0, 17500000.0, // 12.5s, 15s, 17.5s
+		];
+		let query_multiproof_execution_time = custom_histogram(
+			registry,
+			"avail_kate_rpc_query_multiproof_execution_time",
+			"Kate RPC - Query Multiproof Time in microseconds",
+			buckets.to_vec(),
+		)?;
+		let reconstruct_app_rows_execution_time = custom_histogram(
+			registry,
+			"avail_kate_rpc_reconstruct_app_rows_execution_time",
+			"Kate RPC - Reconstruct App Rows Time in microseconds",
+			buckets.to_vec(),
+		)?;
+
 		let buckets = [
 			100.0, 200.0, 300.0, 400.0, 500.0, // 0.10ms, 0.20ms, 0.30ms, 0.40ms, 0.50ms,
 			750.0, 1000.0, 1250.0, 2500.0, // 0.75ms, 1.0ms, 1.25ms, 2.5ms
@@ -201,6 +261,13 @@ impl KateRpcMetrics {
 			buckets.to_vec(),
 		)?;
 
+		let query_block_da_info_execution_time = custom_histogram(
+			registry,
+			"avail_kate_rpc_query_block_da_info_execution_time",
+			"Kate RPC - Query Block DA Info Time in microseconds",
+			buckets.to_vec(),
+		)?;
+
 		let buckets = [
 			100.0, 250.0, 500.0, 1000.0, 2500.0, // 0.10ms, 0.25ms, 0.5ms, 1ms, 2.5ms,
 			5000.0, 7500.0, 10000.0, 25000.0, // 5ms, 7.5ms, 10ms, 25ms
@@ -225,13 +292,191 @@ impl KateRpcMetrics {
 			buckets.to_vec(),
 		)?;
 
+		let buckets = [
+			1000.0, 5000.0, 10000.0, 25000.0, // 1ms, 5ms, 10ms, 25ms
+			50000.0, 100000.0, 250000.0, 500000.0,  // 50ms, 100ms, 250ms, 500ms
+			1000000.0, // 1s
+		];
+		let query_all_data_proofs_v2_execution_time = custom_histogram(
+			registry,
+			"avail_kate_rpc_query_all_data_proofs_v2_execution_time",
+			"Kate RPC - Query All Data Proofs V2 Time in microseconds",
+			buckets.to_vec(),
+		)?;
+
+		let buckets = [
+			100.0, 250.0, 500.0, 1000.0, 2500.0, // 0.10ms, 0.25ms, 0.5ms, 1ms, 2.5ms,
+			5000.0, 7500.0, 10000.0, 25000.0, // 5ms, 7.5ms, 10ms, 25ms
+			50000.0, 100000.0, // 50ms, 100ms
+		];
+		let get_blob_by_hash_execution_time = custom_histogram(
+			registry,
+			"avail_kate_rpc_get_blob_by_hash_execution_time",
+			"Kate RPC - Get Blob By Hash Time in microseconds",
+			buckets.to_vec(),
+		)?;
+
+		let buckets = [
+			100.0, 250.0, 500.0, 1000.0, 2500.0, // 0.10ms, 0.25ms, 0.5ms, 1ms, 2.5ms,
+			5000.0, 7500.0, 10000.0, 25000.0, // 5ms, 7.5ms, 10ms, 25ms
+			50000.0, 100000.0, 250000.0, 500000.0, // 50ms, 100ms, 250ms, 500ms
+		];
+		let get_assembled_blob_execution_time = custom_histogram(
+			registry,
+			"avail_kate_rpc_get_assembled_blob_execution_time",
+			"Kate RPC - Get Assembled Blob Time in microseconds",
+			buckets.to_vec(),
+		)?;
+
+		let buckets = [
+			100.0, 250.0, 500.0, 1000.0, 2500.0, // 0.10ms, 0.25ms, 0.5ms, 1ms, 2.5ms,
+			5000.0, 7500.0, 10000.0, 25000.0, // 5ms, 7.5ms, 10ms, 25ms
+			50000.0, // 50ms
+		];
+		let query_extrinsic_proof_execution_time = custom_histogram(
+			registry,
+			"avail_kate_rpc_query_extrinsic_proof_execution_time",
+			"Kate RPC - Query Extrinsic Proof Time in microseconds",
+			buckets.to_vec(),
+		)?;
+
+		let buckets = [
+			1000.0, 10000.0, 25000.0, 50000.0, // 1ms, 10ms, 25ms, 50ms
+			75000.0, 100000.0, 150000.0, 200000.0, // 75ms, 100ms, 150ms, 200ms
+			300000.0, 500000.0, // 300ms, 500ms
+		];
+		let subscribe_app_data_execution_time = custom_histogram(
+			registry,
+			"avail_kate_rpc_subscribe_app_data_execution_time",
+			"Kate RPC - Subscribe App Data Notification Time in microseconds",
+			buckets.to_vec(),
+		)?;
+
+		let rate_limit_charged_tokens = register(
+			CounterVec::new(
+				Opts::new(
+					"avail_kate_rpc_rate_limit_charged_tokens",
+					"Kate RPC - Tokens charged by the rate limiter",
+				),
+				&["method"],
+			)?,
+			registry,
+		)?;
+		let rate_limit_rejected_calls = register(
+			CounterVec::new(
+				Opts::new(
+					"avail_kate_rpc_rate_limit_rejected_calls",
+					"Kate RPC - Calls rejected by the rate limiter",
+				),
+				&["method", "scope"],
+			)?,
+			registry,
+		)?;
+		let rate_limit_buckets = register(
+			GaugeVec::new(
+				Opts::new(
+					"avail_kate_rpc_rate_limit_buckets",
+					"Kate RPC - Token buckets tracked by the rate limiter",
+				),
+				&["scope"],
+			)?,
+			registry,
+		)?;
+
+		let grid_cache_hits = register(
+			CounterVec::new(
+				Opts::new(
+					"avail_kate_rpc_grid_cache_hits",
+					"Kate RPC - Grid cache hits",
+				),
+				&["cache"],
+			)?,
+			registry,
+		)?;
+		let grid_cache_misses = register(
+			CounterVec::new(
+				Opts::new(
+					"avail_kate_rpc_grid_cache_misses",
+					"Kate RPC - Grid cache misses",
+				),
+				&["cache"],
+			)?,
+			registry,
+		)?;
+		let grid_cache_evictions = register(
+			CounterVec::new(
+				Opts::new(
+					"avail_kate_rpc_grid_cache_evictions",
+					"Kate RPC - Grid cache entries evicted by size",
+				),
+				&["cache"],
+			)?,
+			registry,
+		)?;
+		let buckets = [
+			100000.0, 250000.0, 500000.0, 1000000.0, //  100ms, 250ms, 500ms, 1s
+			2500000.0, 5000000.0, 7500000.0, 10000000.0, // 2.5s, 5s, 7.5s, 10s
+			12500000.0, 15000000.0, 17500000.0, // 12.5s, 15s, 17.5s
+		];
+		let grid_build_time = register(
+			HistogramVec::new(
+				HistogramOpts {
+					common_opts: Opts::new(
+						"avail_kate_rpc_grid_build_time",
+						"Kate RPC - Grid Build Time after a cache miss in microseconds",
+					),
+					buckets: buckets.to_vec(),
+				},
+				&["cache"],
+			)?,
+			registry,
+		)?;
+		let grid_cache_weighted_size = register(
+			GaugeVec::new(
+				Opts::new(
+					"avail_kate_rpc_grid_cache_weighted_size",
+					"Kate RPC - Weighted size of the grid caches in bytes",
+				),
+				&["cache"],
+			)?,
+			registry,
+		)?;
+		let grid_cache_entries = register(
+			GaugeVec::new(
+				Opts::new(
+					"avail_kate_rpc_grid_cache_entries",
+					"Kate RPC - Entries of the grid caches",
+				),
+				&["cache"],
+			)?,
+			registry,
+		)?;
+
 		Ok(Self {
 			query_rows_execution_time,
+			query_columns_execution_time,
 			query_app_data_execution_time,
 			query_proof_execution_time,
+			query_multiproof_execution_time,
+			reconstruct_app_rows_execution_time,
 			query_block_length_execution_time,
+			query_block_da_info_execution_time,
 			query_data_proof_execution_time,
 			query_data_proof_v2_execution_time,
+			query_all_data_proofs_v2_execution_time,
+			get_blob_by_hash_execution_time,
+			get_assembled_blob_execution_time,
+			query_extrinsic_proof_execution_time,
+			subscribe_app_data_execution_time,
+			rate_limit_charged_tokens,
+			rate_limit_rejected_calls,
+			rate_limit_buckets,
+			grid_cache_hits,
+			grid_cache_misses,
+			grid_cache_evictions,
+			grid_build_time,
+			grid_cache_weighted_size,
+			grid_cache_entries,
 		})
 	}
 
@@ -244,6 +489,15 @@ impl KateRpcMetrics {
 		}
 	}
 
+	pub fn observe_query_columns_execution_time(duration: Duration) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.query_columns_execution_time
+				.observe(duration.as_micros() as f64);
+		}
+	}
+
 	pub fn observe_query_app_data_execution_time(duration: Duration) {
 		if let Some(metrics) = AVAIL_METRICS.get() {
 			metrics
@@ -262,6 +516,24 @@ impl KateRpcMetrics {
 		}
 	}
 
+	pub fn observe_query_multiproof_execution_time(duration: Duration) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.query_multiproof_execution_time
+				.observe(duration.as_micros() as f64);
+		}
+	}
+
+	pub fn observe_reconstruct_app_rows_execution_time(duration: Duration) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.reconstruct_app_rows_execution_time
+				.observe(duration.as_micros() as f64);
+		}
+	}
+
 	pub fn observe_query_block_length_execution_time(duration: Duration) {
 		if let Some(metrics) = AVAIL_METRICS.get() {
 			metrics
@@ -271,6 +543,15 @@ impl KateRpcMetrics {
 		}
 	}
 
+	pub fn observe_query_block_da_info_execution_time(duration: Duration) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.query_block_da_info_execution_time
+				.observe(duration.as_micros() as f64);
+		}
+	}
+
 	pub fn observe_query_data_proof_execution_time(duration: Duration) {
 		if let Some(metrics) = AVAIL_METRICS.get() {
 			metrics
@@ -288,6 +569,135 @@ impl KateRpcMetrics {
 				.observe(duration.as_micros() as f64);
 		}
 	}
+
+	pub fn observe_query_all_data_proofs_v2_execution_time(duration: Duration) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.query_all_data_proofs_v2_execution_time
+				.observe(duration.as_micros() as f64);
+		}
+	}
+
+	pub fn observe_get_blob_by_hash_execution_time(duration: Duration) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.get_blob_by_hash_execution_time
+				.observe(duration.as_micros() as f64);
+		}
+	}
+
+	pub fn observe_get_assembled_blob_execution_time(duration: Duration) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.get_assembled_blob_execution_time
+				.observe(duration.as_micros() as f64);
+		}
+	}
+
+	pub fn observe_query_extrinsic_proof_execution_time(duration: Duration) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.query_extrinsic_proof_execution_time
+				.observe(duration.as_micros() as f64);
+		}
+	}
+
+	pub fn observe_subscribe_app_data_execution_time(duration: Duration) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.subscribe_app_data_execution_time
+				.observe(duration.as_micros() as f64);
+		}
+	}
+
+	pub fn observe_rate_limit_charged(method: &str, tokens: u64) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.rate_limit_charged_tokens
+				.with_label_values(&[method])
+				.inc_by(tokens);
+		}
+	}
+
+	pub fn observe_rate_limit_rejected(method: &str, scope: &str) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.rate_limit_rejected_calls
+				.with_label_values(&[method, scope])
+				.inc();
+		}
+	}
+
+	pub fn observe_rate_limit_buckets(connections: usize, ips: usize) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			let buckets = &metrics.kate_rpc.rate_limit_buckets;
+			buckets
+				.with_label_values(&["connection"])
+				.set(connections as u64);
+			buckets.with_label_values(&["ip"]).set(ips as u64);
+		}
+	}
+
+	pub fn observe_grid_cache_hit(cache: &str) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.grid_cache_hits
+				.with_label_values(&[cache])
+				.inc();
+		}
+	}
+
+	pub fn observe_grid_cache_miss(cache: &str) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.grid_cache_misses
+				.with_label_values(&[cache])
+				.inc();
+		}
+	}
+
+	pub fn observe_grid_cache_eviction(cache: &str) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.grid_cache_evictions
+				.with_label_values(&[cache])
+				.inc();
+		}
+	}
+
+	pub fn observe_grid_build_time(cache: &str, duration: Duration) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.grid_build_time
+				.with_label_values(&[cache])
+				.observe(duration.as_micros() as f64);
+		}
+	}
+
+	pub fn observe_grid_cache_size(cache: &str, entries: u64, weighted_size: u64) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			let kate_rpc = &metrics.kate_rpc;
+			kate_rpc
+				.grid_cache_entries
+				.with_label_values(&[cache])
+				.set(entries);
+			kate_rpc
+				.grid_cache_weighted_size
+				.with_label_values(&[cache])
+				.set(weighted_size);
+		}
+	}
 }
 
 pub struct ImportBlockMetrics {
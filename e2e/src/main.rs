@@ -29,10 +29,11 @@ pub mod tests {
 	use kate_recovery::matrix::Dimensions;
 	use kate_recovery::proof::verify;
 	use sp_keyring::AccountKeyring;
-	use subxt::ext::sp_core::keccak_256;
+	use subxt::ext::sp_core::{blake2_256, keccak_256};
 	use subxt::ext::sp_runtime::traits::Keccak256;
 	use subxt::tx::TxClient;
 	use subxt::{
+		dynamic::Value,
 		ext::sp_core::H256,
 		rpc::{types::ChainBlockResponse, Rpc, RpcParams},
 		OnlineClient,
@@ -66,6 +67,26 @@ pub mod tests {
 		Ok(events.block_hash())
 	}
 
+	/// Submits `DataAvailability::{call}` with `fields`, as the calls are not in the generated
+	/// API yet.
+	async fn send_da_dynamic_call(
+		txc: &TxClient<AvailConfig, OnlineClient<AvailConfig>>,
+		call: &str,
+		fields: Vec<Value>,
+	) -> anyhow::Result<H256> {
+		let signer = PairSigner::new(AccountKeyring::Alice.pair());
+
+		let call = subxt::dynamic::tx("DataAvailability", call, fields);
+		let extrinsic_params = AvailExtrinsicParams::new_with_app_id(0.into());
+
+		let tx_progress = txc
+			.sign_and_submit_then_watch(&call, &signer, extrinsic_params)
+			.await?;
+		let events = tx_progress.wait_for_finalized_success().await?;
+
+		Ok(events.block_hash())
+	}
+
 	async fn get_submitted_block(
 		rpc: &Rpc<AvailConfig>,
 		block_hash: H256,
@@ -111,6 +132,39 @@ pub mod tests {
 		Ok(rows)
 	}
 
+	#[derive(serde::Serialize)]
+	struct QueryOptions {
+		finality: &'static str,
+	}
+
+	async fn query_rows_with_finality(
+		rpc: &Rpc<AvailConfig>,
+		rows: &[usize],
+		block_hash: H256,
+		finality: &'static str,
+	) -> anyhow::Result<Vec<Vec<u8>>> {
+		let mut params = RpcParams::new();
+		params.push(rows)?;
+		params.push(Some(block_hash))?;
+		params.push(QueryOptions { finality })?;
+		let rows: Vec<Vec<u8>> = rpc.request("kate_queryRows", params).await?;
+
+		Ok(rows)
+	}
+
+	async fn query_columns(
+		rpc: &Rpc<AvailConfig>,
+		columns: &[usize],
+		block_hash: H256,
+	) -> anyhow::Result<Vec<Vec<u8>>> {
+		let mut params = RpcParams::new();
+		params.push(columns)?;
+		params.push(Some(block_hash))?;
+		let columns: Vec<Vec<u8>> = rpc.request("kate_queryColumns", params).await?;
+
+		Ok(columns)
+	}
+
 	async fn query_app_data(
 		rpc: &Rpc<AvailConfig>,
 		app_id: AppId,
@@ -161,6 +215,200 @@ pub mod tests {
 		Ok(data_proof)
 	}
 
+	async fn query_all_data_proofs_v2(
+		rpc: &Rpc<AvailConfig>,
+		block_hash: H256,
+	) -> anyhow::Result<Vec<(u32, ProofResponse)>> {
+		let mut params = RpcParams::new();
+		params.push(Some(block_hash))?;
+		let data_proofs: Vec<(u32, ProofResponse)> =
+			rpc.request("kate_queryAllDataProofsV2", params).await?;
+		Ok(data_proofs)
+	}
+
+	#[derive(serde::Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct Blob {
+		block_hash: H256,
+		transaction_index: u32,
+		data: Vec<u8>,
+		proof: Option<ProofResponse>,
+	}
+
+	#[derive(serde::Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct AssembledBlob {
+		blob_root: H256,
+		data: Vec<u8>,
+		chunks: Vec<BlobChunk>,
+	}
+
+	#[derive(serde::Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct BlobChunk {
+		block_hash: H256,
+		transaction_index: u32,
+		data_hash: H256,
+		proof: Option<ProofResponse>,
+	}
+
+	#[derive(serde::Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct GridDimensions {
+		rows: u16,
+		cols: u16,
+	}
+
+	#[derive(serde::Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct BlockDaInfo {
+		version: u8,
+		dimensions: GridDimensions,
+		extended_dimensions: GridDimensions,
+		commitments: Vec<Vec<u8>>,
+		data_root: H256,
+		padding_ratio: f64,
+	}
+
+	async fn query_block_da_info(
+		rpc: &Rpc<AvailConfig>,
+		block_hash: H256,
+	) -> anyhow::Result<BlockDaInfo> {
+		let mut params = RpcParams::new();
+		params.push(Some(block_hash))?;
+		let da_info: BlockDaInfo = rpc.request("kate_blockDaInfo", params).await?;
+		Ok(da_info)
+	}
+
+	async fn get_blob_by_hash(
+		rpc: &Rpc<AvailConfig>,
+		data_hash: H256,
+		block_hash: Option<H256>,
+	) -> anyhow::Result<Blob> {
+		let mut params = RpcParams::new();
+		params.push(data_hash)?;
+		params.push(block_hash)?;
+		let blob: Blob = rpc.request("kate_getBlobByHash", params).await?;
+		Ok(blob)
+	}
+
+	async fn get_assembled_blob(
+		rpc: &Rpc<AvailConfig>,
+		blob_root: H256,
+		block_hash: Option<H256>,
+	) -> anyhow::Result<AssembledBlob> {
+		let mut params = RpcParams::new();
+		params.push(blob_root)?;
+		params.push(block_hash)?;
+		let blob: AssembledBlob = rpc.request("kate_getAssembledBlob", params).await?;
+		Ok(blob)
+	}
+
+	#[derive(serde::Serialize)]
+	struct DataCell {
+		row: u32,
+		col: u16,
+		scalar: Vec<u8>,
+	}
+
+	async fn reconstruct_app_rows(
+		rpc: &Rpc<AvailConfig>,
+		app_id: AppId,
+		cells: Vec<DataCell>,
+		block_hash: H256,
+	) -> anyhow::Result<Vec<Option<Vec<u8>>>> {
+		let mut params = RpcParams::new();
+		params.push(app_id)?;
+		params.push(cells)?;
+		params.push(Some(block_hash))?;
+		let rows: Vec<Option<Vec<u8>>> = rpc.request("kate_reconstructAppRows", params).await?;
+		Ok(rows)
+	}
+
+	#[derive(serde::Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct Row {
+		row_index: u32,
+		scalars: Vec<Vec<u8>>,
+	}
+
+	#[derive(serde::Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct RowsV2 {
+		version: u8,
+		rows: Vec<Row>,
+	}
+
+	async fn query_rows_v2(
+		rpc: &Rpc<AvailConfig>,
+		rows: &[usize],
+		block_hash: H256,
+	) -> anyhow::Result<RowsV2> {
+		let mut params = RpcParams::new();
+		params.push(rows)?;
+		params.push(Some(block_hash))?;
+		let rows: RowsV2 = rpc.request("kate_queryRowsV2", params).await?;
+		Ok(rows)
+	}
+
+	#[derive(serde::Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct CellPosition {
+		row: u32,
+		col: u32,
+	}
+
+	#[derive(serde::Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct CellProof {
+		cell: CellPosition,
+		proof: Vec<u8>,
+		scalar: Vec<u8>,
+	}
+
+	#[derive(serde::Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct ProofsV2 {
+		version: u8,
+		proofs: Vec<CellProof>,
+	}
+
+	async fn query_proof_v2(
+		rpc: &Rpc<AvailConfig>,
+		cells: Vec<Cell>,
+		block_hash: H256,
+	) -> anyhow::Result<ProofsV2> {
+		let mut params = RpcParams::new();
+		params.push(cells)?;
+		params.push(Some(block_hash))?;
+		let proofs: ProofsV2 = rpc.request("kate_queryProofV2", params).await?;
+		Ok(proofs)
+	}
+
+	#[derive(serde::Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct ExtrinsicProof {
+		extrinsics_root: H256,
+		transaction_index: u32,
+		extrinsic: Vec<u8>,
+		proof: Vec<Vec<u8>>,
+		signer: Option<String>,
+		app_id: Option<AppId>,
+	}
+
+	async fn query_extrinsic_proof(
+		rpc: &Rpc<AvailConfig>,
+		transaction_index: u32,
+		block_hash: H256,
+	) -> anyhow::Result<ExtrinsicProof> {
+		let mut params = RpcParams::new();
+		params.push(transaction_index)?;
+		params.push(Some(block_hash))?;
+		let proof: ExtrinsicProof = rpc.request("kate_queryExtrinsicProof", params).await?;
+
+		Ok(proof)
+	}
+
 	async fn query_proof(
 		rpc: &Rpc<AvailConfig>,
 		cells: Vec<Cell>,
@@ -174,6 +422,35 @@ pub mod tests {
 		Ok(proof)
 	}
 
+	#[derive(serde::Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct CellBlock {
+		start_x: u32,
+		start_y: u32,
+		end_x: u32,
+		end_y: u32,
+	}
+
+	#[derive(serde::Deserialize)]
+	struct MultiProof {
+		proof: Vec<u8>,
+		evals: Vec<Vec<u8>>,
+		block: CellBlock,
+	}
+
+	async fn query_multiproof(
+		rpc: &Rpc<AvailConfig>,
+		cells: Vec<Cell>,
+		block_hash: H256,
+	) -> anyhow::Result<Vec<MultiProof>> {
+		let mut params = RpcParams::new();
+		params.push(cells)?;
+		params.push(Some(block_hash))?;
+		let proofs: Vec<MultiProof> = rpc.request("kate_queryMultiProof", params).await?;
+
+		Ok(proofs)
+	}
+
 	#[async_std::test]
 	pub async fn rpc_query_rows_test() {
 		let client = establish_a_connection().await.unwrap();
@@ -215,6 +492,44 @@ pub mod tests {
 		}
 	}
 
+	#[async_std::test]
+	pub async fn rpc_query_columns_test() {
+		let client = establish_a_connection().await.unwrap();
+		let (txc, rpc) = (client.tx(), client.rpc());
+
+		let example_data = "ExampleData".as_bytes();
+		assert_eq!(example_data.len(), 11);
+
+		let block_hash = send_da_example_data(&txc, example_data).await.unwrap();
+		let submitted_block = get_submitted_block(rpc, block_hash).await.unwrap();
+		let app_extrinsics = get_block_app_extrinsics(&submitted_block).unwrap();
+
+		// Grid Creation
+		let grid = EvaluationGrid::from_extrinsics(app_extrinsics, 4, 256, 256, [0u8; 32]).unwrap();
+		let extended_grid = grid.extend_columns(NonZeroU16::new(2).unwrap()).unwrap();
+		assert_eq!(extended_grid.dims(), Dimensions::new(2, 8).unwrap());
+
+		// RPC call: Querying non existing columns should fail
+		assert!(query_columns(rpc, &[8], block_hash).await.is_err());
+
+		// RPC call: Querying existing columns should NOT fail
+		let actual_columns = query_columns(rpc, &[0, 7], block_hash).await.unwrap();
+
+		let expected_columns: Vec<Vec<u8>> = [0usize, 7]
+			.iter()
+			.map(|&col| {
+				(0..2usize)
+					.flat_map(|row| {
+						let scalar = extended_grid.get::<usize, usize>(row, col).unwrap();
+						scalar.to_bytes().unwrap()
+					})
+					.collect()
+			})
+			.collect();
+
+		assert_eq!(actual_columns, expected_columns);
+	}
+
 	#[async_std::test]
 	pub async fn rpc_query_app_data_test() {
 		let client = establish_a_connection().await.unwrap();
@@ -301,6 +616,49 @@ pub mod tests {
 		dbg!(&expected_proof);
 	}
 
+	#[async_std::test]
+	pub async fn rpc_query_multiproof_test() {
+		let client = establish_a_connection().await.unwrap();
+		let (txc, rpc) = (client.tx(), client.rpc());
+
+		let example_data = "ExampleData".as_bytes();
+		assert_eq!(example_data.len(), 11);
+
+		let block_hash = send_da_example_data(&txc, example_data).await.unwrap();
+		let submitted_block = get_submitted_block(rpc, block_hash).await.unwrap();
+		let app_extrinsics = get_block_app_extrinsics(&submitted_block).unwrap();
+
+		// Grid Creation
+		let grid = EvaluationGrid::from_extrinsics(app_extrinsics, 4, 256, 256, [0u8; 32]).unwrap();
+		let extended_grid = grid.extend_columns(NonZeroU16::new(2).unwrap()).unwrap();
+		assert_eq!(extended_grid.dims(), Dimensions::new(2, 8).unwrap());
+
+		// All cells of the grid fit into one tile, so only one multiproof is expected.
+		let cells = vec![
+			Cell::new(BlockLengthRows(0), BlockLengthColumns(0)),
+			Cell::new(BlockLengthRows(0), BlockLengthColumns(7)),
+			Cell::new(BlockLengthRows(1), BlockLengthColumns(3)),
+		];
+
+		// RPC call
+		let proofs = query_multiproof(rpc, cells, block_hash).await.unwrap();
+		assert_eq!(proofs.len(), 1);
+
+		let proof = &proofs[0];
+		assert_eq!(proof.proof.len(), 48);
+		assert_eq!((proof.block.start_x, proof.block.end_x), (0, 8));
+		assert_eq!((proof.block.start_y, proof.block.end_y), (0, 2));
+		for (y, row) in proof.evals.iter().enumerate() {
+			let expected: Vec<u8> = extended_grid
+				.row(y)
+				.unwrap()
+				.iter()
+				.flat_map(|s| s.to_bytes().unwrap())
+				.collect();
+			assert_eq!(row, &expected);
+		}
+	}
+
 	#[async_std::test]
 	pub async fn rpc_query_proof_test_2() {
 		let client = establish_a_connection().await.unwrap();
@@ -402,4 +760,374 @@ pub mod tests {
 		assert_eq!(actual_proof.data_proof.blob_root, expected_proof_root.root);
 		assert_eq!(actual_proof.data_proof.bridge_root, H256::zero());
 	}
+
+	#[async_std::test]
+	pub async fn rpc_query_all_data_proofs_v2_test() {
+		let client = establish_a_connection().await.unwrap();
+		let (txc, rpc) = (client.tx(), client.rpc());
+
+		let example_data = "ExampleData".as_bytes();
+		let block_hash = send_da_example_data(&txc, example_data).await.unwrap();
+
+		// Every proof of the batch must match the single proof of its transaction.
+		let proofs = query_all_data_proofs_v2(rpc, block_hash).await.unwrap();
+		assert!(proofs.iter().any(|(index, _)| *index == 1));
+		for (index, proof) in proofs {
+			let expected = query_data_proof_v2(rpc, index, block_hash).await.unwrap();
+			assert_eq!(proof.data_proof.data_root, expected.data_proof.data_root);
+			assert_eq!(proof.data_proof.proof, expected.data_proof.proof);
+			assert_eq!(proof.data_proof.leaf, expected.data_proof.leaf);
+		}
+	}
+
+	#[async_std::test]
+	pub async fn rpc_get_blob_by_hash_test() {
+		let client = establish_a_connection().await.unwrap();
+		let (txc, rpc) = (client.tx(), client.rpc());
+
+		let example_data = "ExampleData".as_bytes();
+		let block_hash = send_da_example_data(&txc, example_data).await.unwrap();
+		let data_hash = H256(blake2_256(example_data));
+
+		// Unknown blobs are not found
+		assert!(get_blob_by_hash(rpc, H256::zero(), Some(block_hash))
+			.await
+			.is_err());
+
+		let blob = get_blob_by_hash(rpc, data_hash, Some(block_hash))
+			.await
+			.unwrap();
+		assert_eq!(blob.block_hash, block_hash);
+		assert_eq!(blob.transaction_index, 1);
+		assert_eq!(blob.data, example_data);
+
+		let expected = query_data_proof_v2(rpc, 1, block_hash).await.unwrap();
+		let proof = blob.proof.unwrap();
+		assert_eq!(proof.data_proof.data_root, expected.data_proof.data_root);
+		assert_eq!(proof.data_proof.proof, expected.data_proof.proof);
+	}
+
+	#[async_std::test]
+	pub async fn rpc_get_assembled_blob_test() {
+		let client = establish_a_connection().await.unwrap();
+		let (txc, rpc) = (client.tx(), client.rpc());
+
+		let chunks: [&[u8]; 2] = [b"ExampleChunk0", b"ExampleChunk1"];
+		for chunk in chunks {
+			let data = Value::from_bytes(chunk);
+			send_da_dynamic_call(&txc, "submit_data_chunk", vec![data])
+				.await
+				.unwrap();
+		}
+		// The root is the hash of the concatenated hashes of the chunks.
+		let chunk_hashes = chunks.map(blake2_256).concat();
+		let blob_root = H256(blake2_256(&chunk_hashes));
+		let block_hash = send_da_dynamic_call(&txc, "finalize_blob", vec![])
+			.await
+			.unwrap();
+
+		let blob = get_assembled_blob(rpc, blob_root, Some(block_hash))
+			.await
+			.unwrap();
+		assert_eq!(blob.blob_root, blob_root);
+		assert_eq!(blob.data, chunks.concat());
+		assert_eq!(blob.chunks.len(), chunks.len());
+
+		// Each chunk is proven like any other submitted data.
+		for (chunk, data) in blob.chunks.into_iter().zip(chunks) {
+			assert_eq!(chunk.data_hash, H256(blake2_256(data)));
+			let expected = query_data_proof_v2(rpc, chunk.transaction_index, chunk.block_hash)
+				.await
+				.unwrap();
+			let proof = chunk.proof.unwrap();
+			assert_eq!(proof.data_proof.data_root, expected.data_proof.data_root);
+			assert_eq!(proof.data_proof.proof, expected.data_proof.proof);
+		}
+
+		// Unknown blobs are not found
+		assert!(get_assembled_blob(rpc, H256::zero(), Some(block_hash))
+			.await
+			.is_err());
+
+		// Removed blobs are not found after their removal.
+		let root = Value::from_bytes(blob_root.as_bytes());
+		let removed_at = send_da_dynamic_call(&txc, "remove_blob", vec![root])
+			.await
+			.unwrap();
+		assert!(get_assembled_blob(rpc, blob_root, Some(removed_at))
+			.await
+			.is_err());
+		assert!(get_assembled_blob(rpc, blob_root, Some(block_hash))
+			.await
+			.is_ok());
+	}
+
+	#[async_std::test]
+	pub async fn rpc_query_block_da_info_test() {
+		let client = establish_a_connection().await.unwrap();
+		let (txc, rpc) = (client.tx(), client.rpc());
+
+		let example_data = "ExampleData".as_bytes();
+		let block_hash = send_da_example_data(&txc, example_data).await.unwrap();
+		let submitted_block = get_submitted_block(rpc, block_hash).await.unwrap();
+		let (commitment, rows, cols, data_root) = match submitted_block.block.header.extension {
+			HeaderExtension::V1(_) => panic!("Unsupported header extension version"),
+			HeaderExtension::V2(ext) => (
+				ext.commitment.commitment,
+				ext.commitment.rows,
+				ext.commitment.cols,
+				ext.commitment.data_root,
+			),
+		};
+
+		let da_info = query_block_da_info(rpc, block_hash).await.unwrap();
+		assert_eq!(da_info.version, 2);
+		assert_eq!(
+			(da_info.dimensions.rows, da_info.dimensions.cols),
+			(rows, cols)
+		);
+		assert_eq!(
+			(
+				da_info.extended_dimensions.rows,
+				da_info.extended_dimensions.cols
+			),
+			(rows * 2, cols)
+		);
+		assert_eq!(da_info.commitments.len(), usize::from(rows) * 2);
+		assert_eq!(da_info.commitments.concat(), commitment);
+		assert_eq!(da_info.data_root, data_root);
+		assert!(da_info.padding_ratio > 0.0 && da_info.padding_ratio < 1.0);
+	}
+
+	#[async_std::test]
+	pub async fn rpc_kate_verifier_test() {
+		use kate_recovery::matrix::Position;
+		use kate_verifier::{verify_blob, verify_cells, verify_data_proof_v2, verify_rows};
+
+		let client = establish_a_connection().await.unwrap();
+		let (txc, rpc) = (client.tx(), client.rpc());
+
+		let example_data = "ExampleData".as_bytes();
+		let block_hash = send_da_example_data(&txc, example_data).await.unwrap();
+		let submitted_block = get_submitted_block(rpc, block_hash).await.unwrap();
+		let (commitment, rows, cols, data_root) = match submitted_block.block.header.extension {
+			HeaderExtension::V1(_) => panic!("Unsupported header extension version"),
+			HeaderExtension::V2(ext) => (
+				ext.commitment.commitment,
+				ext.commitment.rows,
+				ext.commitment.cols,
+				ext.commitment.data_root,
+			),
+		};
+		let commitments = kate_verifier::GridCommitments {
+			commitments: &commitment,
+			extended_dims: Dimensions::new(rows * 2, cols).unwrap(),
+		};
+		let srs = kate::couscous::multiproof_params();
+
+		// Node responses are verified as they come out of the RPC, without rebuilding the grid.
+		let row_indexes = [0u32, 1];
+		let actual_rows = query_rows(rpc, &[0, 1], block_hash).await.unwrap();
+		assert_eq!(
+			verify_rows(&srs, &commitments, &row_indexes, &actual_rows),
+			Ok(true)
+		);
+
+		let cells = (0..2u32)
+			.flat_map(|row| (0..cols).map(move |col| (row, col)))
+			.collect::<Vec<_>>();
+		let positions = cells
+			.iter()
+			.map(|&(row, col)| Position { row, col })
+			.collect::<Vec<_>>();
+		let cells = cells
+			.into_iter()
+			.map(|(row, col)| Cell::new(BlockLengthRows(row), BlockLengthColumns(col.into())))
+			.collect::<Vec<_>>();
+		let actual_proof = query_proof(rpc, cells, block_hash).await.unwrap();
+		assert_eq!(
+			verify_cells(&srs, &commitments, &positions, &actual_proof),
+			Ok(true)
+		);
+
+		let data_proof = query_data_proof_v2(rpc, 1, block_hash).await.unwrap();
+		assert_eq!(verify_data_proof_v2(data_root, &data_proof), Ok(true));
+		assert_eq!(verify_blob(data_root, &data_proof, example_data), Ok(true));
+	}
+
+	#[async_std::test]
+	pub async fn rpc_reconstruct_app_rows_test() {
+		let client = establish_a_connection().await.unwrap();
+		let (txc, rpc) = (client.tx(), client.rpc());
+
+		let example_data = "ExampleData".as_bytes();
+		let block_hash = send_da_example_data(&txc, example_data).await.unwrap();
+
+		// The extended row holds half of the cells of each column of the extended grid.
+		let extended_row = query_rows(rpc, &[1], block_hash).await.unwrap().remove(0);
+		let cells = extended_row
+			.chunks(32)
+			.enumerate()
+			.map(|(col, scalar)| DataCell {
+				row: 1,
+				col: col as u16,
+				scalar: scalar.to_vec(),
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(cells.len(), 8);
+
+		let actual_rows = reconstruct_app_rows(rpc, AppId(0), cells, block_hash)
+			.await
+			.unwrap();
+		let expected_rows = query_app_data(rpc, AppId(0), block_hash).await.unwrap();
+		assert_eq!(actual_rows, expected_rows);
+
+		// Less than half of a column is not enough.
+		let cells = extended_row
+			.chunks(32)
+			.enumerate()
+			.skip(1)
+			.map(|(col, scalar)| DataCell {
+				row: 1,
+				col: col as u16,
+				scalar: scalar.to_vec(),
+			})
+			.collect::<Vec<_>>();
+		assert!(reconstruct_app_rows(rpc, AppId(0), cells, block_hash)
+			.await
+			.is_err());
+	}
+
+	#[async_std::test]
+	pub async fn rpc_query_rows_v2_test() {
+		let client = establish_a_connection().await.unwrap();
+		let (txc, rpc) = (client.tx(), client.rpc());
+
+		let example_data = "ExampleData".as_bytes();
+		let block_hash = send_da_example_data(&txc, example_data).await.unwrap();
+
+		let expected_rows = query_rows(rpc, &[1, 0], block_hash).await.unwrap();
+		let actual = query_rows_v2(rpc, &[1, 0], block_hash).await.unwrap();
+
+		assert_eq!(actual.version, 2);
+		assert_eq!(actual.rows.len(), 2);
+		for (row, (index, expected)) in actual.rows.iter().zip([1, 0].iter().zip(expected_rows)) {
+			assert_eq!(row.row_index, *index);
+			assert_eq!(row.scalars.len(), 8);
+			assert!(row.scalars.iter().all(|scalar| scalar.len() == 32));
+			assert_eq!(row.scalars.concat(), expected);
+		}
+	}
+
+	#[async_std::test]
+	pub async fn rpc_query_proof_v2_test() {
+		let client = establish_a_connection().await.unwrap();
+		let (txc, rpc) = (client.tx(), client.rpc());
+
+		let example_data = "ExampleData".as_bytes();
+		let block_hash = send_da_example_data(&txc, example_data).await.unwrap();
+
+		let cells = vec![
+			Cell::new(BlockLengthRows(1), BlockLengthColumns(3)),
+			Cell::new(BlockLengthRows(0), BlockLengthColumns(7)),
+		];
+		let expected_proof = query_proof(rpc, cells.clone(), block_hash).await.unwrap();
+		let actual = query_proof_v2(rpc, cells, block_hash).await.unwrap();
+
+		assert_eq!(actual.version, 2);
+		let positions = actual
+			.proofs
+			.iter()
+			.map(|proof| (proof.cell.row, proof.cell.col))
+			.collect::<Vec<_>>();
+		assert_eq!(positions, vec![(1, 3), (0, 7)]);
+
+		let actual_proof = actual
+			.proofs
+			.iter()
+			.flat_map(|proof| {
+				assert_eq!((proof.proof.len(), proof.scalar.len()), (48, 32));
+				[proof.proof.clone(), proof.scalar.clone()].concat()
+			})
+			.collect::<Vec<u8>>();
+		assert_eq!(actual_proof, expected_proof);
+	}
+
+	#[async_std::test]
+	pub async fn rpc_query_extrinsic_proof_test() {
+		let client = establish_a_connection().await.unwrap();
+		let (txc, rpc) = (client.tx(), client.rpc());
+
+		let example_data = "ExampleData".as_bytes();
+		let block_hash = send_da_example_data(&txc, example_data).await.unwrap();
+		let submitted_block = get_submitted_block(rpc, block_hash).await.unwrap();
+
+		// The `submit_data` extrinsic is the only signed one.
+		let (index, extrinsic) = submitted_block
+			.block
+			.extrinsics
+			.iter()
+			.enumerate()
+			.find(|(_, tx)| {
+				AppUncheckedExtrinsic::try_from((*tx).clone())
+					.map(|unchecked| unchecked.signature.is_some())
+					.unwrap_or(false)
+			})
+			.unwrap();
+
+		let actual = query_extrinsic_proof(rpc, index as u32, block_hash)
+			.await
+			.unwrap();
+
+		assert_eq!(
+			actual.extrinsics_root,
+			submitted_block.block.header.extrinsics_root
+		);
+		assert_eq!(actual.transaction_index, index as u32);
+		assert_eq!(actual.extrinsic, extrinsic.0);
+		assert!(!actual.proof.is_empty());
+		assert_eq!(
+			actual.signer,
+			Some(AccountKeyring::Alice.to_account_id().to_string())
+		);
+		assert_eq!(actual.app_id, Some(AppId(0)));
+
+		let out_of_range = submitted_block.block.extrinsics.len() as u32;
+		assert!(query_extrinsic_proof(rpc, out_of_range, block_hash)
+			.await
+			.is_err());
+	}
+
+	#[async_std::test]
+	pub async fn rpc_query_finality_options_test() {
+		let client = establish_a_connection().await.unwrap();
+		let (txc, rpc) = (client.tx(), client.rpc());
+
+		let example_data = "ExampleData".as_bytes();
+		let block_hash = send_da_example_data(&txc, example_data).await.unwrap();
+
+		// A finalized block satisfies every finality.
+		let expected_rows = query_rows(rpc, &[0], block_hash).await.unwrap();
+		for finality in ["finalized", "best", "any"] {
+			let actual_rows = query_rows_with_finality(rpc, &[0], block_hash, finality)
+				.await
+				.unwrap();
+			assert_eq!(actual_rows, expected_rows);
+		}
+
+		// Blocks which are not finalized yet are only served to non-strict consumers.
+		let best_hash = rpc.block_hash(None).await.unwrap().unwrap();
+		let best = rpc.header(Some(best_hash)).await.unwrap().unwrap();
+		let finalized_hash = rpc.finalized_head().await.unwrap();
+		let finalized = rpc.header(Some(finalized_hash)).await.unwrap().unwrap();
+		if best.number > finalized.number {
+			let error = query_rows_with_finality(rpc, &[0], best_hash, "finalized")
+				.await
+				.unwrap_err();
+			assert!(error.to_string().contains("is not finalized"));
+			assert!(query_rows_with_finality(rpc, &[0], best_hash, "best")
+				.await
+				.is_ok());
+		}
+	}
 }
@@ -16,6 +16,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use std::net::SocketAddr;
+
+use kate_rpc::rate_limit::{Limit, RateLimits};
+
 /// An overarching CLI command definition.
 #[derive(Debug, clap::Parser)]
 pub struct Cli {
@@ -71,12 +75,94 @@ pub struct Cli {
 	/// the more space will the node use.
 	#[arg(long, value_name = "MiB", default_value_t = 1024)]
 	pub poly_grid_cach_size: u64,
+
+	/// Represents the maximum size (in MiBs) for the on-disk evaluation grid cache.
+	///
+	/// The disk cache keeps the grids of finalized blocks between restarts, and it is used
+	/// when the in-memory caches miss. Zero disables it.
+	#[arg(long, value_name = "MiB", default_value_t = 0)]
+	pub grid_disk_cache_size: u64,
+
+	/// Enable Kate grid pre-warming
+	///
+	/// The evaluation and polynomial grids of every newly finalized block are built in
+	/// background, so the first Kate RPC calls on that block hit the caches.
+	#[clap(long = "enable-kate-grid-prewarm", default_value_t = false)]
+	pub kate_grid_prewarm_enabled: bool,
+
+	/// The maximum number of blocks whose grids are pre-warmed concurrently.
+	#[arg(long, default_value_t = 2, value_parser = kate_grid_prewarm_workers_range)]
+	pub kate_grid_prewarm_workers: usize,
+
+	/// Enable Kate blob index
+	///
+	/// The location of every blob submitted in a finalized block is indexed by its data hash,
+	/// so `kate_getBlobByHash` can find it without the block hash. Only blocks finalized while
+	/// the index is enabled are indexed.
+	#[clap(long = "enable-kate-blob-index", default_value_t = false)]
+	pub kate_blob_index_enabled: bool,
+
+	/// Address of the binary HTTP gateway of Kate RPC, e.g. `127.0.0.1:9955`.
+	///
+	/// The gateway serves rows, app data, cell proofs and indexed blobs as raw bytes or SCALE,
+	/// using the Kate RPC caches. It is disabled by default.
+	#[arg(long, value_name = "ADDR")]
+	pub kate_gateway_addr: Option<SocketAddr>,
+
+	/// Address of the Kate RPC server, e.g. `127.0.0.1:9956`.
+	///
+	/// The server serves the Kate RPCs over HTTP and WebSocket, and charges every call to the
+	/// rate limits of its connection and IP address. It is required by the Kate RPC rate limits.
+	#[arg(long, value_name = "ADDR")]
+	pub kate_rpc_addr: Option<SocketAddr>,
+
+	/// Kate RPC tokens refilled every second for each connection. Zero disables the limit.
+	///
+	/// Every Kate RPC call costs tokens depending on the work it triggers: building the grid of
+	/// a block, reading rows, computing cell proofs or data proofs. Rate limited Kate RPCs are
+	/// served by the Kate RPC server (`--kate-rpc-addr`) instead of the node RPC server, and
+	/// the Kate gateway (`--kate-gateway-addr`) is charged the same way.
+	#[arg(long, value_name = "TOKENS", default_value_t = 0)]
+	pub kate_rate_limit_per_connection: u64,
+
+	/// Kate RPC tokens refilled every second for each IP address. Zero disables the limit.
+	#[arg(long, value_name = "TOKENS", default_value_t = 0)]
+	pub kate_rate_limit_per_ip: u64,
+
+	/// Seconds of Kate RPC tokens that can be spent in one burst.
+	#[arg(long, value_name = "SECONDS", default_value_t = 10, value_parser = kate_rate_limit_burst_range)]
+	pub kate_rate_limit_burst: u64,
+}
+
+impl Cli {
+	/// Kate RPC rate limits set by the command line.
+	pub fn kate_rate_limits(&self) -> RateLimits {
+		let limit = |tokens_per_second: u64| {
+			(tokens_per_second > 0).then(|| Limit {
+				tokens_per_second,
+				burst: tokens_per_second.saturating_mul(self.kate_rate_limit_burst),
+			})
+		};
+
+		RateLimits {
+			per_connection: limit(self.kate_rate_limit_per_connection),
+			per_ip: limit(self.kate_rate_limit_per_ip),
+		}
+	}
 }
 
 fn kate_max_cells_size_upper_bound(s: &str) -> Result<usize, String> {
 	clap_num::number_range(s, 0, 10_000)
 }
 
+fn kate_grid_prewarm_workers_range(s: &str) -> Result<usize, String> {
+	clap_num::number_range(s, 1, 64)
+}
+
+fn kate_rate_limit_burst_range(s: &str) -> Result<u64, String> {
+	clap_num::number_range(s, 1, 3600)
+}
+
 /// Possible subcommands of the main binary.
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, clap::Subcommand)]
@@ -207,6 +207,9 @@ pub fn run() -> Result<()> {
 					cli.kate_rpc_metrics_enabled,
 					cli.eval_grid_cache_size,
 					cli.poly_grid_cach_size,
+					cli.grid_disk_cache_size,
+					cli.kate_blob_index_enabled,
+					cli.kate_rate_limits(),
 				)?;
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
@@ -226,6 +229,9 @@ pub fn run() -> Result<()> {
 					cli.kate_rpc_metrics_enabled,
 					cli.eval_grid_cache_size,
 					cli.poly_grid_cach_size,
+					cli.grid_disk_cache_size,
+					cli.kate_blob_index_enabled,
+					cli.kate_rate_limits(),
 				)?;
 				Ok((cmd.run(client, config.database), task_manager))
 			})
@@ -245,6 +251,9 @@ pub fn run() -> Result<()> {
 					cli.kate_rpc_metrics_enabled,
 					cli.eval_grid_cache_size,
 					cli.poly_grid_cach_size,
+					cli.grid_disk_cache_size,
+					cli.kate_blob_index_enabled,
+					cli.kate_rate_limits(),
 				)?;
 				Ok((cmd.run(client, config.chain_spec), task_manager))
 			})
@@ -265,6 +274,9 @@ pub fn run() -> Result<()> {
 					cli.kate_rpc_metrics_enabled,
 					cli.eval_grid_cache_size,
 					cli.poly_grid_cach_size,
+					cli.grid_disk_cache_size,
+					cli.kate_blob_index_enabled,
+					cli.kate_rate_limits(),
 				)?;
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
@@ -289,6 +301,9 @@ pub fn run() -> Result<()> {
 					cli.kate_rpc_metrics_enabled,
 					cli.eval_grid_cache_size,
 					cli.poly_grid_cach_size,
+					cli.grid_disk_cache_size,
+					cli.kate_blob_index_enabled,
+					cli.kate_rate_limits(),
 				)?;
 				let aux_revert = Box::new(|client: Arc<FullClient>, backend, blocks| {
 					sc_consensus_babe::revert(client.clone(), backend, blocks)?;
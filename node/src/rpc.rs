@@ -40,7 +40,8 @@ use da_runtime::{
 	apis::DataAvailApi, AccountId, Balance, BlockNumber, Hash, Index, NodeBlock as Block,
 };
 use jsonrpsee::RpcModule;
-use sc_client_api::AuxStore;
+use kate_rpc::Kate;
+use sc_client_api::{AuxStore, BlockchainEvents};
 use sc_consensus_babe::BabeWorkerHandle;
 use sc_consensus_grandpa::{
 	FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState,
@@ -93,10 +94,8 @@ pub struct FullDeps<C, P, SC, B> {
 	pub babe: BabeDeps,
 	/// GRANDPA specific dependencies.
 	pub grandpa: GrandpaDeps<B>,
-	/// The maximum number of cells that can be requested in one go.
-	pub kate_max_cells_size: usize,
-	/// Enable Kate RPCs
-	pub kate_rpc_enabled: bool,
+	/// Kate RPC instance, if Kate RPCs are enabled.
+	pub kate: Option<Kate<C, Block>>,
 	/// Enable Kate RPCs Metrics
 	///
 	/// Should not be used unless unless you know what you're doing.
@@ -107,14 +106,13 @@ pub struct FullDeps<C, P, SC, B> {
 pub fn create_full<C, P, SC, B>(
 	deps: FullDeps<C, P, SC, B>,
 	backend: Arc<B>,
-	eval_grid_cache_size: u64,
-	poly_grid_cach_size: u64,
 ) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: ProvideRuntimeApi<Block>
 		+ sc_client_api::BlockBackend<Block>
 		+ HeaderBackend<Block>
 		+ AuxStore
+		+ BlockchainEvents<Block>
 		+ HeaderMetadata<Block, Error = BlockChainError>
 		+ Sync
 		+ Send
@@ -130,8 +128,7 @@ where
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 	B::State: sc_client_api::backend::StateBackend<sp_runtime::traits::HashingFor<Block>>,
 {
-	use kate_rpc::metrics::KateApiMetricsServer;
-	use kate_rpc::{Kate, KateApiServer};
+	use kate_rpc::admin::{KateAdmin, KateAdminApiServer};
 	use mmr_rpc::{Mmr, MmrApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use sc_consensus_babe_rpc::{Babe, BabeApiServer};
@@ -150,8 +147,7 @@ where
 		deny_unsafe,
 		babe,
 		grandpa,
-		kate_max_cells_size,
-		kate_rpc_enabled,
+		kate,
 		kate_rpc_metrics_enabled,
 	} = deps;
 
@@ -221,25 +217,47 @@ where
 		.into_rpc(),
 	)?;
 
-	io.merge(StateMigration::new(client.clone(), backend, deny_unsafe).into_rpc())?;
+	io.merge(StateMigration::new(client.clone(), backend.clone(), deny_unsafe).into_rpc())?;
 
-	if kate_rpc_metrics_enabled {
-		io.merge(KateApiMetricsServer::into_rpc(Kate::<C, Block>::new(
-			client.clone(),
-			kate_max_cells_size,
-			eval_grid_cache_size,
-			poly_grid_cach_size,
-		)))?;
+	if let Some(kate) = kate {
+		io.merge(KateAdmin::new(kate.clone(), deny_unsafe).into_rpc())?;
+		// Rate limited Kate RPCs are served by the Kate RPC server, which knows their callers.
+		if kate.rate_limiter().is_none() {
+			io.merge(create_kate(kate, backend, kate_rpc_metrics_enabled)?)?;
+		}
 	}
 
-	if kate_rpc_enabled || kate_rpc_metrics_enabled {
-		io.merge(KateApiServer::into_rpc(Kate::<C, Block>::new(
-			client,
-			kate_max_cells_size,
-			eval_grid_cache_size,
-			poly_grid_cach_size,
-		)))?;
+	Ok(io)
+}
+
+/// Instantiate the Kate RPC extensions, the admin RPCs excepted.
+pub fn create_kate<C, B>(
+	kate: Kate<C, Block>,
+	backend: Arc<B>,
+	kate_rpc_metrics_enabled: bool,
+) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
+where
+	C: ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ HeaderBackend<Block>
+		+ HeaderMetadata<Block, Error = BlockChainError>
+		+ BlockchainEvents<Block>
+		+ Sync
+		+ Send
+		+ 'static,
+	C::Api: DataAvailApi<Block>,
+	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
+{
+	use kate_rpc::chain_head::{KateChainHead, KateChainHeadApiServer};
+	use kate_rpc::metrics::KateApiMetricsServer;
+	use kate_rpc::KateApiServer;
+
+	let mut io = RpcModule::new(());
+	io.merge(KateChainHead::new(kate.clone(), backend).into_rpc())?;
+	if kate_rpc_metrics_enabled {
+		io.merge(KateApiMetricsServer::into_rpc(kate.clone()))?;
 	}
+	io.merge(KateApiServer::into_rpc(kate))?;
 
 	Ok(io)
 }
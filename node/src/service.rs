@@ -25,9 +25,13 @@ use codec::Encode;
 use da_runtime::{apis::RuntimeApi, NodeBlock as Block, Runtime};
 use frame_system_rpc_runtime_api::AccountNonceApi;
 use futures::prelude::*;
+use kate_rpc::{
+	finality::finalized_blocks,
+	rate_limit::{RateLimiter, RateLimits},
+};
 use pallet_transaction_payment::ChargeTransactionPayment;
 use sc_client_api::Backend;
-use sc_client_api::BlockBackend;
+use sc_client_api::{BlockBackend, BlockchainEvents};
 use sc_consensus_babe::{self, SlotProportion};
 pub use sc_executor::NativeElseWasmExecutor;
 use sc_network::{Event, NetworkEventStream, NetworkService};
@@ -38,9 +42,10 @@ use sc_service::{
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_api::ProvideRuntimeApi;
+use sp_consensus::SyncOracle;
 use sp_core::crypto::Pair;
 use sp_runtime::{generic::Era, traits::Block as BlockT, SaturatedConversion};
-use std::{path::Path, sync::Arc};
+use std::{net::SocketAddr, path::Path, sync::Arc};
 use substrate_prometheus_endpoint::{PrometheusError, Registry};
 
 use crate::rpc as node_rpc;
@@ -81,6 +86,9 @@ type FullGrandpaBlockImport =
 /// The transaction pool type definition.
 pub type TransactionPool = sc_transaction_pool::FullPool<Block, FullClient>;
 
+/// The Kate RPC type definition.
+pub type KateRpc = kate_rpc::Kate<FullClient, Block>;
+
 pub type BlockImport = crate::da_block_import::BlockImport<
 	FullBackend,
 	FullClient,
@@ -174,6 +182,9 @@ pub fn new_partial(
 	kate_rpc_metrics_enabled: bool,
 	eval_grid_cache_size: u64,
 	poly_grid_cach_size: u64,
+	grid_disk_cache_size: u64,
+	kate_blob_index_enabled: bool,
+	kate_rate_limits: RateLimits,
 ) -> Result<
 	sc_service::PartialComponents<
 		FullClient,
@@ -193,6 +204,7 @@ pub fn new_partial(
 			),
 			sc_consensus_grandpa::SharedVoterState,
 			Option<Telemetry>,
+			Option<KateRpc>,
 		),
 	>,
 	ServiceError,
@@ -284,6 +296,42 @@ pub fn new_partial(
 
 	let import_setup = (da_block_import, grandpa_link, babe_link);
 
+	let kate = if kate_rpc_enabled || kate_rpc_metrics_enabled {
+		let grid_disk_cache = if grid_disk_cache_size > 0 {
+			let path = config
+				.base_path
+				.config_dir(config.chain_spec.id())
+				.join("kate_grids");
+			let cache = kate_rpc::disk_cache::GridDiskCache::open(&path, grid_disk_cache_size)
+				.map_err(|e| ServiceError::Other(format!("Grid disk cache: {e:?}")))?;
+			Some(Arc::new(cache))
+		} else {
+			None
+		};
+
+		let blob_index = kate_blob_index_enabled
+			.then(|| Arc::new(kate_rpc::blob_index::BlobIndex::new(client.clone())));
+
+		let rate_limiter = (kate_rate_limits != RateLimits::default())
+			.then(|| Arc::new(RateLimiter::new(kate_rate_limits)));
+
+		// The same instance is shared by the RPCs and the grid pre-warming, so they use the same
+		// grid caches.
+		let kate = KateRpc::new(
+			client.clone(),
+			Arc::new(task_manager.spawn_handle()),
+			kate_max_cells_size,
+			eval_grid_cache_size,
+			poly_grid_cach_size,
+		)
+		.with_grid_disk_cache(grid_disk_cache)
+		.with_blob_index(blob_index)
+		.with_rate_limiter(rate_limiter);
+		Some(kate)
+	} else {
+		None
+	};
+
 	let (rpc_extensions_builder, rpc_setup) = {
 		let (_, grandpa_link, _) = &import_setup;
 
@@ -302,6 +350,7 @@ pub fn new_partial(
 		let select_chain = select_chain.clone();
 		let keystore = keystore_container.keystore();
 		let chain_spec = config.chain_spec.cloned_box();
+		let kate = kate.clone();
 
 		let rpc_backend = backend.clone();
 		let rpc_extensions_builder = move |deny_unsafe, subscription_executor| {
@@ -322,18 +371,11 @@ pub fn new_partial(
 					subscription_executor,
 					finality_provider: finality_proof_provider.clone(),
 				},
-				kate_max_cells_size,
-				kate_rpc_enabled,
+				kate: kate.clone(),
 				kate_rpc_metrics_enabled,
 			};
 
-			node_rpc::create_full(
-				deps,
-				rpc_backend.clone(),
-				eval_grid_cache_size,
-				poly_grid_cach_size,
-			)
-			.map_err(Into::into)
+			node_rpc::create_full(deps, rpc_backend.clone()).map_err(Into::into)
 		};
 
 		(rpc_extensions_builder, shared_voter_state2)
@@ -347,7 +389,13 @@ pub fn new_partial(
 		select_chain,
 		import_queue,
 		transaction_pool,
-		other: (rpc_extensions_builder, import_setup, rpc_setup, telemetry),
+		other: (
+			rpc_extensions_builder,
+			import_setup,
+			rpc_setup,
+			telemetry,
+			kate,
+		),
 	})
 }
 
@@ -379,6 +427,13 @@ pub fn new_full_base(
 	kate_rpc_metrics_enabled: bool,
 	eval_grid_cache_size: u64,
 	poly_grid_cach_size: u64,
+	grid_disk_cache_size: u64,
+	kate_grid_prewarm_enabled: bool,
+	kate_grid_prewarm_workers: usize,
+	kate_blob_index_enabled: bool,
+	kate_rate_limits: RateLimits,
+	kate_gateway_addr: Option<SocketAddr>,
+	kate_rpc_addr: Option<SocketAddr>,
 ) -> Result<NewFullBase, ServiceError> {
 	let hwbench = if !disable_hardware_benchmarks {
 		config.database.path().map(|database_path| {
@@ -397,7 +452,7 @@ pub fn new_full_base(
 		keystore_container,
 		select_chain,
 		transaction_pool,
-		other: (rpc_builder, import_setup, rpc_setup, mut telemetry),
+		other: (rpc_builder, import_setup, rpc_setup, mut telemetry, kate),
 	} = new_partial(
 		&config,
 		unsafe_da_sync,
@@ -406,6 +461,9 @@ pub fn new_full_base(
 		kate_rpc_metrics_enabled,
 		eval_grid_cache_size,
 		poly_grid_cach_size,
+		grid_disk_cache_size,
+		kate_blob_index_enabled,
+		kate_rate_limits,
 	)?;
 
 	let shared_voter_state = rpc_setup;
@@ -481,6 +539,89 @@ pub fn new_full_base(
 		}
 	}
 
+	if kate_blob_index_enabled {
+		if let Some(kate) = kate.clone() {
+			// Indexing reads blocks and writes the aux store, so it runs on its own blocking task.
+			task_manager.spawn_handle().spawn_blocking(
+				"kate-blob-index",
+				Some("kate-rpc"),
+				index_kate_blobs(client.clone(), kate),
+			);
+		} else {
+			log::warn!(
+				target: LOG_TARGET,
+				"Kate blob index requires Kate RPC (--enable-kate-rpc), so it is disabled"
+			);
+		}
+	}
+
+	if let Some(addr) = kate_rpc_addr {
+		if let Some(kate) = kate.clone() {
+			let rate_limiter = kate.rate_limiter();
+			let module = node_rpc::create_kate(kate, backend.clone(), kate_rpc_metrics_enabled)
+				.map_err(|e| ServiceError::Other(format!("Kate RPC server: {e:?}")))?;
+			task_manager.spawn_handle().spawn(
+				"kate-rpc-server",
+				Some("kate-rpc"),
+				kate_rpc::server::run(module, rate_limiter, addr),
+			);
+		} else {
+			log::warn!(
+				target: LOG_TARGET,
+				"Kate RPC server requires Kate RPC (--enable-kate-rpc), so it is disabled"
+			);
+		}
+	} else if kate_rate_limits != RateLimits::default() {
+		return Err(ServiceError::Other(
+			"Kate RPC rate limits require the Kate RPC server (--kate-rpc-addr)".into(),
+		));
+	}
+
+	if let Some(addr) = kate_gateway_addr {
+		if let Some(kate) = kate.clone() {
+			task_manager.spawn_handle().spawn(
+				"kate-gateway",
+				Some("kate-rpc"),
+				kate_rpc::gateway::run(kate, addr),
+			);
+		} else {
+			log::warn!(
+				target: LOG_TARGET,
+				"Kate gateway requires Kate RPC (--enable-kate-rpc), so it is disabled"
+			);
+		}
+	}
+
+	if grid_disk_cache_size > 0 {
+		if let Some(kate) = kate.clone() {
+			task_manager.spawn_handle().spawn(
+				"kate-grid-disk-prune",
+				Some("kate-rpc"),
+				prune_kate_grids(client.clone(), kate),
+			);
+		}
+	}
+
+	if kate_grid_prewarm_enabled {
+		if let Some(kate) = kate {
+			task_manager.spawn_handle().spawn(
+				"kate-grid-prewarm",
+				Some("kate-rpc"),
+				prewarm_kate_grids(
+					client.clone(),
+					sync_service.clone(),
+					kate,
+					kate_grid_prewarm_workers,
+				),
+			);
+		} else {
+			log::warn!(
+				target: LOG_TARGET,
+				"Kate grid pre-warming requires Kate RPC (--enable-kate-rpc), so it is disabled"
+			);
+		}
+	}
+
 	let (block_import, grandpa_link, babe_link) = import_setup;
 
 	(with_startup_data)(&block_import, &babe_link);
@@ -666,6 +807,13 @@ pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceE
 		cli.kate_rpc_metrics_enabled,
 		cli.eval_grid_cache_size,
 		cli.poly_grid_cach_size,
+		cli.grid_disk_cache_size,
+		cli.kate_grid_prewarm_enabled,
+		cli.kate_grid_prewarm_workers,
+		cli.kate_blob_index_enabled,
+		cli.kate_rate_limits(),
+		cli.kate_gateway_addr,
+		cli.kate_rpc_addr,
 	)
 	.map(|NewFullBase { task_manager, .. }| task_manager)?;
 
@@ -681,6 +829,53 @@ pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceE
 	Ok(task_manager)
 }
 
+/// Builds the Kate grids of every newly finalized block ahead of the RPC calls, using up to
+/// `workers` concurrent builds.
+///
+/// Blocks finalized during a major sync are skipped.
+async fn prewarm_kate_grids(
+	client: Arc<FullClient>,
+	sync_service: Arc<SyncingService<Block>>,
+	kate: KateRpc,
+	workers: usize,
+) {
+	finalized_blocks::<Block, _>(&*client)
+		.filter(move |_| future::ready(!sync_service.is_major_syncing()))
+		.for_each_concurrent(workers, |hash| {
+			let kate = kate.clone();
+			async move {
+				if let Err(e) = kate.prewarm_grids(hash).await {
+					log::warn!(target: LOG_TARGET, "Kate grids of {hash:?} cannot be pre-warmed: {e:?}");
+				}
+			}
+		})
+		.await
+}
+
+/// Removes the persisted Kate grids of the blocks pruned by every finalization.
+async fn prune_kate_grids(client: Arc<FullClient>, kate: KateRpc) {
+	let kate = &kate;
+	client
+		.finality_notification_stream()
+		.for_each(|_| kate.prune_persisted_grids())
+		.await
+}
+
+/// Adds the blobs of every newly finalized block to the Kate blob index.
+///
+/// Blocks are indexed one by one in finalization order, including the blocks finalized during a
+/// major sync, so the index has no gaps.
+async fn index_kate_blobs(client: Arc<FullClient>, kate: KateRpc) {
+	finalized_blocks::<Block, _>(&*client)
+		.for_each(|hash| {
+			if let Err(e) = kate.index_blobs(hash) {
+				log::warn!(target: LOG_TARGET, "Blobs of {hash:?} cannot be indexed: {e:?}");
+			}
+			future::ready(())
+		})
+		.await
+}
+
 fn extend_metrics(prometheus: &Registry) -> Result<(), PrometheusError> {
 	use avail_base::metrics::{AvailMetrics, AVAIL_METRICS};
 
@@ -10,17 +10,18 @@ use codec::{Decode, Encode};
 use frame_benchmarking::{
 	impl_benchmark_test_suite, v1::BenchmarkError, v2::*, whitelisted_caller,
 };
-use frame_support::traits::Get;
+use frame_support::traits::{Currency, Get};
 use frame_system::{
 	header_builder::hosted_header_builder, limits::BlockLength, submitted_data, RawOrigin,
 };
 use scale_info::{StaticTypeInfo, TypeInfo};
 use sp_core::H256;
 use sp_runtime::{
-	traits::{DispatchInfoOf, Dispatchable, SignedExtension},
+	traits::{Bounded, DispatchInfoOf, Dispatchable, SignedExtension, StaticLookup},
 	transaction_validity::{TransactionValidity, TransactionValidityError},
 };
 use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
 	fmt::Debug,
 	iter::{once, repeat},
 	vec,
@@ -110,6 +111,80 @@ where
 	B::try_from(raw).expect("Bounded fixed by `len` parameter .qed")
 }
 
+/// Funds `who` with enough balance to reserve any deposit.
+fn fund<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+/// Creates an application key of maximum length owned by a funded `owner`.
+fn create_funded_app_key<T: Config>(owner: &T::AccountId) -> AppKeyFor<T> {
+	fund::<T>(owner);
+	let key = generate_bounded::<AppKeyFor<T>>(T::MaxAppKeyLength::get());
+	Pallet::<T>::create_application_key(RawOrigin::Signed(owner.clone()).into(), key.clone())
+		.expect("Key does not exist yet .qed");
+	key
+}
+
+/// Sets metadata of maximum length on the application of `key`.
+fn set_max_metadata<T: Config>(owner: &T::AccountId, key: AppKeyFor<T>) {
+	let url = generate_bounded::<AppMetadataFor<T>>(T::MaxAppMetadataLength::get());
+	let description = url.clone();
+	Pallet::<T>::set_application_metadata(
+		RawOrigin::Signed(owner.clone()).into(),
+		key,
+		url,
+		description,
+	)
+	.expect("Owner is funded .qed");
+}
+
+/// Pending blob of `n` chunks.
+fn pending_chunks<T: Config>(n: u32) -> BlobChunksFor<T> {
+	let chunks = (0..n)
+		.map(|i| BlobChunk {
+			data_hash: H256::repeat_byte(i as u8),
+			len: T::MaxAppDataLength::get(),
+			block_number: frame_system::Pallet::<T>::block_number(),
+			extrinsic_index: i,
+		})
+		.collect::<Vec<_>>();
+	BlobChunksFor::<T>::try_from(chunks).expect("Bounded by `MaxBlobChunks` .qed")
+}
+
+/// Pending blob of `n` chunks of a funded `owner`, with its deposit reserved.
+fn pending_blob<T: Config>(owner: &T::AccountId, n: u32) -> PendingBlobFor<T> {
+	fund::<T>(owner);
+	let deposit = T::BlobDepositPerChunk::get()
+		.saturating_mul(n.into())
+		.saturating_add(T::BlobDepositBase::get());
+	T::Currency::reserve(owner, deposit).expect("Owner is funded .qed");
+	PendingBlob {
+		chunks: pending_chunks::<T>(n),
+		deposit,
+	}
+}
+
+/// `n` scheduled block length proposals, activated after block `100`.
+fn scheduled_block_lengths<T: Config>(n: u32) -> ScheduledBlockLengthsFor<T> {
+	let mut id = T::BlockLenProposalId::default();
+	let proposals = (0..n)
+		.map(|i| {
+			id = id
+				.checked_add(&One::one())
+				.expect("Proposal Id does not overflow .qed");
+			ScheduledBlockLength {
+				id: id.clone(),
+				rows: T::MinBlockRows::get(),
+				cols: T::MinBlockCols::get(),
+				activation: BlockNumberFor::<T>::from(100 + i),
+			}
+		})
+		.collect::<Vec<_>>();
+	LastBlockLenProposalId::<T>::put(id);
+	ScheduledBlockLengthsFor::<T>::try_from(proposals)
+		.expect("Bounded by `MaxScheduledBlockLengths` .qed")
+}
+
 fn submit_data_ext<
 	T: frame_system::Config + Send + Sync + pallet::Config + Debug + StaticTypeInfo,
 >(
@@ -171,6 +246,7 @@ mod benchmarks {
 	#[benchmark]
 	fn create_application_key() -> Result<(), BenchmarkError> {
 		let caller = whitelisted_caller::<T::AccountId>();
+		fund::<T>(&caller);
 		let origin = RawOrigin::Signed(caller.clone());
 		let max_key_len = T::MaxAppKeyLength::get();
 		let key = generate_bounded::<AppKeyFor<T>>(max_key_len);
@@ -184,13 +260,184 @@ mod benchmarks {
 			info,
 			Some(AppKeyInfoFor::<T> {
 				owner: caller,
-				id: AppId(3)
+				id: AppId(3),
+				deposit: T::AppKeyDeposit::get(),
 			})
 		);
 
 		Ok(())
 	}
 
+	// Worst case: the application has metadata whose deposit is moved to the new owner.
+	#[benchmark]
+	fn transfer_application_key() -> Result<(), BenchmarkError> {
+		let caller = whitelisted_caller::<T::AccountId>();
+		let key = create_funded_app_key::<T>(&caller);
+		set_max_metadata::<T>(&caller, key.clone());
+		let new_owner: T::AccountId = account("new_owner", 0, 0);
+		T::Currency::make_free_balance_be(&new_owner, T::Currency::minimum_balance());
+		let new_owner_lookup = T::Lookup::unlookup(new_owner.clone());
+		let id = Pallet::<T>::application_key(&key)
+			.map(|info| info.id)
+			.unwrap();
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			key.clone(),
+			new_owner_lookup,
+		);
+
+		assert_last_event::<T>(
+			Event::ApplicationKeyTransferred {
+				key,
+				id,
+				old_owner: caller,
+				new_owner,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	// Worst case: the metadata is replaced by a longer one, which reserves more deposit.
+	#[benchmark]
+	fn set_application_metadata() -> Result<(), BenchmarkError> {
+		let caller = whitelisted_caller::<T::AccountId>();
+		let key = create_funded_app_key::<T>(&caller);
+		let id = Pallet::<T>::application_key(&key)
+			.map(|info| info.id)
+			.unwrap();
+		let short = generate_bounded::<AppMetadataFor<T>>(1);
+		Pallet::<T>::set_application_metadata(
+			RawOrigin::Signed(caller.clone()).into(),
+			key.clone(),
+			short.clone(),
+			short,
+		)?;
+
+		let max_len = T::MaxAppMetadataLength::get();
+		let url = generate_bounded::<AppMetadataFor<T>>(max_len);
+		let description = url.clone();
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			key.clone(),
+			url,
+			description,
+		);
+
+		assert_last_event::<T>(
+			Event::ApplicationMetadataSet {
+				key,
+				id,
+				owner: caller,
+				deposit: Pallet::<T>::metadata_deposit(2 * max_len),
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	// Worst case: the application has metadata whose deposit is released with the key.
+	#[benchmark]
+	fn release_application_key() -> Result<(), BenchmarkError> {
+		let caller = whitelisted_caller::<T::AccountId>();
+		let key = create_funded_app_key::<T>(&caller);
+		set_max_metadata::<T>(&caller, key.clone());
+		let id = Pallet::<T>::application_key(&key)
+			.map(|info| info.id)
+			.unwrap();
+		// Worst case: the application has a block space cap and reservation to remove.
+		let share = Perbill::from_percent(10);
+		BlockSpaceCaps::<T>::insert(id, share);
+		if let Ok(lanes) = ReservedLanesFor::<T>::try_from(BTreeMap::from([(id, share)])) {
+			ReservedLanes::<T>::put(lanes);
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), key.clone());
+
+		assert!(Pallet::<T>::is_app_id_retired(id));
+		assert_eq!(Pallet::<T>::block_space_cap(id), None);
+		assert!(Pallet::<T>::reserved_lanes().is_empty());
+		assert_last_event::<T>(
+			Event::ApplicationKeyReleased {
+				key,
+				id,
+				owner: caller,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_submitter_policy(
+		s: Linear<0, { T::MaxAppSubmitters::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let caller = whitelisted_caller::<T::AccountId>();
+		let key = create_funded_app_key::<T>(&caller);
+		let id = Pallet::<T>::application_key(&key)
+			.map(|info| info.id)
+			.unwrap();
+		let submitters = (0..s)
+			.map(|i| account::<T::AccountId>("submitter", i, 0))
+			.collect::<BTreeSet<_>>();
+		let submitters =
+			SubmittersFor::<T>::try_from(submitters).expect("Bounded by `MaxAppSubmitters` .qed");
+		let policy = SubmitterPolicy::Allowlist(submitters);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			key.clone(),
+			policy.clone(),
+		);
+
+		assert_last_event::<T>(Event::SubmitterPolicySet { key, id, policy }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_block_space_cap() -> Result<(), BenchmarkError> {
+		let app_id = AppId(1);
+		let cap = Some(Perbill::from_percent(50));
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, app_id, cap);
+
+		assert_last_event::<T>(Event::BlockSpaceCapSet { app_id, cap }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_reserved_block_space() -> Result<(), BenchmarkError> {
+		// Worst case: every other lane is already reserved. Part of the block stays unreserved.
+		let max_lanes = T::MaxReservedLanes::get();
+		let share = Perbill::from_parts(Perbill::one().deconstruct() / max_lanes.saturating_add(1));
+		let lanes = (1..max_lanes)
+			.map(|id| (AppId(id), share))
+			.collect::<BTreeMap<_, _>>();
+		let lanes =
+			ReservedLanesFor::<T>::try_from(lanes).expect("Bounded by `MaxReservedLanes` .qed");
+		ReservedLanes::<T>::put(lanes);
+		let app_id = AppId(max_lanes);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, app_id, Some(share));
+
+		assert_last_event::<T>(
+			Event::ReservedBlockSpaceSet {
+				app_id,
+				share: Some(share),
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
 	#[benchmark]
 	fn submit_block_length_proposal() -> Result<(), BenchmarkError> {
 		let origin = RawOrigin::Root;
@@ -223,6 +470,138 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn submit_data_chunk(
+		i: Linear<1, { T::MaxAppDataLength::get() }>,
+	) -> Result<(), BenchmarkError> {
+		// Worst case: the pending blob only has room for one more chunk.
+		let caller = whitelisted_caller::<T::AccountId>();
+		let pending = pending_blob::<T>(&caller, T::MaxBlobChunks::get().saturating_sub(1));
+		let index = pending.chunks.len() as u32;
+		PendingBlobs::<T>::insert(&caller, pending);
+		let origin = RawOrigin::Signed(caller.clone());
+		let data = generate_bounded::<AppDataFor<T>>(i);
+		let data_hash = H256(blake2_256(&data));
+
+		#[extrinsic_call]
+		_(origin, data);
+
+		assert_last_event::<T>(
+			Event::BlobChunkSubmitted {
+				who: caller,
+				data_hash,
+				index,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn finalize_blob(c: Linear<1, { T::MaxBlobChunks::get() }>) -> Result<(), BenchmarkError> {
+		let caller = whitelisted_caller::<T::AccountId>();
+		let pending = pending_blob::<T>(&caller, c);
+		let chunks = &pending.chunks;
+		let total_len = chunks.iter().map(|chunk| u64::from(chunk.len)).sum();
+		let blob_root = crate::blob_root(chunks.iter().map(|chunk| &chunk.data_hash));
+		PendingBlobs::<T>::insert(&caller, pending);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert_last_event::<T>(
+			Event::BlobAssembled {
+				who: caller,
+				blob_root,
+				total_len,
+				num_chunks: c,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_dimension_adjustment() -> Result<(), BenchmarkError> {
+		let params = Some(DimensionAdjustment {
+			grow_above: Perbill::from_percent(80),
+			shrink_below: Perbill::from_percent(20),
+			smoothing: Perbill::from_percent(10),
+			period: 10,
+		});
+		GridUtilization::<T>::put(Perbill::from_percent(50));
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, params);
+
+		assert_last_event::<T>(Event::DimensionAdjustmentSet { params }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn schedule_block_length_proposal() -> Result<(), BenchmarkError> {
+		// Worst case: the proposal is inserted first in an almost full schedule.
+		let max = T::MaxScheduledBlockLengths::get();
+		let scheduled = scheduled_block_lengths::<T>(max.saturating_sub(1));
+		ScheduledBlockLengths::<T>::put(scheduled);
+		let rows = T::MaxBlockRows::get();
+		let cols = T::MaxBlockCols::get();
+		let activation = BlockNumberFor::<T>::from(100u32);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, rows.0, cols.0, activation);
+
+		let id = LastBlockLenProposalId::<T>::get();
+		assert_last_event::<T>(
+			Event::BlockLengthProposalScheduled {
+				id,
+				rows,
+				cols,
+				activation,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_block_length_proposal() -> Result<(), BenchmarkError> {
+		let max = T::MaxScheduledBlockLengths::get();
+		ScheduledBlockLengths::<T>::put(scheduled_block_lengths::<T>(max));
+		let id = LastBlockLenProposalId::<T>::get();
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, id.clone());
+
+		assert_last_event::<T>(Event::BlockLengthProposalCancelled { id }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn replace_block_length_proposal() -> Result<(), BenchmarkError> {
+		// Worst case: the last proposal of a full schedule becomes the first one.
+		let max = T::MaxScheduledBlockLengths::get();
+		ScheduledBlockLengths::<T>::put(scheduled_block_lengths::<T>(max));
+		let id = LastBlockLenProposalId::<T>::get();
+		let rows = T::MaxBlockRows::get();
+		let cols = T::MaxBlockCols::get();
+		let activation = BlockNumberFor::<T>::from(99u32);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, id.clone(), rows.0, cols.0, activation);
+
+		assert_last_event::<T>(
+			Event::BlockLengthProposalReplaced {
+				id,
+				rows,
+				cols,
+				activation,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
 	#[benchmark]
 	fn data_root(i: Linear<0, { T::MaxAppDataLength::get() }>) -> Result<(), BenchmarkError> {
 		let data = generate_bounded::<AppDataFor<T>>(i);
@@ -351,5 +730,51 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn cancel_blob() -> Result<(), BenchmarkError> {
+		let caller = whitelisted_caller::<T::AccountId>();
+		let num_chunks = T::MaxBlobChunks::get();
+		PendingBlobs::<T>::insert(&caller, pending_blob::<T>(&caller, num_chunks));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert_last_event::<T>(
+			Event::BlobCancelled {
+				who: caller,
+				num_chunks,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_blob() -> Result<(), BenchmarkError> {
+		let caller = whitelisted_caller::<T::AccountId>();
+		let PendingBlob { chunks, deposit } = pending_blob::<T>(&caller, T::MaxBlobChunks::get());
+		let blob_root = crate::blob_root(chunks.iter().map(|chunk| &chunk.data_hash));
+		let manifest = BlobManifest {
+			owner: caller.clone(),
+			total_len: chunks.iter().map(|chunk| u64::from(chunk.len)).sum(),
+			chunks,
+			blob_root,
+			deposit,
+		};
+		BlobManifests::<T>::insert(blob_root, manifest);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), blob_root);
+
+		assert_last_event::<T>(
+			Event::BlobRemoved {
+				who: caller,
+				blob_root,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -0,0 +1,92 @@
+//! Automatic adjustment of the block dimensions to the demand of block space.
+//!
+//! Like `TargetedFeeAdjustment` does for the fee multiplier, the block dimensions follow the
+//! utilization of the padded grid: the rolling utilization is updated at the end of every block,
+//! and every `period` blocks the grid grows when it is above `grow_above`, or shrinks when it is
+//! below `shrink_below`.
+//!
+//! Each adjustment doubles or halves a single dimension, so the capacity changes at most by a
+//! factor of two per step. Rows grow before columns, and columns shrink before rows.
+use avail_core::{BlockLengthColumns, BlockLengthRows};
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{Perbill, RuntimeDebug, Saturating};
+
+/// Parameters of the automatic adjustment of the block dimensions.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Encode, Decode, TypeInfo, PartialEq, Eq, RuntimeDebug, MaxEncodedLen)]
+pub struct DimensionAdjustment {
+	/// Rolling utilization above which the block grows.
+	pub grow_above: Perbill,
+	/// Rolling utilization below which the block shrinks.
+	pub shrink_below: Perbill,
+	/// Weight of the last block in the rolling utilization.
+	pub smoothing: Perbill,
+	/// Number of blocks between two adjustments.
+	pub period: u32,
+}
+
+impl DimensionAdjustment {
+	/// Parameters are valid if they leave a dead band between shrinking and growing, and if the
+	/// rolling utilization follows the blocks.
+	pub fn is_valid(&self) -> bool {
+		self.shrink_below < self.grow_above && self.smoothing != Perbill::zero() && self.period > 0
+	}
+
+	/// Rolling utilization once a block with `utilization` is added.
+	pub fn next_utilization(&self, rolling: Perbill, utilization: Perbill) -> Perbill {
+		let decay = Saturating::saturating_sub(Perbill::one(), self.smoothing);
+		Saturating::saturating_add(self.smoothing * utilization, decay * rolling)
+	}
+
+	/// Next dimensions for `rolling` utilization, or `None` if they do not change.
+	///
+	/// The new dimensions stay within `min` and `max`.
+	pub fn next_dimensions(
+		&self,
+		rolling: Perbill,
+		current: (BlockLengthRows, BlockLengthColumns),
+		min: (BlockLengthRows, BlockLengthColumns),
+		max: (BlockLengthRows, BlockLengthColumns),
+	) -> Option<(BlockLengthRows, BlockLengthColumns)> {
+		let (rows, cols) = current;
+
+		if rolling > self.grow_above {
+			let grown_rows = BlockLengthRows(rows.0.saturating_mul(2));
+			let grown_cols = BlockLengthColumns(cols.0.saturating_mul(2));
+			if grown_rows <= max.0 {
+				return Some((grown_rows, cols));
+			}
+			if grown_cols <= max.1 {
+				return Some((rows, grown_cols));
+			}
+		} else if rolling < self.shrink_below {
+			let shrunk_rows = BlockLengthRows(rows.0 / 2);
+			let shrunk_cols = BlockLengthColumns(cols.0 / 2);
+			if shrunk_cols >= min.1 {
+				return Some((rows, shrunk_cols));
+			}
+			if shrunk_rows >= min.0 {
+				return Some((shrunk_rows, cols));
+			}
+		}
+
+		None
+	}
+
+	/// Rolling utilization of the grid once its capacity changed from `current` to `next`, so the
+	/// same load is not counted twice against the new grid.
+	pub fn rescaled_utilization(
+		rolling: Perbill,
+		current: (BlockLengthRows, BlockLengthColumns),
+		next: (BlockLengthRows, BlockLengthColumns),
+	) -> Perbill {
+		let capacity = |(rows, cols): (BlockLengthRows, BlockLengthColumns)| {
+			u64::from(rows.0).saturating_mul(u64::from(cols.0))
+		};
+		let used = rolling.mul_floor(capacity(current));
+		Perbill::from_rational(used, capacity(next).max(1))
+	}
+}
@@ -33,7 +33,14 @@ const PADDED_LEN_EXCEEDED: InvalidTransaction =
 ///
 /// # Transaction Validity
 ///
-/// Only registered application can be used by transactions.
+/// Only registered application can be used by transactions. The IDs of released application
+/// keys are retired and can no longer be used. Data can only be submitted by the accounts allowed
+/// by the submitter policy of the application.
+///
+/// # Block Space
+///
+/// Transactions of an application cannot use more padded scalars than its cap, nor the space
+/// reserved to other applications and not used by them yet.
 ///
 #[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
 #[scale_info(skip_type_params(T))]
@@ -54,20 +61,24 @@ where
 
 	/// It validates that `AppId` is correct and already registered for the call and potential nested calls.
 	/// Transaction validation:
-	///  - `DataAvailability::submit_data(..)` extrinsic can use `AppId != 0`.
-	///  - `Utility::batch/batch_all/force_batch(..)` extrinsic can use `AppId != 0` If the wrapped calls are ALL `DataAvailability::submit_data(..)`.
+	///  - `DataAvailability::submit_data(..)`, `DataAvailability::submit_data_chunk(..)` and
+	///  `DataAvailability::finalize_blob(..)` extrinsics can use `AppId != 0`, if `who` is allowed
+	///  by the submitter policy of the application.
+	///  - `Utility::batch/batch_all/force_batch(..)` extrinsic can use `AppId != 0` If the wrapped calls are ALL of those calls.
 	///  - Any other call must use `AppId == 0`.
 	///  - It also ensures that Kate's evaluation grid can be generated during the header
 	///  production.
 	pub fn do_validate(
 		&self,
+		who: &T::AccountId,
 		call: &<T as SystemConfig>::RuntimeCall,
 		len: usize,
 	) -> TransactionValidity {
-		self.ensure_valid_app_id(call)?;
+		self.ensure_valid_app_id(who, call)?;
 		let all_extrinsics_len = self
 			.next_all_extrinsics_len(len)
 			.ok_or(PADDED_LEN_EXCEEDED)?;
+		self.ensure_block_space(&all_extrinsics_len)?;
 		AllExtrinsicsLen::<T>::put(all_extrinsics_len);
 
 		Ok(ValidTransaction::default())
@@ -99,8 +110,31 @@ where
 		}
 	}
 
+	fn ensure_block_space(
+		&self,
+		all_extrinsics_len: &ExtrinsicLenOf<T>,
+	) -> Result<(), TransactionValidityError> {
+		let app_id = self.app_id();
+		let curr_len = DynamicBlockLength::<T>::get();
+		let max_scalars = curr_len.rows.0.saturating_mul(curr_len.cols.0);
+
+		// Per application cap and reserved block space of other applications.
+		if <Pallet<T>>::fits_block_space(app_id, all_extrinsics_len, max_scalars) {
+			Ok(())
+		} else {
+			log::debug!(
+				target: LOG_TARGET,
+				"Block space available to app {} (max {max_scalars} scalars) is exhausted",
+				app_id.0
+			);
+
+			Err(InvalidTransaction::ExhaustsResources.into())
+		}
+	}
+
 	fn ensure_valid_app_id(
 		&self,
+		who: &T::AccountId,
 		call: &<T as SystemConfig>::RuntimeCall,
 	) -> Result<(), TransactionValidityError> {
 		if self.app_id() == AppId(0) {
@@ -111,16 +145,25 @@ where
 		stack.push(call);
 
 		let mut maybe_next_app_id: Option<AppId> = None;
+		let mut maybe_allowed: Option<bool> = None;
 		let mut iterations = 0;
 
 		while let Some(call) = stack.pop() {
-			if let Some(DACall::<T>::submit_data { .. }) = call.is_sub_type() {
+			if let Some(
+				DACall::<T>::submit_data { .. }
+				| DACall::<T>::submit_data_chunk { .. }
+				| DACall::<T>::finalize_blob { .. },
+			) = call.is_sub_type()
+			{
 				let next_app_id =
 					maybe_next_app_id.get_or_insert_with(<Pallet<T>>::peek_next_application_id);
 				ensure!(
-					self.app_id() < *next_app_id,
+					self.app_id() < *next_app_id && !<Pallet<T>>::is_app_id_retired(self.app_id()),
 					InvalidTransaction::Custom(InvalidTransactionCustomId::InvalidAppId as u8)
 				);
+				let allowed = maybe_allowed
+					.get_or_insert_with(|| <Pallet<T>>::is_allowed_submitter(self.app_id(), who));
+				ensure!(*allowed, InvalidTransaction::BadSigner);
 			} else {
 				match call.is_sub_type() {
 					Some(UtilityCall::<T>::batch { calls })
@@ -187,22 +230,22 @@ where
 
 	fn validate(
 		&self,
-		_who: &Self::AccountId,
+		who: &Self::AccountId,
 		call: &Self::Call,
 		_info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> TransactionValidity {
-		self.do_validate(call, len)
+		self.do_validate(who, call, len)
 	}
 
 	fn pre_dispatch(
 		self,
-		_who: &Self::AccountId,
+		who: &Self::AccountId,
 		call: &Self::Call,
 		_info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> Result<Self::Pre, TransactionValidityError> {
-		self.do_validate(call, len)?;
+		self.do_validate(who, call, len)?;
 		Ok(())
 	}
 
@@ -229,15 +272,22 @@ mod tests {
 	};
 	use frame_system::pallet::Call as SysCall;
 	use pallet_utility::pallet::Call as UtilityCall;
-	use sp_runtime::transaction_validity::InvalidTransaction;
+	use sp_runtime::{transaction_validity::InvalidTransaction, Perbill};
+	use sp_std::collections::btree_map::BTreeMap;
 	use test_case::test_case;
 
 	use super::*;
 	use crate::{
 		mock::{new_test_ext, RuntimeCall, Test},
 		pallet::Call as DACall,
+		AppSubmitterPolicy, BlockSpaceCaps, ReservedLanes, ReservedLanesFor, RetiredAppIds,
+		SubmitterPolicies, SubmitterPolicy, SubmittersFor,
 	};
 
+	const ALICE: u64 = 1;
+	const BOB: u64 = 2;
+	const RETIRED_APP_ID: u32 = 2;
+
 	fn remark_call() -> RuntimeCall {
 		RuntimeCall::System(SysCall::remark { remark: vec![] })
 	}
@@ -248,6 +298,16 @@ mod tests {
 		})
 	}
 
+	fn submit_data_chunk_call() -> RuntimeCall {
+		RuntimeCall::DataAvailability(DACall::submit_data_chunk {
+			data: vec![].try_into().unwrap(),
+		})
+	}
+
+	fn finalize_blob_call() -> RuntimeCall {
+		RuntimeCall::DataAvailability(DACall::finalize_blob {})
+	}
+
 	fn batch_submit_call() -> RuntimeCall {
 		let call = submit_data_call();
 		RuntimeCall::Utility(UtilityCall::batch {
@@ -270,9 +330,14 @@ mod tests {
 	}
 
 	#[test_case(100, submit_data_call() => to_invalid_tx(InvalidAppId); "100 AppId is invalid" )]
+	#[test_case(RETIRED_APP_ID, submit_data_call() => to_invalid_tx(InvalidAppId); "retired AppId is invalid" )]
+	#[test_case(RETIRED_APP_ID, batch_submit_call() => to_invalid_tx(InvalidAppId); "utility batch with retired AppId is invalid" )]
 	#[test_case(0, remark_call() => Ok(ValidTransaction::default()); "System::remark can be called if AppId == 0" )]
 	#[test_case(1, remark_call() => to_invalid_tx(ForbiddenAppId); "System::remark cannot be called if AppId != 0" )]
 	#[test_case(1, submit_data_call() => Ok(ValidTransaction::default()); "submit_data can be called with any valid AppId" )]
+	#[test_case(1, submit_data_chunk_call() => Ok(ValidTransaction::default()); "submit_data_chunk can be called with any valid AppId" )]
+	#[test_case(1, finalize_blob_call() => Ok(ValidTransaction::default()); "finalize_blob can be called with any valid AppId" )]
+	#[test_case(100, submit_data_chunk_call() => to_invalid_tx(InvalidAppId); "submit_data_chunk with 100 AppId is invalid" )]
 	#[test_case(1, batch_submit_call() => Ok(ValidTransaction::default()); "utility batch filled with submit_data can be called with any valid AppId" )]
 	#[test_case(1, batch_mixed_call() => to_invalid_tx(ForbiddenAppId); "utility batch filled with submit_data and remark cannot be called if AppId != 0" )]
 	#[test_case(0, batch_mixed_call() => Ok(ValidTransaction::default()); "utility batch filled with submit_data and remark can be called if AppId == 0" )]
@@ -280,6 +345,76 @@ mod tests {
 		let extrinsic =
 			AppUncheckedExtrinsic::<u32, RuntimeCall, (), ()>::new_unsigned(call.clone());
 		let len = extrinsic.encoded_size();
-		new_test_ext().execute_with(|| CheckAppId::<Test>::from(AppId(id)).do_validate(&call, len))
+		new_test_ext().execute_with(|| {
+			RetiredAppIds::<Test>::insert(AppId(RETIRED_APP_ID), ());
+			CheckAppId::<Test>::from(AppId(id)).do_validate(&ALICE, &call, len)
+		})
+	}
+
+	fn bad_signer() -> TransactionValidity {
+		Err(InvalidTransaction::BadSigner.into())
+	}
+
+	fn owner_only() -> SubmitterPolicy<SubmittersFor<Test>> {
+		SubmitterPolicy::OwnerOnly
+	}
+
+	fn allowlist(submitters: Vec<u64>) -> SubmitterPolicy<SubmittersFor<Test>> {
+		let submitters = submitters
+			.into_iter()
+			.collect::<sp_std::collections::btree_set::BTreeSet<_>>();
+		SubmitterPolicy::Allowlist(submitters.try_into().unwrap())
+	}
+
+	#[test_case(ALICE, owner_only(), submit_data_call() => bad_signer(); "OwnerOnly rejects other accounts" )]
+	#[test_case(BOB, owner_only(), submit_data_call() => Ok(ValidTransaction::default()); "OwnerOnly accepts the owner" )]
+	#[test_case(ALICE, owner_only(), batch_submit_call() => bad_signer(); "OwnerOnly rejects batches of other accounts" )]
+	#[test_case(ALICE, allowlist(vec![]), submit_data_call() => bad_signer(); "Allowlist rejects unlisted accounts" )]
+	#[test_case(BOB, allowlist(vec![]), submit_data_call() => Ok(ValidTransaction::default()); "Allowlist accepts the owner" )]
+	#[test_case(ALICE, allowlist(vec![ALICE]), batch_submit_call() => Ok(ValidTransaction::default()); "Allowlist accepts listed accounts" )]
+	#[test_case(ALICE, SubmitterPolicy::Open, submit_data_call() => Ok(ValidTransaction::default()); "Open accepts any account" )]
+	fn submitter_policy_test(
+		who: u64,
+		policy: SubmitterPolicy<SubmittersFor<Test>>,
+		call: RuntimeCall,
+	) -> TransactionValidity {
+		let extrinsic =
+			AppUncheckedExtrinsic::<u32, RuntimeCall, (), ()>::new_unsigned(call.clone());
+		let len = extrinsic.encoded_size();
+		new_test_ext().execute_with(|| {
+			// `AppId(1)` is owned by `BOB` at genesis.
+			let app_id = AppId(1);
+			SubmitterPolicies::<Test>::insert(app_id, AppSubmitterPolicy { owner: BOB, policy });
+			CheckAppId::<Test>::from(app_id).do_validate(&who, &call, len)
+		})
+	}
+
+	fn exhausts_resources() -> TransactionValidity {
+		Err(InvalidTransaction::ExhaustsResources.into())
+	}
+
+	#[test_case(1, Some(Perbill::zero()), None => exhausts_resources(); "Cap exhausted by AppId" )]
+	#[test_case(1, Some(Perbill::from_percent(50)), None => Ok(ValidTransaction::default()); "Cap not exhausted by AppId" )]
+	#[test_case(1, None, Some(Perbill::one()) => exhausts_resources(); "Space reserved to other AppId cannot be used" )]
+	#[test_case(1, None, Some(Perbill::from_percent(50)) => Ok(ValidTransaction::default()); "Space not reserved to other AppId can be used" )]
+	#[test_case(2, None, Some(Perbill::one()) => Ok(ValidTransaction::default()); "Space reserved to AppId can be used" )]
+	fn block_space_test(
+		id: u32,
+		cap: Option<Perbill>,
+		reserved: Option<Perbill>,
+	) -> TransactionValidity {
+		let call = submit_data_call();
+		let extrinsic =
+			AppUncheckedExtrinsic::<u32, RuntimeCall, (), ()>::new_unsigned(call.clone());
+		let len = extrinsic.encoded_size();
+		new_test_ext().execute_with(|| {
+			BlockSpaceCaps::<Test>::set(AppId(id), cap);
+			// Space is reserved to `AppId(2)`.
+			if let Some(share) = reserved {
+				let lanes = ReservedLanesFor::<Test>::try_from(BTreeMap::from([(AppId(2), share)]));
+				ReservedLanes::<Test>::put(lanes.unwrap());
+			}
+			CheckAppId::<Test>::from(AppId(id)).do_validate(&ALICE, &call, len)
+		})
 	}
 }
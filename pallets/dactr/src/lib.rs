@@ -5,20 +5,33 @@
 use avail_core::{
 	AppId, BlockLengthColumns, BlockLengthRows, BLOCK_CHUNK_SIZE, NORMAL_DISPATCH_RATIO,
 };
-use frame_support::{dispatch::DispatchClass, weights::Weight};
-use frame_system::{limits::BlockLength, pallet::DynamicBlockLength};
+use frame_support::{
+	dispatch::DispatchClass,
+	traits::{BalanceStatus, Currency, Get, ReservableCurrency},
+	weights::Weight,
+};
+use frame_system::{
+	limits::BlockLength, pallet::DynamicBlockLength, pallet_prelude::BlockNumberFor,
+	AllExtrinsicsLen, ExtrinsicLenOf,
+};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_arithmetic::traits::{CheckedAdd, One, SaturatedConversion};
 use sp_core::H256;
 use sp_io::{hashing::blake2_256, transaction_index};
+use sp_runtime::{
+	traits::{Saturating, StaticLookup, Zero},
+	Perbill,
+};
 use sp_std::{mem::replace, vec, vec::Vec};
 
-pub use crate::{pallet::*, weights::WeightInfo};
+pub use crate::{dimension_adjustment::DimensionAdjustment, pallet::*, weights::WeightInfo};
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod dimension_adjustment;
 mod extensions;
+pub mod migrations;
 #[cfg(feature = "std")]
 pub mod mock;
 #[cfg(test)]
@@ -30,7 +43,7 @@ pub const LOG_TARGET: &str = "runtime::da_control";
 
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{pallet_prelude::*, DefaultNoBound};
+	use frame_support::{pallet_prelude::*, BoundedBTreeMap, BoundedBTreeSet, DefaultNoBound};
 	use frame_system::pallet_prelude::*;
 
 	use super::*;
@@ -40,14 +53,138 @@ pub mod pallet {
 
 	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 	#[derive(Clone, Encode, Decode, TypeInfo, PartialEq, RuntimeDebug, MaxEncodedLen)]
-	pub struct AppKeyInfo<Acc: PartialEq> {
+	pub struct AppKeyInfo<Acc: PartialEq, Balance> {
 		/// Owner of the key
 		pub owner: Acc,
 		/// Application ID associated.
 		pub id: AppId,
+		/// Deposit reserved from the owner of the key.
+		pub deposit: Balance,
+	}
+
+	pub type AppKeyInfoFor<T> = AppKeyInfo<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+	pub type AppMetadataFor<T> = BoundedVec<u8, <T as Config>::MaxAppMetadataLength>;
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+
+	#[derive(Clone, Encode, Decode, TypeInfo, PartialEq, RuntimeDebug, MaxEncodedLen)]
+	pub struct AppMetadata<Balance, Data> {
+		/// URL of the application.
+		pub url: Data,
+		/// Description of the application.
+		pub description: Data,
+		/// Deposit reserved from the owner of the key.
+		pub deposit: Balance,
+	}
+
+	pub type AppMetadataInfoFor<T> = AppMetadata<BalanceOf<T>, AppMetadataFor<T>>;
+
+	/// Accounts allowed to submit data under an application ID.
+	#[derive(Clone, Encode, Decode, TypeInfo, PartialEq, Eq, RuntimeDebug, MaxEncodedLen)]
+	pub enum SubmitterPolicy<Submitters> {
+		/// Any account.
+		Open,
+		/// The owner of the key and the listed accounts.
+		Allowlist(Submitters),
+		/// Only the owner of the key.
+		OwnerOnly,
 	}
 
-	pub type AppKeyInfoFor<T> = AppKeyInfo<<T as frame_system::Config>::AccountId>;
+	pub type SubmittersFor<T> =
+		BoundedBTreeSet<<T as frame_system::Config>::AccountId, <T as Config>::MaxAppSubmitters>;
+	pub type SubmitterPolicyFor<T> = SubmitterPolicy<SubmittersFor<T>>;
+
+	/// Submitter policy of an application, along with the current owner of its key.
+	#[derive(Clone, Encode, Decode, TypeInfo, PartialEq, RuntimeDebug, MaxEncodedLen)]
+	pub struct AppSubmitterPolicy<Acc, Policy> {
+		/// Owner of the application key.
+		pub owner: Acc,
+		/// Policy set by the owner.
+		pub policy: Policy,
+	}
+
+	pub type AppSubmitterPolicyFor<T> =
+		AppSubmitterPolicy<<T as frame_system::Config>::AccountId, SubmitterPolicyFor<T>>;
+
+	/// Chunk of a blob, submitted with `submit_data_chunk`.
+	#[derive(Clone, Encode, Decode, TypeInfo, PartialEq, Eq, RuntimeDebug, MaxEncodedLen)]
+	pub struct BlobChunk<BlockNumber> {
+		/// `blake2_256` hash of the chunk data.
+		pub data_hash: H256,
+		/// Length of the chunk data.
+		pub len: u32,
+		/// Block which includes the chunk.
+		pub block_number: BlockNumber,
+		/// Index of the `submit_data_chunk` extrinsic in that block.
+		pub extrinsic_index: u32,
+	}
+
+	pub type BlobChunksFor<T> =
+		BoundedVec<BlobChunk<BlockNumberFor<T>>, <T as Config>::MaxBlobChunks>;
+
+	/// Chunks of a blob which is not finalized yet.
+	#[derive(
+		Clone, Encode, Decode, TypeInfo, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, Default,
+	)]
+	pub struct PendingBlob<Balance, Chunks> {
+		/// Chunks of the blob, in order.
+		pub chunks: Chunks,
+		/// Deposit reserved for the chunks.
+		pub deposit: Balance,
+	}
+
+	pub type PendingBlobFor<T> = PendingBlob<BalanceOf<T>, BlobChunksFor<T>>;
+
+	/// Manifest of a blob assembled from its chunks by `finalize_blob`.
+	#[derive(Clone, Encode, Decode, TypeInfo, PartialEq, Eq, RuntimeDebug, MaxEncodedLen)]
+	pub struct BlobManifest<Acc, Balance, Chunks> {
+		/// Account which submitted the chunks.
+		pub owner: Acc,
+		/// Length of the full blob.
+		pub total_len: u64,
+		/// Chunks of the blob, in order.
+		pub chunks: Chunks,
+		/// Root of the blob, see [`blob_root`](crate::blob_root).
+		pub blob_root: H256,
+		/// Deposit reserved for the manifest, until it is removed.
+		pub deposit: Balance,
+	}
+
+	pub type BlobManifestFor<T> =
+		BlobManifest<<T as frame_system::Config>::AccountId, BalanceOf<T>, BlobChunksFor<T>>;
+
+	/// Block space used by an application.
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	#[derive(Clone, Encode, Decode, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+	pub struct AppBlockSpaceUsage {
+		pub app_id: AppId,
+		/// Padded scalars used in the last block.
+		pub num_scalars: u32,
+		/// Maximum padded scalars the application can use in a block, if capped.
+		pub cap: Option<u32>,
+		/// Padded scalars reserved to the application in each block.
+		pub reserved: u32,
+	}
+
+	/// Block length proposal scheduled to be applied at the start of block `activation`.
+	#[derive(Clone, Encode, Decode, TypeInfo, PartialEq, Eq, RuntimeDebug, MaxEncodedLen)]
+	pub struct ScheduledBlockLength<Id, BlockNumber> {
+		pub id: Id,
+		pub rows: BlockLengthRows,
+		pub cols: BlockLengthColumns,
+		pub activation: BlockNumber,
+	}
+
+	pub type ScheduledBlockLengthFor<T> =
+		ScheduledBlockLength<<T as Config>::BlockLenProposalId, BlockNumberFor<T>>;
+	pub type ScheduledBlockLengthsFor<T> =
+		BoundedVec<ScheduledBlockLengthFor<T>, <T as Config>::MaxScheduledBlockLengths>;
+
+	pub type ReservedLanesFor<T> = BoundedBTreeMap<AppId, Perbill, <T as Config>::MaxReservedLanes>;
+	pub type BlockSpaceUsageFor<T> =
+		BoundedVec<(AppId, u32), <T as frame_system::Config>::MaxDiffAppIdPerBlock>;
 
 	/// Default implementations of [`DefaultConfig`], which can be used to implement [`Config`].
 	pub mod config_preludes {
@@ -67,6 +204,11 @@ pub mod pallet {
 			type BlockLenProposalId = u32;
 			type MaxAppDataLength = ();
 			type MaxAppKeyLength = ();
+			type MaxAppMetadataLength = ();
+			type MaxAppSubmitters = ();
+			type MaxReservedLanes = ();
+			type MaxBlobChunks = ();
+			type MaxScheduledBlockLengths = ();
 			type MaxBlockCols = ();
 			type MaxBlockRows = ();
 			type MinBlockCols = ();
@@ -94,6 +236,56 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxAppDataLength: Get<u32>;
 
+		/// The max length of the URL and of the description of an application.
+		#[pallet::constant]
+		type MaxAppMetadataLength: Get<u32>;
+
+		/// The max number of accounts in the submitter allowlist of an application.
+		#[pallet::constant]
+		type MaxAppSubmitters: Get<u32>;
+
+		/// The max number of applications with reserved block space.
+		#[pallet::constant]
+		type MaxReservedLanes: Get<u32>;
+
+		/// The max number of chunks of a blob.
+		#[pallet::constant]
+		type MaxBlobChunks: Get<u32>;
+
+		/// The max number of block length proposals waiting for their activation.
+		#[pallet::constant]
+		type MaxScheduledBlockLengths: Get<u32>;
+
+		/// The currency in which the application key and metadata deposits are reserved.
+		#[pallet::no_default]
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved for an application key, until it is released.
+		#[pallet::constant]
+		#[pallet::no_default]
+		type AppKeyDeposit: Get<BalanceOf<Self>>;
+
+		/// The base deposit reserved for the metadata of an application.
+		#[pallet::constant]
+		#[pallet::no_default]
+		type MetadataDepositBase: Get<BalanceOf<Self>>;
+
+		/// The deposit reserved per byte of metadata of an application.
+		#[pallet::constant]
+		#[pallet::no_default]
+		type MetadataDepositPerByte: Get<BalanceOf<Self>>;
+
+		/// The base deposit reserved for a blob, from its first chunk until its manifest is
+		/// removed.
+		#[pallet::constant]
+		#[pallet::no_default]
+		type BlobDepositBase: Get<BalanceOf<Self>>;
+
+		/// The deposit reserved per chunk of a blob.
+		#[pallet::constant]
+		#[pallet::no_default]
+		type BlobDepositPerChunk: Get<BalanceOf<Self>>;
+
 		/// Minimum number of rows in a block.
 		#[pallet::constant]
 		type MinBlockRows: Get<BlockLengthRows>;
@@ -114,9 +306,68 @@ pub mod pallet {
 		type WeightInfo: weights::WeightInfo;
 	}
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			// `on_finalize` reads `AllExtrinsicsLen` and `DimensionAdjustmentParams`, and writes
+			// `LastBlockSpaceUsage`.
+			let mut weight = T::DbWeight::get().reads_writes(3, 1);
+
+			let applied = Self::apply_scheduled_block_lengths(now);
+			if applied > 0 {
+				weight.saturating_accrue(T::DbWeight::get().writes(u64::from(applied) + 2));
+			}
+
+			if let Some(params) = DimensionAdjustmentParams::<T>::get() {
+				// `on_finalize` also updates `GridUtilization`.
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 1));
+
+				let now: u32 = now.saturated_into();
+				if now.checked_rem(params.period) == Some(0) {
+					Self::adjust_block_dimensions(&params);
+					weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
+				}
+			}
+
+			weight
+		}
+
+		fn on_finalize(_: BlockNumberFor<T>) {
+			let all_extrinsics_len = AllExtrinsicsLen::<T>::get();
+
+			if let Some(params) = DimensionAdjustmentParams::<T>::get() {
+				let used = all_extrinsics_len
+					.as_ref()
+					.and_then(|len| len.total_num_scalars())
+					.unwrap_or_default();
+				let block_length = DynamicBlockLength::<T>::get();
+				let max_scalars = block_length.rows.0.saturating_mul(block_length.cols.0);
+				let utilization = Perbill::from_rational(used, max_scalars.max(1));
+				GridUtilization::<T>::mutate(|rolling| {
+					let next = rolling.map_or(utilization, |previous| {
+						params.next_utilization(previous, utilization)
+					});
+					*rolling = Some(next);
+				});
+			}
+
+			let usage = all_extrinsics_len
+				.map(|len| len.num_scalars_per_app().collect::<Vec<_>>())
+				.unwrap_or_default();
+			LastBlockSpaceUsage::<T>::put(BlockSpaceUsageFor::<T>::truncate_from(usage));
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+	}
+
 	/// Last application ID
 	#[pallet::storage]
 	#[pallet::getter(fn peek_next_application_id)]
@@ -127,9 +378,76 @@ pub mod pallet {
 	#[pallet::getter(fn application_key)]
 	pub type AppKeys<T: Config> = StorageMap<_, Blake2_128Concat, AppKeyFor<T>, AppKeyInfoFor<T>>;
 
+	/// Metadata of the applications, by application ID.
+	#[pallet::storage]
+	#[pallet::getter(fn application_metadata)]
+	pub type AppMetadataOf<T: Config> = StorageMap<_, Twox64Concat, AppId, AppMetadataInfoFor<T>>;
+
+	/// Submitter policies of the applications, by application ID. Applications without one are
+	/// open to any submitter.
+	#[pallet::storage]
+	#[pallet::getter(fn submitter_policy)]
+	pub type SubmitterPolicies<T: Config> =
+		StorageMap<_, Twox64Concat, AppId, AppSubmitterPolicyFor<T>>;
+
+	/// Maximum share of the padded scalars of a block that an application can use.
+	#[pallet::storage]
+	#[pallet::getter(fn block_space_cap)]
+	pub type BlockSpaceCaps<T: Config> = StorageMap<_, Twox64Concat, AppId, Perbill>;
+
+	/// Share of the padded scalars of a block reserved to an application, which other
+	/// applications cannot use.
+	#[pallet::storage]
+	#[pallet::getter(fn reserved_lanes)]
+	pub type ReservedLanes<T: Config> = StorageValue<_, ReservedLanesFor<T>, ValueQuery>;
+
+	/// Padded scalars used by each application in the last block.
+	#[pallet::storage]
+	pub type LastBlockSpaceUsage<T: Config> = StorageValue<_, BlockSpaceUsageFor<T>, ValueQuery>;
+
+	/// Chunks submitted by an account for the blob it has not finalized yet.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_blob)]
+	pub type PendingBlobs<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, PendingBlobFor<T>, ValueQuery>;
+
+	/// Manifests of the assembled blobs, by blob root.
+	#[pallet::storage]
+	#[pallet::getter(fn blob_manifest)]
+	pub type BlobManifests<T: Config> = StorageMap<_, Blake2_128Concat, H256, BlobManifestFor<T>>;
+
+	/// Parameters of the automatic adjustment of the block dimensions, which is disabled if they
+	/// are not set.
+	#[pallet::storage]
+	#[pallet::getter(fn dimension_adjustment)]
+	pub type DimensionAdjustmentParams<T: Config> = StorageValue<_, DimensionAdjustment>;
+
+	/// Rolling utilization of the padded grid, updated while the automatic adjustment of the
+	/// block dimensions is enabled. It is not set until a block is finalized with the current
+	/// parameters.
+	#[pallet::storage]
+	#[pallet::getter(fn grid_utilization)]
+	pub type GridUtilization<T: Config> = StorageValue<_, Perbill>;
+
+	/// Last block length proposal Id.
+	#[pallet::storage]
+	pub type LastBlockLenProposalId<T: Config> = StorageValue<_, T::BlockLenProposalId, ValueQuery>;
+
+	/// Block length proposals waiting for their activation, sorted by activation block.
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled_block_lengths)]
+	pub type ScheduledBlockLengths<T: Config> =
+		StorageValue<_, ScheduledBlockLengthsFor<T>, ValueQuery>;
+
+	/// Application IDs whose key was released. They are never assigned again.
+	#[pallet::storage]
+	pub type RetiredAppIds<T: Config> = StorageMap<_, Twox64Concat, AppId, ()>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Creates an application key if `key` does not exist yet.
+		///
+		/// `AppKeyDeposit` is reserved from the owner until the key is released.
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::create_application_key())]
 		pub fn create_application_key(
@@ -138,13 +456,16 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let owner = ensure_signed(origin)?;
 			ensure!(!key.is_empty(), Error::<T>::AppKeyCannotBeEmpty);
-			let id = AppKeys::<T>::try_mutate(&key, |key_info| -> Result<AppId, Error<T>> {
+			let id = AppKeys::<T>::try_mutate(&key, |key_info| -> Result<AppId, DispatchError> {
 				ensure!(key_info.is_none(), Error::<T>::AppKeyAlreadyExists);
 
+				let deposit = T::AppKeyDeposit::get();
+				T::Currency::reserve(&owner, deposit)?;
 				let id = Self::next_application_id()?;
 				*key_info = Some(AppKeyInfo {
 					id,
 					owner: owner.clone(),
+					deposit,
 				});
 
 				Ok(id)
@@ -191,15 +512,7 @@ pub mod pallet {
 			ensure_root(origin)?;
 			let rows = BlockLengthRows(rows);
 			let cols = BlockLengthColumns(cols);
-
-			ensure!(
-				rows <= T::MaxBlockRows::get() && cols <= T::MaxBlockCols::get(),
-				Error::<T>::BlockDimensionsOutOfBounds
-			);
-			ensure!(
-				rows >= T::MinBlockRows::get() && cols >= T::MinBlockCols::get(),
-				Error::<T>::BlockDimensionsTooSmall
-			);
+			let block_length = Self::proposed_block_length(rows, cols)?;
 
 			let current_block_dimension = DynamicBlockLength::<T>::get();
 			let is_increase =
@@ -209,16 +522,482 @@ pub mod pallet {
 				Error::<T>::InvalidBlockWeightReduction
 			);
 
-			let block_length =
-				BlockLength::with_normal_ratio(rows, cols, BLOCK_CHUNK_SIZE, NORMAL_DISPATCH_RATIO)
-					.map_err(|_| Error::<T>::BlockDimensionsOutOfBounds)?;
-
 			DynamicBlockLength::<T>::put(block_length);
 
 			Self::deposit_event(Event::BlockLengthProposalSubmitted { rows, cols });
 
 			Ok(().into())
 		}
+
+		/// Transfers the ownership of `key` to `new_owner`.
+		///
+		/// The key and metadata deposits are moved to `new_owner`, who gets them back once the
+		/// metadata is cleared or the key released.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::transfer_application_key())]
+		pub fn transfer_application_key(
+			origin: OriginFor<T>,
+			key: AppKeyFor<T>,
+			new_owner: AccountIdLookupOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let new_owner = T::Lookup::lookup(new_owner)?;
+
+			let id = AppKeys::<T>::try_mutate(&key, |key_info| -> Result<AppId, DispatchError> {
+				let key_info = key_info.as_mut().ok_or(Error::<T>::UnknownAppKey)?;
+				ensure!(key_info.owner == who, Error::<T>::NotAppKeyOwner);
+
+				let metadata_deposit = AppMetadataOf::<T>::get(key_info.id)
+					.map(|metadata| metadata.deposit)
+					.unwrap_or_default();
+				let deposit = key_info.deposit.saturating_add(metadata_deposit);
+				if !deposit.is_zero() {
+					T::Currency::repatriate_reserved(
+						&who,
+						&new_owner,
+						deposit,
+						BalanceStatus::Reserved,
+					)?;
+				}
+				key_info.owner = new_owner.clone();
+				SubmitterPolicies::<T>::mutate_exists(key_info.id, |submitter_policy| {
+					if let Some(submitter_policy) = submitter_policy {
+						submitter_policy.owner = new_owner.clone();
+					}
+				});
+
+				Ok(key_info.id)
+			})?;
+
+			Self::deposit_event(Event::ApplicationKeyTransferred {
+				key,
+				id,
+				old_owner: who,
+				new_owner,
+			});
+			Ok(().into())
+		}
+
+		/// Sets the metadata of the application of `key`, reserving a deposit from its owner.
+		///
+		/// An empty `url` and `description` clear the metadata and release the deposit.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::set_application_metadata())]
+		pub fn set_application_metadata(
+			origin: OriginFor<T>,
+			key: AppKeyFor<T>,
+			url: AppMetadataFor<T>,
+			description: AppMetadataFor<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let key_info = AppKeys::<T>::get(&key).ok_or(Error::<T>::UnknownAppKey)?;
+			ensure!(key_info.owner == who, Error::<T>::NotAppKeyOwner);
+			let id = key_info.id;
+
+			let old_deposit = AppMetadataOf::<T>::get(id)
+				.map(|metadata| metadata.deposit)
+				.unwrap_or_default();
+
+			if url.is_empty() && description.is_empty() {
+				T::Currency::unreserve(&who, old_deposit);
+				AppMetadataOf::<T>::remove(id);
+				Self::deposit_event(Event::ApplicationMetadataCleared { key, id });
+				return Ok(().into());
+			}
+
+			// SAFETY: Both lengths are bounded by `MaxAppMetadataLength`, which is a `u32`.
+			let len = (url.len() as u32).saturating_add(description.len() as u32);
+			let deposit = Self::metadata_deposit(len);
+			if deposit > old_deposit {
+				T::Currency::reserve(&who, deposit.saturating_sub(old_deposit))?;
+			} else {
+				T::Currency::unreserve(&who, old_deposit.saturating_sub(deposit));
+			}
+
+			AppMetadataOf::<T>::insert(
+				id,
+				AppMetadata {
+					url,
+					description,
+					deposit,
+				},
+			);
+
+			Self::deposit_event(Event::ApplicationMetadataSet {
+				key,
+				id,
+				owner: who,
+				deposit,
+			});
+			Ok(().into())
+		}
+
+		/// Releases `key` and retires its application ID.
+		///
+		/// The key and metadata deposits are unreserved, and the block space cap and reservation
+		/// of the application are removed. The key can be created again, but gets a new
+		/// application ID, and transactions can no longer use the retired one.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::release_application_key())]
+		pub fn release_application_key(
+			origin: OriginFor<T>,
+			key: AppKeyFor<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let key_info = AppKeys::<T>::get(&key).ok_or(Error::<T>::UnknownAppKey)?;
+			ensure!(key_info.owner == who, Error::<T>::NotAppKeyOwner);
+			let id = key_info.id;
+
+			let metadata_deposit = AppMetadataOf::<T>::take(id)
+				.map(|metadata| metadata.deposit)
+				.unwrap_or_default();
+			T::Currency::unreserve(&who, key_info.deposit.saturating_add(metadata_deposit));
+			AppKeys::<T>::remove(&key);
+			SubmitterPolicies::<T>::remove(id);
+			BlockSpaceCaps::<T>::remove(id);
+			ReservedLanes::<T>::mutate(|lanes| lanes.remove(&id));
+			RetiredAppIds::<T>::insert(id, ());
+
+			Self::deposit_event(Event::ApplicationKeyReleased {
+				key,
+				id,
+				owner: who,
+			});
+			Ok(().into())
+		}
+
+		/// Sets which accounts can submit data under the application ID of `key`.
+		///
+		/// The policy is enforced by `CheckAppId` when transactions are validated.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::set_submitter_policy(T::MaxAppSubmitters::get()))]
+		pub fn set_submitter_policy(
+			origin: OriginFor<T>,
+			key: AppKeyFor<T>,
+			policy: SubmitterPolicyFor<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let key_info = AppKeys::<T>::get(&key).ok_or(Error::<T>::UnknownAppKey)?;
+			ensure!(key_info.owner == who, Error::<T>::NotAppKeyOwner);
+			let id = key_info.id;
+
+			// SAFETY: The allowlist is bounded by `MaxAppSubmitters`, which is a `u32`.
+			let submitters = match &policy {
+				SubmitterPolicy::Allowlist(submitters) => submitters.len() as u32,
+				SubmitterPolicy::Open | SubmitterPolicy::OwnerOnly => 0,
+			};
+			if policy == SubmitterPolicy::Open {
+				SubmitterPolicies::<T>::remove(id);
+			} else {
+				SubmitterPolicies::<T>::insert(
+					id,
+					AppSubmitterPolicy {
+						owner: who,
+						policy: policy.clone(),
+					},
+				);
+			}
+
+			Self::deposit_event(Event::SubmitterPolicySet { key, id, policy });
+			Ok(Some(T::WeightInfo::set_submitter_policy(submitters)).into())
+		}
+
+		/// Caps the share of the padded scalars of a block used by `app_id`, or removes its cap.
+		///
+		/// Application ID 0 cannot be capped, so that governance can always undo a cap.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::set_block_space_cap())]
+		pub fn set_block_space_cap(
+			origin: OriginFor<T>,
+			app_id: AppId,
+			cap: Option<Perbill>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(app_id != AppId(0), Error::<T>::CannotRestrictAppIdZero);
+			BlockSpaceCaps::<T>::set(app_id, cap);
+
+			Self::deposit_event(Event::BlockSpaceCapSet { app_id, cap });
+			Ok(().into())
+		}
+
+		/// Reserves a share of the padded scalars of each block to `app_id`, or removes its
+		/// reservation.
+		///
+		/// The reserved shares of all applications must leave part of the block unreserved, and
+		/// application ID 0 cannot get reserved block space.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::set_reserved_block_space())]
+		pub fn set_reserved_block_space(
+			origin: OriginFor<T>,
+			app_id: AppId,
+			share: Option<Perbill>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(app_id != AppId(0), Error::<T>::CannotRestrictAppIdZero);
+
+			ReservedLanes::<T>::try_mutate(|lanes| -> DispatchResult {
+				match share {
+					Some(share) => {
+						lanes
+							.try_insert(app_id, share)
+							.map_err(|_| Error::<T>::TooManyReservedLanes)?;
+					},
+					None => {
+						lanes.remove(&app_id);
+					},
+				}
+				let total = lanes
+					.values()
+					.try_fold(0u32, |acc, share| acc.checked_add(share.deconstruct()));
+				ensure!(
+					total.map_or(false, |total| total < Perbill::one().deconstruct()),
+					Error::<T>::ReservedBlockSpaceExceeded
+				);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ReservedBlockSpaceSet { app_id, share });
+			Ok(().into())
+		}
+
+		/// Submits `data` as the next chunk of the pending blob of the sender.
+		///
+		/// The chunk is indexed like `submit_data`, and its hash and location are appended to the
+		/// pending manifest until `finalize_blob` or `cancel_blob` is called. The deposit of the
+		/// chunk, and of the blob for its first chunk, is reserved from the sender.
+		#[pallet::call_index(9)]
+		#[pallet::weight(weight_helper::submit_data_chunk::<T>(data.len()))]
+		pub fn submit_data_chunk(
+			origin: OriginFor<T>,
+			data: AppDataFor<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!data.is_empty(), Error::<T>::DataCannotBeEmpty);
+
+			// SAFETY: `data.len()` is always less than `u32::MAX` because it is bounded by
+			// `BoundedVec`
+			let len = data.len() as u32;
+
+			// Index Tx in DB block, so the chunk can be fetched by its hash.
+			let data_hash = blake2_256(&data);
+			let extrinsic_index =
+				<frame_system::Pallet<T>>::extrinsic_index().ok_or(Error::<T>::BadContext)?;
+			transaction_index::index(extrinsic_index, len, data_hash);
+
+			let chunk = BlobChunk {
+				data_hash: H256(data_hash),
+				len,
+				block_number: <frame_system::Pallet<T>>::block_number(),
+				extrinsic_index,
+			};
+			let index = PendingBlobs::<T>::try_mutate(&who, |pending| {
+				let mut deposit = T::BlobDepositPerChunk::get();
+				if pending.chunks.is_empty() {
+					deposit = deposit.saturating_add(T::BlobDepositBase::get());
+				}
+				pending
+					.chunks
+					.try_push(chunk)
+					.map_err(|_| Error::<T>::TooManyBlobChunks)?;
+				T::Currency::reserve(&who, deposit)?;
+				pending.deposit = pending.deposit.saturating_add(deposit);
+				Ok::<_, DispatchError>(pending.chunks.len() as u32 - 1)
+			})?;
+
+			Self::deposit_event(Event::BlobChunkSubmitted {
+				who,
+				data_hash: H256(data_hash),
+				index,
+			});
+			Ok(().into())
+		}
+
+		/// Assembles the pending chunks of the sender into a blob, and records its manifest
+		/// under its root, see [`blob_root`](crate::blob_root).
+		///
+		/// The deposit of the pending chunks stays reserved for the manifest, until it is removed
+		/// with `remove_blob`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::finalize_blob(T::MaxBlobChunks::get()))]
+		pub fn finalize_blob(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let PendingBlob { chunks, deposit } = PendingBlobs::<T>::get(&who);
+			ensure!(!chunks.is_empty(), Error::<T>::NoPendingBlob);
+			let blob_root = crate::blob_root(chunks.iter().map(|chunk| &chunk.data_hash));
+			ensure!(
+				!BlobManifests::<T>::contains_key(blob_root),
+				Error::<T>::BlobAlreadyAssembled
+			);
+			PendingBlobs::<T>::remove(&who);
+
+			let num_chunks = chunks.len() as u32;
+			let total_len = chunks.iter().map(|chunk| u64::from(chunk.len)).sum::<u64>();
+			let manifest = BlobManifest {
+				owner: who.clone(),
+				total_len,
+				chunks,
+				blob_root,
+				deposit,
+			};
+			BlobManifests::<T>::insert(blob_root, manifest);
+
+			Self::deposit_event(Event::BlobAssembled {
+				who,
+				blob_root,
+				total_len,
+				num_chunks,
+			});
+			Ok(Some(T::WeightInfo::finalize_blob(num_chunks)).into())
+		}
+
+		/// Enables the automatic adjustment of the block dimensions with `params`, or disables it.
+		///
+		/// The rolling utilization starts again from the utilization of the current block.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::set_dimension_adjustment())]
+		pub fn set_dimension_adjustment(
+			origin: OriginFor<T>,
+			params: Option<DimensionAdjustment>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(
+				params.map_or(true, |params| params.is_valid()),
+				Error::<T>::InvalidDimensionAdjustment
+			);
+
+			DimensionAdjustmentParams::<T>::set(params);
+			GridUtilization::<T>::kill();
+
+			Self::deposit_event(Event::DimensionAdjustmentSet { params });
+			Ok(().into())
+		}
+
+		/// Schedules the block dimensions to change to `rows` x `cols` at the start of block
+		/// `activation`, so nodes and light clients can prepare for it.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::schedule_block_length_proposal())]
+		pub fn schedule_block_length_proposal(
+			origin: OriginFor<T>,
+			rows: u32,
+			cols: u32,
+			activation: BlockNumberFor<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let rows = BlockLengthRows(rows);
+			let cols = BlockLengthColumns(cols);
+			Self::proposed_block_length(rows, cols)?;
+			Self::ensure_future_activation(activation)?;
+
+			let id = LastBlockLenProposalId::<T>::try_mutate(|last_id| {
+				*last_id = last_id
+					.checked_add(&One::one())
+					.ok_or(Error::<T>::LastBlockLenProposalIdOverflowed)?;
+				Ok::<_, Error<T>>(last_id.clone())
+			})?;
+			ScheduledBlockLengths::<T>::try_mutate(|scheduled| {
+				let proposal = ScheduledBlockLength {
+					id: id.clone(),
+					rows,
+					cols,
+					activation,
+				};
+				Self::insert_scheduled(scheduled, proposal)
+			})?;
+
+			Self::deposit_event(Event::BlockLengthProposalScheduled {
+				id,
+				rows,
+				cols,
+				activation,
+			});
+			Ok(().into())
+		}
+
+		/// Cancels the scheduled block length proposal `id`.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::cancel_block_length_proposal())]
+		pub fn cancel_block_length_proposal(
+			origin: OriginFor<T>,
+			id: T::BlockLenProposalId,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ScheduledBlockLengths::<T>::try_mutate(|scheduled| {
+				Self::remove_scheduled(scheduled, &id).map(|_| ())
+			})?;
+
+			Self::deposit_event(Event::BlockLengthProposalCancelled { id });
+			Ok(().into())
+		}
+
+		/// Replaces the dimensions and the activation of the scheduled block length proposal
+		/// `id`.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::replace_block_length_proposal())]
+		pub fn replace_block_length_proposal(
+			origin: OriginFor<T>,
+			id: T::BlockLenProposalId,
+			rows: u32,
+			cols: u32,
+			activation: BlockNumberFor<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let rows = BlockLengthRows(rows);
+			let cols = BlockLengthColumns(cols);
+			Self::proposed_block_length(rows, cols)?;
+			Self::ensure_future_activation(activation)?;
+
+			ScheduledBlockLengths::<T>::try_mutate(|scheduled| {
+				let mut proposal = Self::remove_scheduled(scheduled, &id)?;
+				proposal.rows = rows;
+				proposal.cols = cols;
+				proposal.activation = activation;
+				Self::insert_scheduled(scheduled, proposal)
+			})?;
+
+			Self::deposit_event(Event::BlockLengthProposalReplaced {
+				id,
+				rows,
+				cols,
+				activation,
+			});
+			Ok(().into())
+		}
+
+		/// Discards the pending chunks of the sender and unreserves their deposit.
+		///
+		/// The chunks stay in the blocks which include them, only their manifest is discarded.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::cancel_blob())]
+		pub fn cancel_blob(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let PendingBlob { chunks, deposit } = PendingBlobs::<T>::take(&who);
+			ensure!(!chunks.is_empty(), Error::<T>::NoPendingBlob);
+			T::Currency::unreserve(&who, deposit);
+
+			Self::deposit_event(Event::BlobCancelled {
+				who,
+				num_chunks: chunks.len() as u32,
+			});
+			Ok(().into())
+		}
+
+		/// Removes the manifest of the blob `blob_root` assembled by the sender, and unreserves
+		/// its deposit.
+		///
+		/// `kate_getAssembledBlob` can no longer assemble the blob from later blocks.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::remove_blob())]
+		pub fn remove_blob(origin: OriginFor<T>, blob_root: H256) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let manifest = BlobManifests::<T>::get(blob_root).ok_or(Error::<T>::UnknownBlob)?;
+			ensure!(manifest.owner == who, Error::<T>::NotBlobOwner);
+
+			BlobManifests::<T>::remove(blob_root);
+			T::Currency::unreserve(&who, manifest.deposit);
+
+			Self::deposit_event(Event::BlobRemoved { who, blob_root });
+			Ok(().into())
+		}
 	}
 
 	/// Event for the pallet.
@@ -239,6 +1018,104 @@ pub mod pallet {
 			rows: BlockLengthRows,
 			cols: BlockLengthColumns,
 		},
+		/// A block length proposal was scheduled.
+		BlockLengthProposalScheduled {
+			id: T::BlockLenProposalId,
+			rows: BlockLengthRows,
+			cols: BlockLengthColumns,
+			activation: BlockNumberFor<T>,
+		},
+		/// A scheduled block length proposal was cancelled.
+		BlockLengthProposalCancelled {
+			id: T::BlockLenProposalId,
+		},
+		/// The dimensions and the activation of a scheduled block length proposal were replaced.
+		BlockLengthProposalReplaced {
+			id: T::BlockLenProposalId,
+			rows: BlockLengthRows,
+			cols: BlockLengthColumns,
+			activation: BlockNumberFor<T>,
+		},
+		/// A scheduled block length proposal was applied.
+		BlockLengthProposalApplied {
+			id: T::BlockLenProposalId,
+			rows: BlockLengthRows,
+			cols: BlockLengthColumns,
+		},
+		/// The ownership of an application key was transferred.
+		ApplicationKeyTransferred {
+			key: AppKeyFor<T>,
+			id: AppId,
+			old_owner: T::AccountId,
+			new_owner: T::AccountId,
+		},
+		/// The metadata of an application was set.
+		ApplicationMetadataSet {
+			key: AppKeyFor<T>,
+			id: AppId,
+			owner: T::AccountId,
+			deposit: BalanceOf<T>,
+		},
+		/// The metadata of an application was cleared.
+		ApplicationMetadataCleared {
+			key: AppKeyFor<T>,
+			id: AppId,
+		},
+		/// An application key was released and its application ID retired.
+		ApplicationKeyReleased {
+			key: AppKeyFor<T>,
+			id: AppId,
+			owner: T::AccountId,
+		},
+		/// The submitter policy of an application was set.
+		SubmitterPolicySet {
+			key: AppKeyFor<T>,
+			id: AppId,
+			policy: SubmitterPolicyFor<T>,
+		},
+		/// The block space cap of an application was set.
+		BlockSpaceCapSet {
+			app_id: AppId,
+			cap: Option<Perbill>,
+		},
+		/// The reserved block space of an application was set.
+		ReservedBlockSpaceSet {
+			app_id: AppId,
+			share: Option<Perbill>,
+		},
+		/// A chunk was appended to the pending blob of an account.
+		BlobChunkSubmitted {
+			who: T::AccountId,
+			data_hash: H256,
+			index: u32,
+		},
+		/// The automatic adjustment of the block dimensions was enabled, updated or disabled.
+		DimensionAdjustmentSet {
+			params: Option<DimensionAdjustment>,
+		},
+		/// The block dimensions were adjusted to the rolling utilization of the grid.
+		BlockDimensionsAdjusted {
+			rows: BlockLengthRows,
+			cols: BlockLengthColumns,
+			utilization: Perbill,
+		},
+		/// The pending chunks of an account were assembled into a blob.
+		BlobAssembled {
+			who: T::AccountId,
+			blob_root: H256,
+			total_len: u64,
+			num_chunks: u32,
+		},
+		/// The pending chunks of an account were discarded.
+		BlobCancelled {
+			who: T::AccountId,
+			num_chunks: u32,
+		},
+		/// The manifest of an assembled blob was removed.
+		BlobRemoved {
+			who: T::AccountId,
+			blob_root: H256,
+		},
 	}
 
 	/// Error for the System pallet
@@ -262,6 +1139,34 @@ pub mod pallet {
 		InvalidBlockWeightReduction,
 		/// Submit data call outside of block execution context.
 		BadContext,
+		/// The application key does not exist.
+		UnknownAppKey,
+		/// The sender is not the owner of the application key.
+		NotAppKeyOwner,
+		/// There are too many applications with reserved block space.
+		TooManyReservedLanes,
+		/// The reserved block space of all applications exceeds the block.
+		ReservedBlockSpaceExceeded,
+		/// The pending blob already has the max number of chunks.
+		TooManyBlobChunks,
+		/// The sender has no pending blob chunk.
+		NoPendingBlob,
+		/// A blob with the same chunks was already assembled.
+		BlobAlreadyAssembled,
+		/// The parameters of the automatic adjustment of the block dimensions are not valid.
+		InvalidDimensionAdjustment,
+		/// The activation block of the block length proposal is not in the future.
+		ActivationNotInFuture,
+		/// There are too many block length proposals waiting for their activation.
+		TooManyScheduledBlockLengths,
+		/// The block length proposal is not scheduled.
+		UnknownBlockLengthProposal,
+		/// Application ID 0 cannot be capped nor get reserved block space.
+		CannotRestrictAppIdZero,
+		/// The blob is not assembled.
+		UnknownBlob,
+		/// The sender is not the owner of the blob.
+		NotBlobOwner,
 	}
 
 	#[pallet::genesis_config]
@@ -285,12 +1190,14 @@ pub mod pallet {
 			NextAppId::<T>::put::<AppId>(next_app_id);
 
 			// Insert app keys. It verifies the length limitation of each key.
+			// Genesis keys do not hold any deposit.
 			for (key, (owner, id)) in app_keys {
 				let key = AppKeyFor::<T>::try_from(key)
 					.expect("DA Control Genesis contains invalid keys");
 				let value = AppKeyInfo {
 					id: AppId(id),
 					owner,
+					deposit: Zero::zero(),
 				};
 				AppKeys::<T>::insert(key, value);
 			}
@@ -307,6 +1214,220 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// Returns `true` if the application key of `id` was released.
+	pub fn is_app_id_retired(id: AppId) -> bool {
+		RetiredAppIds::<T>::contains_key(id)
+	}
+
+	/// Returns `true` if the submitter policy of `id` allows `who` to submit data.
+	pub fn is_allowed_submitter(id: AppId, who: &T::AccountId) -> bool {
+		let Some(AppSubmitterPolicy { owner, policy }) = SubmitterPolicies::<T>::get(id) else {
+			return true;
+		};
+		match policy {
+			SubmitterPolicy::Open => true,
+			SubmitterPolicy::Allowlist(submitters) => owner == *who || submitters.contains(who),
+			SubmitterPolicy::OwnerOnly => owner == *who,
+		}
+	}
+
+	/// Returns `true` if the padded scalars used by the extrinsics of `all_extrinsics_len` fit in
+	/// `max_scalars`, once the cap of `app_id` and the unused reservations of the other
+	/// applications are taken into account.
+	///
+	/// Application ID 0 is exempt from caps and reservations, so the transactions of governance,
+	/// which could lift them, are never locked out.
+	pub fn fits_block_space(
+		app_id: AppId,
+		all_extrinsics_len: &ExtrinsicLenOf<T>,
+		max_scalars: u32,
+	) -> bool {
+		let Some(total_scalars) = all_extrinsics_len.total_num_scalars() else {
+			return false;
+		};
+		if app_id == AppId(0) {
+			return total_scalars < max_scalars;
+		}
+
+		if let Some(cap) = BlockSpaceCaps::<T>::get(app_id) {
+			if all_extrinsics_len.num_scalars_of(app_id) > cap.mul_floor(max_scalars) {
+				return false;
+			}
+		}
+
+		let unused_reserved = ReservedLanes::<T>::get()
+			.into_iter()
+			.filter(|(id, _)| *id != app_id)
+			.map(|(id, share)| {
+				share
+					.mul_floor(max_scalars)
+					.saturating_sub(all_extrinsics_len.num_scalars_of(id))
+			})
+			.fold(0u32, |acc, unused| acc.saturating_add(unused));
+
+		total_scalars.saturating_add(unused_reserved) < max_scalars
+	}
+
+	/// Padded scalars used by each application in the last block, along with their caps and
+	/// reservations for the current block length.
+	///
+	/// Applications with reserved block space are always reported.
+	pub fn block_space_usage() -> Vec<AppBlockSpaceUsage> {
+		let block_length = DynamicBlockLength::<T>::get();
+		let max_scalars = block_length.rows.0.saturating_mul(block_length.cols.0);
+		let lanes = ReservedLanes::<T>::get();
+
+		let mut usage = LastBlockSpaceUsage::<T>::get().into_inner();
+		let unused_lanes = lanes
+			.keys()
+			.filter(|id| !usage.iter().any(|(used_id, _)| used_id == *id))
+			.map(|id| (*id, 0))
+			.collect::<Vec<_>>();
+		usage.extend(unused_lanes);
+		usage.sort_by_key(|(id, _)| *id);
+
+		usage
+			.into_iter()
+			.map(|(app_id, num_scalars)| AppBlockSpaceUsage {
+				app_id,
+				num_scalars,
+				cap: BlockSpaceCaps::<T>::get(app_id).map(|cap| cap.mul_floor(max_scalars)),
+				reserved: lanes
+					.get(&app_id)
+					.map(|share| share.mul_floor(max_scalars))
+					.unwrap_or_default(),
+			})
+			.collect()
+	}
+
+	/// Deposit reserved for `len` bytes of application metadata.
+	pub fn metadata_deposit(len: u32) -> BalanceOf<T> {
+		T::MetadataDepositPerByte::get()
+			.saturating_mul(len.into())
+			.saturating_add(T::MetadataDepositBase::get())
+	}
+
+	/// Block length for the proposed dimensions, if they are within bounds.
+	fn proposed_block_length(
+		rows: BlockLengthRows,
+		cols: BlockLengthColumns,
+	) -> Result<BlockLength, Error<T>> {
+		ensure!(
+			rows <= T::MaxBlockRows::get() && cols <= T::MaxBlockCols::get(),
+			Error::<T>::BlockDimensionsOutOfBounds
+		);
+		ensure!(
+			rows >= T::MinBlockRows::get() && cols >= T::MinBlockCols::get(),
+			Error::<T>::BlockDimensionsTooSmall
+		);
+
+		BlockLength::with_normal_ratio(rows, cols, BLOCK_CHUNK_SIZE, NORMAL_DISPATCH_RATIO)
+			.map_err(|_| Error::<T>::BlockDimensionsOutOfBounds)
+	}
+
+	fn ensure_future_activation(activation: BlockNumberFor<T>) -> Result<(), Error<T>> {
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(activation > now, Error::<T>::ActivationNotInFuture);
+		Ok(())
+	}
+
+	/// Inserts `proposal` after the proposals with the same or an earlier activation.
+	fn insert_scheduled(
+		scheduled: &mut ScheduledBlockLengthsFor<T>,
+		proposal: ScheduledBlockLengthFor<T>,
+	) -> Result<(), Error<T>> {
+		let index = scheduled.partition_point(|other| other.activation <= proposal.activation);
+		scheduled
+			.try_insert(index, proposal)
+			.map_err(|_| Error::<T>::TooManyScheduledBlockLengths)
+	}
+
+	fn remove_scheduled(
+		scheduled: &mut ScheduledBlockLengthsFor<T>,
+		id: &T::BlockLenProposalId,
+	) -> Result<ScheduledBlockLengthFor<T>, Error<T>> {
+		let index = scheduled
+			.iter()
+			.position(|proposal| &proposal.id == id)
+			.ok_or(Error::<T>::UnknownBlockLengthProposal)?;
+		Ok(scheduled.remove(index))
+	}
+
+	/// Applies the block length proposals whose activation is reached, and returns how many
+	/// were applied.
+	///
+	/// The rolling utilization of the grid starts again once the dimensions change.
+	fn apply_scheduled_block_lengths(now: BlockNumberFor<T>) -> u32 {
+		let scheduled = ScheduledBlockLengths::<T>::get();
+		let due = scheduled.partition_point(|proposal| proposal.activation <= now);
+		if due == 0 {
+			return 0;
+		}
+
+		let mut due_proposals = scheduled.into_inner();
+		let pending = due_proposals.split_off(due);
+		ScheduledBlockLengths::<T>::put(ScheduledBlockLengthsFor::<T>::truncate_from(pending));
+
+		for ScheduledBlockLength { id, rows, cols, .. } in due_proposals {
+			let block_length =
+				BlockLength::with_normal_ratio(rows, cols, BLOCK_CHUNK_SIZE, NORMAL_DISPATCH_RATIO);
+			match block_length {
+				Ok(block_length) => {
+					DynamicBlockLength::<T>::put(block_length);
+					Self::deposit_event(Event::BlockLengthProposalApplied { id, rows, cols });
+				},
+				Err(_) => log::warn!(
+					target: LOG_TARGET,
+					"Invalid scheduled block dimensions {} x {}",
+					rows.0,
+					cols.0
+				),
+			}
+		}
+
+		GridUtilization::<T>::kill();
+		due.saturated_into()
+	}
+
+	/// Grows or shrinks the block dimensions by one step, following the rolling utilization of
+	/// the grid.
+	///
+	/// It runs before any extrinsic of the block, so shrinking never invalidates included data.
+	fn adjust_block_dimensions(params: &DimensionAdjustment) {
+		let current_length = DynamicBlockLength::<T>::get();
+		let current = (current_length.rows, current_length.cols);
+		let Some(rolling) = GridUtilization::<T>::get() else {
+			return;
+		};
+		let min = (T::MinBlockRows::get(), T::MinBlockCols::get());
+		let max = (T::MaxBlockRows::get(), T::MaxBlockCols::get());
+
+		let Some((rows, cols)) = params.next_dimensions(rolling, current, min, max) else {
+			return;
+		};
+		let Ok(block_length) =
+			BlockLength::with_normal_ratio(rows, cols, BLOCK_CHUNK_SIZE, NORMAL_DISPATCH_RATIO)
+		else {
+			log::warn!(
+				target: LOG_TARGET,
+				"Invalid adjusted block dimensions {} x {}",
+				rows.0,
+				cols.0
+			);
+			return;
+		};
+
+		DynamicBlockLength::<T>::put(block_length);
+		let rescaled = DimensionAdjustment::rescaled_utilization(rolling, current, (rows, cols));
+		GridUtilization::<T>::put(rescaled);
+
+		Self::deposit_event(Event::BlockDimensionsAdjusted {
+			rows,
+			cols,
+			utilization: rolling,
+		});
+	}
+
 	/// Check if the block weight is acceptable to execute the extrinsic
 	/// We check the current normal ratio weight, if it's too high, it means we won't reduce the block size
 	pub fn is_block_weight_acceptable() -> bool {
@@ -330,13 +1451,32 @@ pub mod weight_helper {
 		let total_weight = basic_weight.saturating_add(data_root_weight);
 		(total_weight, DispatchClass::Normal)
 	}
+
+	/// Weight for `dataAvailability::submit_data_chunk`.
+	pub fn submit_data_chunk<T: Config>(data_len: usize) -> (Weight, DispatchClass) {
+		let data_len: u32 = data_len.saturated_into();
+		let basic_weight = T::WeightInfo::submit_data_chunk(data_len);
+		let data_root_weight = T::WeightInfo::data_root(data_len);
+		let total_weight = basic_weight.saturating_add(data_root_weight);
+		(total_weight, DispatchClass::Normal)
+	}
 }
 
-impl<Acc> AppKeyInfo<Acc>
+impl<Acc, Balance> AppKeyInfo<Acc, Balance>
 where
 	Acc: PartialEq,
 {
-	pub fn new(owner: Acc, id: AppId) -> Self {
-		Self { owner, id }
+	pub fn new(owner: Acc, id: AppId, deposit: Balance) -> Self {
+		Self { owner, id, deposit }
 	}
 }
+
+/// Root of a blob assembled by `finalize_blob`: the `blake2_256` hash of the concatenated hashes
+/// of its chunks, in order.
+pub fn blob_root<'a>(data_hashes: impl IntoIterator<Item = &'a H256>) -> H256 {
+	let hashes = data_hashes
+		.into_iter()
+		.flat_map(|hash| hash.0)
+		.collect::<Vec<_>>();
+	H256(blake2_256(&hashes))
+}
@@ -0,0 +1,28 @@
+use frame_support::{
+	traits::{GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+use sp_core::Get;
+
+use crate::{Config, Pallet};
+
+pub mod v1;
+
+pub fn migrate<T: Config>() -> Weight {
+	// At least one read: `StorageVersion`
+	let mut weight = T::DbWeight::get().reads(1);
+	let on_chain = Pallet::<T>::on_chain_storage_version();
+	let curr_version = Pallet::<T>::current_storage_version();
+
+	if on_chain < curr_version {
+		if on_chain < StorageVersion::new(1) {
+			weight = weight.saturating_add(v1::migrate::<T>());
+		}
+
+		// After migration, we need to update the storage version.
+		curr_version.put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	weight
+}
@@ -0,0 +1,37 @@
+//! # V1 Migrations
+//! - `AppKeys` values gain the `deposit` reserved from their owner.
+//!
+//! Keys created before the deposit existed are grandfathered with a zero deposit: nothing is
+//! reserved from their owners, and releasing them refunds nothing.
+use crate::{AppKeyInfoFor, AppKeys, Config, LOG_TARGET};
+
+use avail_core::AppId;
+use codec::{Decode, Encode};
+use frame_support::weights::Weight;
+use sp_core::Get;
+use sp_runtime::traits::Zero;
+
+/// `AppKeys` value before V1.
+#[derive(Decode, Encode)]
+pub struct OldAppKeyInfo<Acc> {
+	pub owner: Acc,
+	pub id: AppId,
+}
+
+pub fn migrate<T: Config>() -> Weight {
+	let mut translated = 0u64;
+	AppKeys::<T>::translate::<OldAppKeyInfo<T::AccountId>, _>(|_key, old| {
+		translated = translated.saturating_add(1);
+		Some(AppKeyInfoFor::<T> {
+			owner: old.owner,
+			id: old.id,
+			deposit: Zero::zero(),
+		})
+	});
+	log::info!(
+		target: LOG_TARGET,
+		"Migrated {translated} application keys to V1 with a zero deposit."
+	);
+
+	T::DbWeight::get().reads_writes(translated, translated)
+}
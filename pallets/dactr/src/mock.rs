@@ -61,6 +61,9 @@ parameter_types! {
 	pub const OperationalFeeMultiplier: u8 = 5;
 }
 impl pallet_transaction_payment::Config for Test {
+	type BlobLength = ();
+	type BlobLengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
+	type BlobMultiplierUpdate = ();
 	type FeeMultiplierUpdate = ();
 	type LengthMultiplierUpdate = ();
 	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
@@ -101,6 +104,16 @@ impl pallet_utility::Config for Test {
 parameter_types! {
 	pub const MaxAppKeyLength: u32 = 32;
 	pub const MaxAppDataLength: u32 = 512 * 1024; // 512 Kb
+	pub const MaxAppMetadataLength: u32 = 128;
+	pub const MaxAppSubmitters: u32 = 8;
+	pub const MaxReservedLanes: u32 = 4;
+	pub const MaxBlobChunks: u32 = 4;
+	pub const MaxScheduledBlockLengths: u32 = 4;
+	pub const AppKeyDeposit: Balance = 10 * AVL;
+	pub const MetadataDepositBase: Balance = AVL;
+	pub const MetadataDepositPerByte: Balance = AVL / 100;
+	pub const BlobDepositBase: Balance = AVL;
+	pub const BlobDepositPerChunk: Balance = AVL / 10;
 	pub const MinBlockRows: BlockLengthRows = BlockLengthRows(32);
 	pub const MaxBlockRows: BlockLengthRows = BlockLengthRows(1024);
 	pub const MinBlockCols: BlockLengthColumns = BlockLengthColumns(32);
@@ -109,12 +122,23 @@ parameter_types! {
 
 #[derive_impl(da_control::config_preludes::TestDefaultConfig)]
 impl da_control::Config for Test {
+	type AppKeyDeposit = AppKeyDeposit;
+	type Currency = Balances;
 	type MaxAppDataLength = MaxAppDataLength;
 	type MaxAppKeyLength = MaxAppKeyLength;
+	type MaxAppMetadataLength = MaxAppMetadataLength;
+	type MaxAppSubmitters = MaxAppSubmitters;
+	type MaxReservedLanes = MaxReservedLanes;
+	type MaxBlobChunks = MaxBlobChunks;
+	type MaxScheduledBlockLengths = MaxScheduledBlockLengths;
 	type MaxBlockCols = MaxBlockCols;
 	type MaxBlockRows = MaxBlockRows;
 	type MinBlockCols = MinBlockCols;
 	type MinBlockRows = MinBlockRows;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type BlobDepositBase = BlobDepositBase;
+	type BlobDepositPerChunk = BlobDepositPerChunk;
 	type RuntimeEvent = RuntimeEvent;
 }
 
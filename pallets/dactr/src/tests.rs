@@ -1,19 +1,54 @@
-use avail_core::{BlockLengthColumns, BlockLengthRows, BLOCK_CHUNK_SIZE, NORMAL_DISPATCH_RATIO};
-use frame_support::{assert_noop, assert_ok, error::BadOrigin};
-use frame_system::{limits::BlockLength, RawOrigin};
+use avail_core::{
+	currency::Balance, AppId, BlockLengthColumns, BlockLengthRows, BLOCK_CHUNK_SIZE,
+	NORMAL_DISPATCH_RATIO,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	error::BadOrigin,
+	traits::{Currency, GetStorageVersion, Hooks, StorageVersion},
+};
+use frame_system::{
+	limits::BlockLength, pallet_prelude::BlockNumberFor, AllExtrinsicsLen, ExtrinsicLenOf,
+	RawOrigin,
+};
 use sp_core::H256;
+use sp_runtime::Perbill;
+use sp_std::collections::btree_set::BTreeSet;
 
 use crate::{
+	blob_root, migrations,
 	mock::{
-		new_test_ext, DataAvailability, MaxAppDataLength, MaxBlockCols, MaxBlockRows, MinBlockCols,
-		MinBlockRows, RuntimeEvent, RuntimeOrigin, System, Test,
+		new_test_ext, AppKeyDeposit, Balances, BlobDepositBase, BlobDepositPerChunk,
+		DataAvailability, MaxAppDataLength, MaxBlobChunks, MaxBlockCols, MaxBlockRows,
+		MaxReservedLanes, MaxScheduledBlockLengths, MinBlockCols, MinBlockRows, RuntimeEvent,
+		RuntimeOrigin, System, Test,
 	},
-	AppDataFor, AppKeyFor, AppKeyInfoFor, Event,
+	AppBlockSpaceUsage, AppDataFor, AppKeyFor, AppKeyInfoFor, AppKeys, AppMetadata, AppMetadataFor,
+	AppSubmitterPolicy, BlobChunk, DimensionAdjustment, Event, ScheduledBlockLength,
+	SubmitterPolicy, SubmitterPolicyFor, SubmittersFor,
 };
 
 type Error = crate::Error<Test>;
 
 const ALICE: u64 = 1;
+const BOB: u64 = 2;
+
+fn key(raw: &[u8]) -> AppKeyFor<Test> {
+	AppKeyFor::<Test>::try_from(raw.to_vec()).unwrap()
+}
+
+fn metadata(raw: &[u8]) -> AppMetadataFor<Test> {
+	AppMetadataFor::<Test>::try_from(raw.to_vec()).unwrap()
+}
+
+/// Extrinsics length where each `(id, len)` is an extrinsic of `len` bytes of `AppId(id)`.
+fn extrinsics_len(txs: &[(u32, u32)]) -> ExtrinsicLenOf<Test> {
+	let mut len = ExtrinsicLenOf::<Test>::default();
+	for (id, tx_len) in txs {
+		len.add_padded(AppId(*id), *tx_len).unwrap();
+	}
+	len
+}
 
 mod create_application_key {
 	use super::*;
@@ -34,9 +69,11 @@ mod create_application_key {
 				DataAvailability::application_key(&new_key),
 				Some(AppKeyInfoFor::<Test> {
 					id: new_id,
-					owner: ALICE
+					owner: ALICE,
+					deposit: AppKeyDeposit::get(),
 				})
 			);
+			assert_eq!(Balances::reserved_balance(ALICE), AppKeyDeposit::get());
 
 			let event = RuntimeEvent::DataAvailability(Event::ApplicationKeyCreated {
 				key: new_key,
@@ -58,6 +95,16 @@ mod create_application_key {
 		})
 	}
 
+	#[test]
+	fn insufficient_balance() {
+		new_test_ext().execute_with(|| {
+			let poor: RuntimeOrigin = RawOrigin::Signed(4).into();
+
+			let err = DataAvailability::create_application_key(poor, key(b"New App"));
+			assert_noop!(err, pallet_balances::Error::<Test>::InsufficientBalance);
+		})
+	}
+
 	#[test]
 	fn app_key_already_exists() {
 		new_test_ext().execute_with(|| {
@@ -234,3 +281,1389 @@ mod submit_block_length_proposal {
 		})
 	}
 }
+
+mod transfer_application_key {
+	use super::*;
+
+	#[test]
+	fn transfer_application_key() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let data_avail = key(b"Data Avail");
+
+			assert_ok!(DataAvailability::transfer_application_key(
+				alice,
+				data_avail.clone(),
+				BOB
+			));
+			assert_eq!(
+				DataAvailability::application_key(&data_avail),
+				Some(AppKeyInfoFor::<Test> {
+					id: AppId(0),
+					owner: BOB,
+					deposit: 0,
+				})
+			);
+
+			let event = RuntimeEvent::DataAvailability(Event::ApplicationKeyTransferred {
+				key: data_avail,
+				id: AppId(0),
+				old_owner: ALICE,
+				new_owner: BOB,
+			});
+			System::assert_last_event(event);
+		})
+	}
+
+	#[test]
+	fn transfer_moves_metadata_deposit() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let data_avail = key(b"Data Avail");
+
+			assert_ok!(DataAvailability::set_application_metadata(
+				alice.clone(),
+				data_avail.clone(),
+				metadata(b"https://availproject.org"),
+				metadata(b"Avail"),
+			));
+			let deposit = Balances::reserved_balance(ALICE);
+			let bob_reserved = Balances::reserved_balance(BOB);
+			let alice_total = Balances::total_balance(&ALICE);
+
+			assert_ok!(DataAvailability::transfer_application_key(
+				alice, data_avail, BOB
+			));
+			assert_eq!(Balances::reserved_balance(ALICE), 0);
+			assert_eq!(Balances::total_balance(&ALICE), alice_total - deposit);
+			assert_eq!(Balances::reserved_balance(BOB), bob_reserved + deposit);
+		})
+	}
+
+	#[test]
+	fn transfer_moves_key_deposit() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let new_key = key(b"New App");
+
+			assert_ok!(DataAvailability::create_application_key(
+				alice.clone(),
+				new_key.clone()
+			));
+			assert_ok!(DataAvailability::transfer_application_key(
+				alice, new_key, BOB
+			));
+			assert_eq!(Balances::reserved_balance(ALICE), 0);
+			assert_eq!(Balances::reserved_balance(BOB), AppKeyDeposit::get());
+		})
+	}
+
+	#[test]
+	fn unknown_app_key() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+
+			let err = DataAvailability::transfer_application_key(alice, key(b"Unknown"), BOB);
+			assert_noop!(err, Error::UnknownAppKey);
+		})
+	}
+
+	#[test]
+	fn not_app_key_owner() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+
+			let err = DataAvailability::transfer_application_key(alice, key(b"Ethereum"), ALICE);
+			assert_noop!(err, Error::NotAppKeyOwner);
+		})
+	}
+}
+
+mod set_application_metadata {
+	use super::*;
+
+	#[test]
+	fn set_application_metadata() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let data_avail = key(b"Data Avail");
+			let url = metadata(b"https://availproject.org");
+			let description = metadata(b"Avail");
+			let deposit =
+				DataAvailability::metadata_deposit((url.len() + description.len()) as u32);
+
+			assert_ok!(DataAvailability::set_application_metadata(
+				alice,
+				data_avail.clone(),
+				url.clone(),
+				description.clone(),
+			));
+			assert_eq!(
+				DataAvailability::application_metadata(AppId(0)),
+				Some(AppMetadata {
+					url,
+					description,
+					deposit
+				})
+			);
+			assert_eq!(Balances::reserved_balance(ALICE), deposit);
+
+			let event = RuntimeEvent::DataAvailability(Event::ApplicationMetadataSet {
+				key: data_avail,
+				id: AppId(0),
+				owner: ALICE,
+				deposit,
+			});
+			System::assert_last_event(event);
+		})
+	}
+
+	#[test]
+	fn update_adjusts_deposit() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let data_avail = key(b"Data Avail");
+
+			assert_ok!(DataAvailability::set_application_metadata(
+				alice.clone(),
+				data_avail.clone(),
+				metadata(b"https://availproject.org"),
+				metadata(b"Avail"),
+			));
+			assert_ok!(DataAvailability::set_application_metadata(
+				alice,
+				data_avail,
+				metadata(b"https://avail.so"),
+				metadata(b""),
+			));
+			assert_eq!(
+				Balances::reserved_balance(ALICE),
+				DataAvailability::metadata_deposit(16)
+			);
+		})
+	}
+
+	#[test]
+	fn empty_metadata_clears_and_refunds() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let data_avail = key(b"Data Avail");
+
+			assert_ok!(DataAvailability::set_application_metadata(
+				alice.clone(),
+				data_avail.clone(),
+				metadata(b"https://availproject.org"),
+				metadata(b"Avail"),
+			));
+			assert_ok!(DataAvailability::set_application_metadata(
+				alice,
+				data_avail.clone(),
+				metadata(b""),
+				metadata(b""),
+			));
+			assert_eq!(DataAvailability::application_metadata(AppId(0)), None);
+			assert_eq!(Balances::reserved_balance(ALICE), 0);
+
+			let event = RuntimeEvent::DataAvailability(Event::ApplicationMetadataCleared {
+				key: data_avail,
+				id: AppId(0),
+			});
+			System::assert_last_event(event);
+		})
+	}
+
+	#[test]
+	fn not_app_key_owner() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+
+			let err = DataAvailability::set_application_metadata(
+				alice,
+				key(b"Ethereum"),
+				metadata(b"https://ethereum.org"),
+				metadata(b""),
+			);
+			assert_noop!(err, Error::NotAppKeyOwner);
+		})
+	}
+
+	#[test]
+	fn insufficient_balance() {
+		new_test_ext().execute_with(|| {
+			let poor: u64 = 4;
+			let poor_origin: RuntimeOrigin = RawOrigin::Signed(poor).into();
+			let new_key = key(b"New App");
+			Balances::make_free_balance_be(&poor, AppKeyDeposit::get() + 1);
+
+			assert_ok!(DataAvailability::create_application_key(
+				poor_origin.clone(),
+				new_key.clone()
+			));
+			let err = DataAvailability::set_application_metadata(
+				poor_origin,
+				new_key,
+				metadata(b"https://example.com"),
+				metadata(b""),
+			);
+			assert_noop!(err, pallet_balances::Error::<Test>::InsufficientBalance);
+		})
+	}
+}
+
+mod release_application_key {
+	use super::*;
+
+	#[test]
+	fn release_application_key() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let new_key = key(b"New App");
+			let free = Balances::free_balance(ALICE);
+
+			assert_ok!(DataAvailability::create_application_key(
+				alice.clone(),
+				new_key.clone()
+			));
+			let id = DataAvailability::application_key(&new_key).unwrap().id;
+			assert_ok!(DataAvailability::set_application_metadata(
+				alice.clone(),
+				new_key.clone(),
+				metadata(b"https://example.com"),
+				metadata(b""),
+			));
+
+			assert_ok!(DataAvailability::release_application_key(
+				alice,
+				new_key.clone()
+			));
+			assert_eq!(DataAvailability::application_key(&new_key), None);
+			assert_eq!(DataAvailability::application_metadata(id), None);
+			assert!(DataAvailability::is_app_id_retired(id));
+			assert_eq!(Balances::reserved_balance(ALICE), 0);
+			assert_eq!(Balances::free_balance(ALICE), free);
+
+			let event = RuntimeEvent::DataAvailability(Event::ApplicationKeyReleased {
+				key: new_key,
+				id,
+				owner: ALICE,
+			});
+			System::assert_last_event(event);
+		})
+	}
+
+	#[test]
+	fn released_app_id_loses_block_space() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let new_key = key(b"New App");
+			let share = Some(Perbill::from_percent(20));
+
+			assert_ok!(DataAvailability::create_application_key(
+				alice.clone(),
+				new_key.clone()
+			));
+			let id = DataAvailability::application_key(&new_key).unwrap().id;
+			assert_ok!(DataAvailability::set_block_space_cap(
+				root.clone(),
+				id,
+				share
+			));
+			assert_ok!(DataAvailability::set_reserved_block_space(
+				root.clone(),
+				id,
+				share
+			));
+			assert_ok!(DataAvailability::set_reserved_block_space(
+				root,
+				AppId(1),
+				share
+			));
+
+			assert_ok!(DataAvailability::release_application_key(alice, new_key));
+			assert_eq!(DataAvailability::block_space_cap(id), None);
+			assert_eq!(DataAvailability::reserved_lanes().get(&id), None);
+			assert_eq!(
+				DataAvailability::reserved_lanes().get(&AppId(1)).copied(),
+				share
+			);
+		})
+	}
+
+	#[test]
+	fn released_key_gets_new_id() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let new_key = key(b"New App");
+
+			assert_ok!(DataAvailability::create_application_key(
+				alice.clone(),
+				new_key.clone()
+			));
+			let old_id = DataAvailability::application_key(&new_key).unwrap().id;
+			assert_ok!(DataAvailability::release_application_key(
+				alice.clone(),
+				new_key.clone()
+			));
+			assert_ok!(DataAvailability::create_application_key(
+				alice,
+				new_key.clone()
+			));
+
+			let new_id = DataAvailability::application_key(&new_key).unwrap().id;
+			assert!(new_id > old_id);
+			assert!(!DataAvailability::is_app_id_retired(new_id));
+		})
+	}
+
+	#[test]
+	fn unknown_app_key() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+
+			let err = DataAvailability::release_application_key(alice, key(b"Unknown"));
+			assert_noop!(err, Error::UnknownAppKey);
+		})
+	}
+
+	#[test]
+	fn not_app_key_owner() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+
+			let err = DataAvailability::release_application_key(alice, key(b"Ethereum"));
+			assert_noop!(err, Error::NotAppKeyOwner);
+		})
+	}
+}
+
+mod set_submitter_policy {
+	use super::*;
+
+	fn allowlist(submitters: &[u64]) -> SubmitterPolicyFor<Test> {
+		let submitters = submitters.iter().copied().collect::<BTreeSet<_>>();
+		SubmitterPolicy::Allowlist(SubmittersFor::<Test>::try_from(submitters).unwrap())
+	}
+
+	#[test]
+	fn set_submitter_policy() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let data_avail = key(b"Data Avail");
+			let policy = allowlist(&[BOB]);
+
+			assert_ok!(DataAvailability::set_submitter_policy(
+				alice,
+				data_avail.clone(),
+				policy.clone()
+			));
+			assert_eq!(
+				DataAvailability::submitter_policy(AppId(0)),
+				Some(AppSubmitterPolicy {
+					owner: ALICE,
+					policy: policy.clone()
+				})
+			);
+			assert!(DataAvailability::is_allowed_submitter(AppId(0), &ALICE));
+			assert!(DataAvailability::is_allowed_submitter(AppId(0), &BOB));
+			assert!(!DataAvailability::is_allowed_submitter(AppId(0), &3));
+
+			let event = RuntimeEvent::DataAvailability(Event::SubmitterPolicySet {
+				key: data_avail,
+				id: AppId(0),
+				policy,
+			});
+			System::assert_last_event(event);
+		})
+	}
+
+	#[test]
+	fn open_policy_removes_entry() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let data_avail = key(b"Data Avail");
+
+			assert_ok!(DataAvailability::set_submitter_policy(
+				alice.clone(),
+				data_avail.clone(),
+				SubmitterPolicy::OwnerOnly
+			));
+			assert!(!DataAvailability::is_allowed_submitter(AppId(0), &BOB));
+			assert_ok!(DataAvailability::set_submitter_policy(
+				alice,
+				data_avail,
+				SubmitterPolicy::Open
+			));
+			assert_eq!(DataAvailability::submitter_policy(AppId(0)), None);
+			assert!(DataAvailability::is_allowed_submitter(AppId(0), &BOB));
+		})
+	}
+
+	#[test]
+	fn transfer_updates_owner() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let data_avail = key(b"Data Avail");
+
+			assert_ok!(DataAvailability::set_submitter_policy(
+				alice.clone(),
+				data_avail.clone(),
+				SubmitterPolicy::OwnerOnly
+			));
+			assert_ok!(DataAvailability::transfer_application_key(
+				alice, data_avail, BOB
+			));
+			assert!(DataAvailability::is_allowed_submitter(AppId(0), &BOB));
+			assert!(!DataAvailability::is_allowed_submitter(AppId(0), &ALICE));
+		})
+	}
+
+	#[test]
+	fn release_removes_policy() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let data_avail = key(b"Data Avail");
+
+			assert_ok!(DataAvailability::set_submitter_policy(
+				alice.clone(),
+				data_avail.clone(),
+				SubmitterPolicy::OwnerOnly
+			));
+			assert_ok!(DataAvailability::release_application_key(alice, data_avail));
+			assert_eq!(DataAvailability::submitter_policy(AppId(0)), None);
+		})
+	}
+
+	#[test]
+	fn not_app_key_owner() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+
+			let err = DataAvailability::set_submitter_policy(
+				alice,
+				key(b"Ethereum"),
+				SubmitterPolicy::OwnerOnly,
+			);
+			assert_noop!(err, Error::NotAppKeyOwner);
+		})
+	}
+}
+
+mod blob_assembly {
+	use super::*;
+
+	fn chunk(raw: &[u8]) -> AppDataFor<Test> {
+		AppDataFor::<Test>::try_from(raw.to_vec()).unwrap()
+	}
+
+	fn blob_chunk(raw: &[u8]) -> BlobChunk<BlockNumberFor<Test>> {
+		BlobChunk {
+			data_hash: H256(sp_io::hashing::blake2_256(raw)),
+			len: raw.len() as u32,
+			block_number: System::block_number(),
+			extrinsic_index: System::extrinsic_index().unwrap(),
+		}
+	}
+
+	fn root_of(chunks: &[&[u8]]) -> H256 {
+		let hashes = chunks
+			.iter()
+			.map(|raw| H256(sp_io::hashing::blake2_256(raw)))
+			.collect::<Vec<_>>();
+		blob_root(&hashes)
+	}
+
+	fn blob_deposit(num_chunks: u32) -> Balance {
+		BlobDepositBase::get() + BlobDepositPerChunk::get() * Balance::from(num_chunks)
+	}
+
+	/// Submits `chunks` from `who` and assembles them into a blob.
+	fn assemble(who: u64, chunks: &[&[u8]]) -> H256 {
+		let origin: RuntimeOrigin = RawOrigin::Signed(who).into();
+		for raw in chunks {
+			assert_ok!(DataAvailability::submit_data_chunk(
+				origin.clone(),
+				chunk(raw)
+			));
+		}
+		assert_ok!(DataAvailability::finalize_blob(origin));
+		root_of(chunks)
+	}
+
+	#[test]
+	fn submit_data_chunk_and_finalize_blob() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let chunks: [&[u8]; 2] = [b"First chunk", b"Second chunk"];
+			let reserved = Balances::reserved_balance(ALICE);
+
+			for (index, raw) in chunks.iter().enumerate() {
+				assert_ok!(DataAvailability::submit_data_chunk(
+					alice.clone(),
+					chunk(raw)
+				));
+				let event = RuntimeEvent::DataAvailability(Event::BlobChunkSubmitted {
+					who: ALICE,
+					data_hash: H256(sp_io::hashing::blake2_256(raw)),
+					index: index as u32,
+				});
+				System::assert_last_event(event);
+				assert_eq!(
+					Balances::reserved_balance(ALICE),
+					reserved + blob_deposit(index as u32 + 1)
+				);
+			}
+			let expected_chunks = chunks.iter().map(|raw| blob_chunk(raw)).collect::<Vec<_>>();
+			let pending = DataAvailability::pending_blob(ALICE);
+			assert_eq!(pending.chunks.into_inner(), expected_chunks);
+			assert_eq!(pending.deposit, blob_deposit(2));
+
+			assert_ok!(DataAvailability::finalize_blob(alice));
+
+			// The root is derived from the chunks, and the deposit stays reserved.
+			let blob_root = root_of(&chunks);
+			assert!(DataAvailability::pending_blob(ALICE).chunks.is_empty());
+			let manifest = DataAvailability::blob_manifest(blob_root).unwrap();
+			assert_eq!(manifest.owner, ALICE);
+			assert_eq!(manifest.total_len, 23);
+			assert_eq!(manifest.chunks.into_inner(), expected_chunks);
+			assert_eq!(manifest.blob_root, blob_root);
+			assert_eq!(manifest.deposit, blob_deposit(2));
+			assert_eq!(
+				Balances::reserved_balance(ALICE),
+				reserved + blob_deposit(2)
+			);
+
+			let event = RuntimeEvent::DataAvailability(Event::BlobAssembled {
+				who: ALICE,
+				blob_root,
+				total_len: 23,
+				num_chunks: 2,
+			});
+			System::assert_last_event(event);
+		})
+	}
+
+	#[test]
+	fn blob_root_commits_to_the_chunks() {
+		new_test_ext().execute_with(|| {
+			let root = root_of(&[b"First chunk", b"Second chunk"]);
+			assert_ne!(root, root_of(&[b"Second chunk", b"First chunk"]));
+			assert_ne!(root, root_of(&[b"First chunkSecond chunk"]));
+
+			// Other chunks cannot take the root of a blob before it is finalized.
+			let junk = assemble(BOB, &[b"Junk"]);
+			assert_ne!(junk, root);
+			assert_eq!(assemble(ALICE, &[b"First chunk", b"Second chunk"]), root);
+		})
+	}
+
+	#[test]
+	fn chunk_cannot_be_empty() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+
+			let err = DataAvailability::submit_data_chunk(alice, chunk(b""));
+			assert_noop!(err, Error::DataCannotBeEmpty);
+		})
+	}
+
+	#[test]
+	fn insufficient_balance() {
+		new_test_ext().execute_with(|| {
+			let poor: RuntimeOrigin = RawOrigin::Signed(4).into();
+
+			let err = DataAvailability::submit_data_chunk(poor, chunk(b"Chunk"));
+			assert_noop!(err, pallet_balances::Error::<Test>::InsufficientBalance);
+		})
+	}
+
+	#[test]
+	fn too_many_blob_chunks() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+
+			for _ in 0..MaxBlobChunks::get() {
+				assert_ok!(DataAvailability::submit_data_chunk(
+					alice.clone(),
+					chunk(b"Chunk")
+				));
+			}
+			let err = DataAvailability::submit_data_chunk(alice, chunk(b"Chunk"));
+			assert_noop!(err, Error::TooManyBlobChunks);
+		})
+	}
+
+	#[test]
+	fn no_pending_blob() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let bob: RuntimeOrigin = RawOrigin::Signed(BOB).into();
+
+			let err = DataAvailability::finalize_blob(alice.clone());
+			assert_noop!(err, Error::NoPendingBlob);
+			let err = DataAvailability::cancel_blob(alice.clone());
+			assert_noop!(err, Error::NoPendingBlob);
+
+			// Chunks are pending per account.
+			assert_ok!(DataAvailability::submit_data_chunk(bob, chunk(b"Chunk")));
+			let err = DataAvailability::finalize_blob(alice);
+			assert_noop!(err, Error::NoPendingBlob);
+		})
+	}
+
+	#[test]
+	fn blob_already_assembled() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			assemble(ALICE, &[b"Chunk"]);
+
+			assert_ok!(DataAvailability::submit_data_chunk(
+				alice.clone(),
+				chunk(b"Chunk")
+			));
+			let err = DataAvailability::finalize_blob(alice);
+			assert_noop!(err, Error::BlobAlreadyAssembled);
+			assert_eq!(DataAvailability::pending_blob(ALICE).chunks.len(), 1);
+		})
+	}
+
+	#[test]
+	fn cancel_blob() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let reserved = Balances::reserved_balance(ALICE);
+			for raw in [b"First chunk", b"Other chunk"] {
+				assert_ok!(DataAvailability::submit_data_chunk(
+					alice.clone(),
+					chunk(raw)
+				));
+			}
+
+			assert_ok!(DataAvailability::cancel_blob(alice));
+			assert!(DataAvailability::pending_blob(ALICE).chunks.is_empty());
+			assert_eq!(Balances::reserved_balance(ALICE), reserved);
+
+			let event = RuntimeEvent::DataAvailability(Event::BlobCancelled {
+				who: ALICE,
+				num_chunks: 2,
+			});
+			System::assert_last_event(event);
+		})
+	}
+
+	#[test]
+	fn remove_blob() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+			let reserved = Balances::reserved_balance(ALICE);
+			let blob_root = assemble(ALICE, &[b"Chunk"]);
+
+			assert_ok!(DataAvailability::remove_blob(alice.clone(), blob_root));
+			assert_eq!(DataAvailability::blob_manifest(blob_root), None);
+			assert_eq!(Balances::reserved_balance(ALICE), reserved);
+
+			let event = RuntimeEvent::DataAvailability(Event::BlobRemoved {
+				who: ALICE,
+				blob_root,
+			});
+			System::assert_last_event(event);
+
+			// The blob can be assembled again.
+			assert_eq!(assemble(ALICE, &[b"Chunk"]), blob_root);
+		})
+	}
+
+	#[test]
+	fn unknown_blob() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+
+			let err = DataAvailability::remove_blob(alice, H256::zero());
+			assert_noop!(err, Error::UnknownBlob);
+		})
+	}
+
+	#[test]
+	fn not_blob_owner() {
+		new_test_ext().execute_with(|| {
+			let bob: RuntimeOrigin = RawOrigin::Signed(BOB).into();
+			let blob_root = assemble(ALICE, &[b"Chunk"]);
+
+			let err = DataAvailability::remove_blob(bob, blob_root);
+			assert_noop!(err, Error::NotBlobOwner);
+		})
+	}
+}
+
+mod block_space {
+	use super::*;
+
+	#[test]
+	fn set_block_space_cap() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			let cap = Some(Perbill::from_percent(10));
+
+			assert_ok!(DataAvailability::set_block_space_cap(
+				root.clone(),
+				AppId(1),
+				cap
+			));
+			assert_eq!(DataAvailability::block_space_cap(AppId(1)), cap);
+
+			let event = RuntimeEvent::DataAvailability(Event::BlockSpaceCapSet {
+				app_id: AppId(1),
+				cap,
+			});
+			System::assert_last_event(event);
+
+			// 310 bytes use 11 scalars, over the cap of 10 scalars.
+			let len = extrinsics_len(&[(1, 310)]);
+			assert!(!DataAvailability::fits_block_space(AppId(1), &len, 100));
+			assert!(DataAvailability::fits_block_space(AppId(1), &len, 110));
+			assert!(DataAvailability::fits_block_space(AppId(2), &len, 100));
+
+			assert_ok!(DataAvailability::set_block_space_cap(root, AppId(1), None));
+			assert_eq!(DataAvailability::block_space_cap(AppId(1)), None);
+			assert!(DataAvailability::fits_block_space(AppId(1), &len, 100));
+		})
+	}
+
+	#[test]
+	fn set_block_space_cap_requires_root() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+
+			let err = DataAvailability::set_block_space_cap(alice, AppId(1), None);
+			assert_noop!(err, BadOrigin);
+		})
+	}
+
+	#[test]
+	fn set_reserved_block_space() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			let share = Some(Perbill::from_percent(50));
+
+			assert_ok!(DataAvailability::set_reserved_block_space(
+				root.clone(),
+				AppId(2),
+				share
+			));
+			assert_eq!(
+				DataAvailability::reserved_lanes().get(&AppId(2)).copied(),
+				share
+			);
+
+			let event = RuntimeEvent::DataAvailability(Event::ReservedBlockSpaceSet {
+				app_id: AppId(2),
+				share,
+			});
+			System::assert_last_event(event);
+
+			// 11 scalars of `AppId(1)` fit along the 50 reserved to `AppId(2)`, but 51 do not.
+			let len = extrinsics_len(&[(1, 310)]);
+			assert!(DataAvailability::fits_block_space(AppId(1), &len, 100));
+			let len = extrinsics_len(&[(1, 1_550)]);
+			assert!(!DataAvailability::fits_block_space(AppId(1), &len, 100));
+			assert!(DataAvailability::fits_block_space(AppId(2), &len, 100));
+
+			// Reserved space used by `AppId(2)` is only counted once: 40 + 20 + (50 - 20) scalars.
+			let len = extrinsics_len(&[(1, 1_209), (2, 589)]);
+			assert!(DataAvailability::fits_block_space(AppId(1), &len, 100));
+
+			assert_ok!(DataAvailability::set_reserved_block_space(
+				root,
+				AppId(2),
+				None
+			));
+			assert!(DataAvailability::reserved_lanes().is_empty());
+		})
+	}
+
+	#[test]
+	fn reserved_block_space_exceeded() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+
+			assert_ok!(DataAvailability::set_reserved_block_space(
+				root.clone(),
+				AppId(1),
+				Some(Perbill::from_percent(60))
+			));
+			let err = DataAvailability::set_reserved_block_space(
+				root.clone(),
+				AppId(2),
+				Some(Perbill::from_percent(50)),
+			);
+			assert_noop!(err, Error::ReservedBlockSpaceExceeded);
+
+			// Updating an existing reservation replaces it.
+			assert_ok!(DataAvailability::set_reserved_block_space(
+				root.clone(),
+				AppId(1),
+				Some(Perbill::from_percent(90))
+			));
+
+			// Part of the block always stays unreserved.
+			let err = DataAvailability::set_reserved_block_space(
+				root,
+				AppId(1),
+				Some(Perbill::from_percent(100)),
+			);
+			assert_noop!(err, Error::ReservedBlockSpaceExceeded);
+		})
+	}
+
+	#[test]
+	fn too_many_reserved_lanes() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			let share = Some(Perbill::from_percent(10));
+
+			for id in 1..=MaxReservedLanes::get() {
+				assert_ok!(DataAvailability::set_reserved_block_space(
+					root.clone(),
+					AppId(id),
+					share
+				));
+			}
+			let err = DataAvailability::set_reserved_block_space(root, AppId(100), share);
+			assert_noop!(err, Error::TooManyReservedLanes);
+		})
+	}
+
+	#[test]
+	fn app_id_zero_is_exempt() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			let share = Some(Perbill::zero());
+
+			let err = DataAvailability::set_block_space_cap(root.clone(), AppId(0), share);
+			assert_noop!(err, Error::CannotRestrictAppIdZero);
+			let err = DataAvailability::set_reserved_block_space(root.clone(), AppId(0), share);
+			assert_noop!(err, Error::CannotRestrictAppIdZero);
+
+			// The unused 90 scalars reserved to `AppId(1)` only apply to other applications.
+			assert_ok!(DataAvailability::set_reserved_block_space(
+				root,
+				AppId(1),
+				Some(Perbill::from_percent(90))
+			));
+			let len = extrinsics_len(&[(0, 310)]);
+			assert!(DataAvailability::fits_block_space(AppId(0), &len, 100));
+			let len = extrinsics_len(&[(2, 310)]);
+			assert!(!DataAvailability::fits_block_space(AppId(2), &len, 100));
+
+			// The whole block still bounds `AppId(0)`.
+			let len = extrinsics_len(&[(0, 310)]);
+			assert!(!DataAvailability::fits_block_space(AppId(0), &len, 11));
+		})
+	}
+
+	#[test]
+	fn set_reserved_block_space_requires_root() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+
+			let err = DataAvailability::set_reserved_block_space(alice, AppId(1), None);
+			assert_noop!(err, BadOrigin);
+		})
+	}
+
+	#[test]
+	fn block_space_usage() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			let cap = Perbill::from_percent(50);
+			let share = Perbill::from_percent(20);
+			assert_ok!(DataAvailability::set_block_space_cap(
+				root.clone(),
+				AppId(1),
+				Some(cap)
+			));
+			assert_ok!(DataAvailability::set_reserved_block_space(
+				root,
+				AppId(3),
+				Some(share)
+			));
+
+			AllExtrinsicsLen::<Test>::put(extrinsics_len(&[(1, 310), (2, 30)]));
+			DataAvailability::on_finalize(1);
+
+			let block_length = System::block_length();
+			let max_scalars = block_length.rows.0 * block_length.cols.0;
+			let expected = vec![
+				AppBlockSpaceUsage {
+					app_id: AppId(1),
+					num_scalars: 11,
+					cap: Some(cap.mul_floor(max_scalars)),
+					reserved: 0,
+				},
+				AppBlockSpaceUsage {
+					app_id: AppId(2),
+					num_scalars: 2,
+					cap: None,
+					reserved: 0,
+				},
+				AppBlockSpaceUsage {
+					app_id: AppId(3),
+					num_scalars: 0,
+					cap: None,
+					reserved: share.mul_floor(max_scalars),
+				},
+			];
+			assert_eq!(DataAvailability::block_space_usage(), expected);
+		})
+	}
+}
+
+mod dimension_adjustment {
+	use super::*;
+
+	fn params() -> DimensionAdjustment {
+		DimensionAdjustment {
+			grow_above: Perbill::from_percent(60),
+			shrink_below: Perbill::from_percent(20),
+			smoothing: Perbill::from_percent(50),
+			period: 2,
+		}
+	}
+
+	fn dimensions() -> (BlockLengthRows, BlockLengthColumns) {
+		let block_length = System::block_length();
+		(block_length.rows, block_length.cols)
+	}
+
+	fn capacity((rows, cols): (BlockLengthRows, BlockLengthColumns)) -> u32 {
+		rows.0 * cols.0
+	}
+
+	/// Runs the next `n` blocks, where each block uses `load` of the grid, and returns the
+	/// dimensions at the start of each block.
+	fn run_blocks(n: u32, load: Perbill) -> Vec<(BlockLengthRows, BlockLengthColumns)> {
+		(0..n)
+			.map(|_| {
+				let now = System::block_number() + 1;
+				System::set_block_number(now);
+				DataAvailability::on_initialize(now);
+
+				let current = dimensions();
+				let used = load.mul_floor(capacity(current));
+				if used > 0 {
+					// The padding of the extrinsic adds at most one scalar.
+					let len = used * 31 - 1;
+					AllExtrinsicsLen::<Test>::put(extrinsics_len(&[(1, len)]));
+				} else {
+					AllExtrinsicsLen::<Test>::kill();
+				}
+				DataAvailability::on_finalize(now);
+
+				current
+			})
+			.collect()
+	}
+
+	#[test]
+	fn set_dimension_adjustment() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			let params = Some(params());
+
+			assert_ok!(DataAvailability::set_dimension_adjustment(
+				root.clone(),
+				params
+			));
+			assert_eq!(DataAvailability::dimension_adjustment(), params);
+			assert_eq!(DataAvailability::grid_utilization(), None);
+
+			let event = RuntimeEvent::DataAvailability(Event::DimensionAdjustmentSet { params });
+			System::assert_last_event(event);
+
+			assert_ok!(DataAvailability::set_dimension_adjustment(root, None));
+			assert_eq!(DataAvailability::dimension_adjustment(), None);
+		})
+	}
+
+	#[test]
+	fn set_dimension_adjustment_invalid() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			let invalid = [
+				DimensionAdjustment {
+					shrink_below: Perbill::from_percent(60),
+					..params()
+				},
+				DimensionAdjustment {
+					smoothing: Perbill::zero(),
+					..params()
+				},
+				DimensionAdjustment {
+					period: 0,
+					..params()
+				},
+			];
+
+			for params in invalid {
+				let err = DataAvailability::set_dimension_adjustment(root.clone(), Some(params));
+				assert_noop!(err, Error::InvalidDimensionAdjustment);
+			}
+		})
+	}
+
+	#[test]
+	fn set_dimension_adjustment_requires_root() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+
+			let err = DataAvailability::set_dimension_adjustment(alice, Some(params()));
+			assert_noop!(err, BadOrigin);
+		})
+	}
+
+	#[test]
+	fn disabled_adjustment_keeps_dimensions() {
+		new_test_ext().execute_with(|| {
+			let initial = dimensions();
+
+			run_blocks(10, Perbill::one());
+
+			assert_eq!(dimensions(), initial);
+			assert_eq!(DataAvailability::grid_utilization(), None);
+		})
+	}
+
+	#[test]
+	fn grows_under_high_load() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			assert_ok!(DataAvailability::set_dimension_adjustment(
+				root,
+				Some(params())
+			));
+
+			let history = run_blocks(40, Perbill::one());
+
+			// The grid never shrinks, and its capacity at most doubles per block.
+			for step in history.windows(2) {
+				let (previous, next) = (capacity(step[0]), capacity(step[1]));
+				assert!(previous <= next && next <= 2 * previous);
+			}
+			assert_eq!(dimensions(), (MaxBlockRows::get(), MaxBlockCols::get()));
+
+			let (rows, cols) = dimensions();
+			let adjusted = System::events().into_iter().any(|record| {
+				matches!(
+					record.event,
+					RuntimeEvent::DataAvailability(Event::BlockDimensionsAdjusted { rows: r, cols: c, .. })
+						if r == rows && c == cols
+				)
+			});
+			assert!(adjusted);
+		})
+	}
+
+	#[test]
+	fn shrinks_under_low_load() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			assert_ok!(DataAvailability::set_dimension_adjustment(
+				root,
+				Some(params())
+			));
+
+			let history = run_blocks(40, Perbill::zero());
+
+			// The grid never grows, and its capacity at most halves per block.
+			for step in history.windows(2) {
+				let (previous, next) = (capacity(step[0]), capacity(step[1]));
+				assert!(next <= previous && previous <= 2 * next);
+			}
+			assert_eq!(dimensions(), (MinBlockRows::get(), MinBlockCols::get()));
+		})
+	}
+
+	#[test]
+	fn keeps_dimensions_between_thresholds() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			assert_ok!(DataAvailability::set_dimension_adjustment(
+				root,
+				Some(params())
+			));
+			let initial = dimensions();
+
+			run_blocks(20, Perbill::from_percent(40));
+
+			assert_eq!(dimensions(), initial);
+			let utilization = DataAvailability::grid_utilization().unwrap();
+			assert!(utilization >= Perbill::from_percent(40));
+			assert!(utilization < Perbill::from_percent(41));
+		})
+	}
+}
+
+mod scheduled_block_length {
+	use super::*;
+
+	fn dimensions() -> (BlockLengthRows, BlockLengthColumns) {
+		let block_length = System::block_length();
+		(block_length.rows, block_length.cols)
+	}
+
+	/// Initializes the next blocks up to block `n`.
+	fn run_to_block(n: BlockNumberFor<Test>) {
+		while System::block_number() < n {
+			let now = System::block_number() + 1;
+			System::set_block_number(now);
+			DataAvailability::on_initialize(now);
+		}
+	}
+
+	#[test]
+	fn schedule_block_length_proposal() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			let initial = dimensions();
+			let rows = BlockLengthRows(128);
+			let cols = BlockLengthColumns(64);
+
+			assert_ok!(DataAvailability::schedule_block_length_proposal(
+				root, rows.0, cols.0, 5
+			));
+
+			let event = RuntimeEvent::DataAvailability(Event::BlockLengthProposalScheduled {
+				id: 1,
+				rows,
+				cols,
+				activation: 5,
+			});
+			System::assert_last_event(event);
+			let expected = ScheduledBlockLength {
+				id: 1,
+				rows,
+				cols,
+				activation: 5,
+			};
+			assert_eq!(
+				DataAvailability::scheduled_block_lengths().to_vec(),
+				vec![expected]
+			);
+
+			run_to_block(4);
+			assert_eq!(dimensions(), initial);
+
+			run_to_block(5);
+			let block_length =
+				BlockLength::with_normal_ratio(rows, cols, BLOCK_CHUNK_SIZE, NORMAL_DISPATCH_RATIO)
+					.unwrap();
+			assert_eq!(System::block_length(), block_length);
+			assert!(DataAvailability::scheduled_block_lengths().is_empty());
+
+			let event = RuntimeEvent::DataAvailability(Event::BlockLengthProposalApplied {
+				id: 1,
+				rows,
+				cols,
+			});
+			System::assert_last_event(event);
+		})
+	}
+
+	#[test]
+	fn schedule_keeps_activation_order() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+
+			for activation in [10, 5, 10, 7] {
+				assert_ok!(DataAvailability::schedule_block_length_proposal(
+					root.clone(),
+					64,
+					64,
+					activation
+				));
+			}
+
+			let scheduled = DataAvailability::scheduled_block_lengths()
+				.iter()
+				.map(|proposal| (proposal.id, proposal.activation))
+				.collect::<Vec<_>>();
+			assert_eq!(scheduled, vec![(2, 5), (4, 7), (1, 10), (3, 10)]);
+
+			run_to_block(7);
+			let scheduled = DataAvailability::scheduled_block_lengths();
+			assert_eq!(scheduled.len(), 2);
+			assert_eq!(dimensions(), (BlockLengthRows(64), BlockLengthColumns(64)));
+		})
+	}
+
+	#[test]
+	fn schedule_block_length_proposal_errors() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			let max_rows = MaxBlockRows::get().0;
+			let min_cols = MinBlockCols::get().0;
+
+			let err = DataAvailability::schedule_block_length_proposal(root.clone(), 64, 64, 1);
+			assert_noop!(err, Error::ActivationNotInFuture);
+
+			let err =
+				DataAvailability::schedule_block_length_proposal(root.clone(), max_rows * 2, 64, 5);
+			assert_noop!(err, Error::BlockDimensionsOutOfBounds);
+
+			let err =
+				DataAvailability::schedule_block_length_proposal(root.clone(), 64, min_cols / 2, 5);
+			assert_noop!(err, Error::BlockDimensionsTooSmall);
+
+			for _ in 0..MaxScheduledBlockLengths::get() {
+				assert_ok!(DataAvailability::schedule_block_length_proposal(
+					root.clone(),
+					64,
+					64,
+					5
+				));
+			}
+			let err = DataAvailability::schedule_block_length_proposal(root, 64, 64, 5);
+			assert_noop!(err, Error::TooManyScheduledBlockLengths);
+		})
+	}
+
+	#[test]
+	fn cancel_block_length_proposal() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			let initial = dimensions();
+
+			assert_ok!(DataAvailability::schedule_block_length_proposal(
+				root.clone(),
+				64,
+				64,
+				5
+			));
+			assert_ok!(DataAvailability::cancel_block_length_proposal(
+				root.clone(),
+				1
+			));
+
+			let event =
+				RuntimeEvent::DataAvailability(Event::BlockLengthProposalCancelled { id: 1 });
+			System::assert_last_event(event);
+			assert!(DataAvailability::scheduled_block_lengths().is_empty());
+
+			let err = DataAvailability::cancel_block_length_proposal(root, 1);
+			assert_noop!(err, Error::UnknownBlockLengthProposal);
+
+			run_to_block(5);
+			assert_eq!(dimensions(), initial);
+		})
+	}
+
+	#[test]
+	fn replace_block_length_proposal() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			let rows = BlockLengthRows(256);
+			let cols = BlockLengthColumns(32);
+
+			assert_ok!(DataAvailability::schedule_block_length_proposal(
+				root.clone(),
+				64,
+				64,
+				5
+			));
+			assert_ok!(DataAvailability::schedule_block_length_proposal(
+				root.clone(),
+				64,
+				64,
+				8
+			));
+			assert_ok!(DataAvailability::replace_block_length_proposal(
+				root.clone(),
+				1,
+				rows.0,
+				cols.0,
+				10
+			));
+
+			let event = RuntimeEvent::DataAvailability(Event::BlockLengthProposalReplaced {
+				id: 1,
+				rows,
+				cols,
+				activation: 10,
+			});
+			System::assert_last_event(event);
+			let expected = ScheduledBlockLength {
+				id: 1,
+				rows,
+				cols,
+				activation: 10,
+			};
+			let scheduled = DataAvailability::scheduled_block_lengths();
+			assert_eq!(scheduled.last(), Some(&expected));
+
+			let err = DataAvailability::replace_block_length_proposal(root.clone(), 3, 64, 64, 10);
+			assert_noop!(err, Error::UnknownBlockLengthProposal);
+
+			let err = DataAvailability::replace_block_length_proposal(root, 1, 64, 64, 1);
+			assert_noop!(err, Error::ActivationNotInFuture);
+
+			run_to_block(10);
+			assert_eq!(dimensions(), (rows, cols));
+		})
+	}
+
+	#[test]
+	fn scheduling_requires_root() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+
+			let err = DataAvailability::schedule_block_length_proposal(alice.clone(), 64, 64, 5);
+			assert_noop!(err, BadOrigin);
+
+			let err = DataAvailability::cancel_block_length_proposal(alice.clone(), 1);
+			assert_noop!(err, BadOrigin);
+
+			let err = DataAvailability::replace_block_length_proposal(alice, 1, 64, 64, 5);
+			assert_noop!(err, BadOrigin);
+		})
+	}
+}
+
+mod migration {
+	use super::*;
+	use frame_support::storage::unhashed;
+
+	#[test]
+	fn v1_adds_zero_deposit() {
+		new_test_ext().execute_with(|| {
+			// Rewrites the genesis keys, and a new one, with the old layout.
+			let old_key = key(b"Old App");
+			let mut old_keys = AppKeys::<Test>::iter()
+				.map(|(key, info)| (key, info.owner, info.id))
+				.collect::<Vec<_>>();
+			old_keys.push((old_key.clone(), BOB, AppId(7)));
+			for (key, owner, id) in old_keys {
+				let old_info = migrations::v1::OldAppKeyInfo { owner, id };
+				unhashed::put(&AppKeys::<Test>::hashed_key_for(&key), &old_info);
+			}
+			StorageVersion::new(0).put::<DataAvailability>();
+
+			migrations::migrate::<Test>();
+
+			assert_eq!(
+				DataAvailability::on_chain_storage_version(),
+				DataAvailability::current_storage_version()
+			);
+			assert_eq!(
+				DataAvailability::application_key(&old_key),
+				Some(AppKeyInfoFor::<Test> {
+					owner: BOB,
+					id: AppId(7),
+					deposit: 0,
+				})
+			);
+			assert_eq!(AppKeys::<Test>::iter().count(), 4);
+			assert!(AppKeys::<Test>::iter_values().all(|info| info.deposit == 0));
+		})
+	}
+}
@@ -55,23 +55,39 @@ pub trait WeightInfo {
 	fn submit_data(i: u32, ) -> Weight;
 	fn data_root(i: u32, ) -> Weight;
 	fn data_root_batch(i: u32, ) -> Weight;
+	fn transfer_application_key() -> Weight;
+	fn set_application_metadata() -> Weight;
+	fn release_application_key() -> Weight;
+	fn set_submitter_policy(s: u32, ) -> Weight;
+	fn set_block_space_cap() -> Weight;
+	fn set_reserved_block_space() -> Weight;
+	fn submit_data_chunk(i: u32, ) -> Weight;
+	fn finalize_blob(c: u32, ) -> Weight;
+	fn set_dimension_adjustment() -> Weight;
+	fn schedule_block_length_proposal() -> Weight;
+	fn cancel_block_length_proposal() -> Weight;
+	fn replace_block_length_proposal() -> Weight;
+	fn cancel_blob() -> Weight;
+	fn remove_blob() -> Weight;
 }
 
 /// Weights for `da_control` using the Avail node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Storage: `DataAvailability::AppKeys` (r:1 w:1)
-	/// Proof: `DataAvailability::AppKeys` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Proof: `DataAvailability::AppKeys` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	/// Storage: `DataAvailability::NextAppId` (r:1 w:1)
 	/// Proof: `DataAvailability::NextAppId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
 	fn create_application_key() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `117`
-		//  Estimated: `3583`
-		// Minimum execution time: 26_999_000 picoseconds.
-		Weight::from_parts(28_094_000, 3583)
-			.saturating_add(T::DbWeight::get().reads(2_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
+		//  Measured:  `220`
+		//  Estimated: `3599`
+		// Minimum execution time: 44_310_000 picoseconds.
+		Weight::from_parts(45_872_000, 3599)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
 	/// Storage: `System::DynamicBlockLength` (r:1 w:1)
 	/// Proof: `System::DynamicBlockLength` (`max_values`: Some(1), `max_size`: Some(24), added: 519, mode: `MaxEncodedLen`)
@@ -114,22 +130,219 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			// Standard Error: 41
 			.saturating_add(Weight::from_parts(5_154, 0).saturating_mul(i.into()))
 	}
+	/// Storage: `DataAvailability::AppKeys` (r:1 w:1)
+	/// Proof: `DataAvailability::AppKeys` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::AppMetadataOf` (r:1 w:0)
+	/// Proof: `DataAvailability::AppMetadataOf` (`max_values`: None, `max_size`: Some(546), added: 3021, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn transfer_application_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `541`
+		//  Estimated: `6196`
+		// Minimum execution time: 62_431_000 picoseconds.
+		Weight::from_parts(64_015_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `DataAvailability::AppKeys` (r:1 w:0)
+	/// Proof: `DataAvailability::AppKeys` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::AppMetadataOf` (r:1 w:1)
+	/// Proof: `DataAvailability::AppMetadataOf` (`max_values`: None, `max_size`: Some(546), added: 3021, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn set_application_metadata() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `296`
+		//  Estimated: `4011`
+		// Minimum execution time: 41_870_000 picoseconds.
+		Weight::from_parts(43_262_000, 4011)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `DataAvailability::AppKeys` (r:1 w:1)
+	/// Proof: `DataAvailability::AppKeys` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::AppMetadataOf` (r:1 w:1)
+	/// Proof: `DataAvailability::AppMetadataOf` (`max_values`: None, `max_size`: Some(546), added: 3021, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::ReservedLanes` (r:1 w:1)
+	/// Proof: `DataAvailability::ReservedLanes` (`max_values`: Some(1), `max_size`: Some(129), added: 624, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::BlockSpaceCaps` (r:0 w:1)
+	/// Proof: `DataAvailability::BlockSpaceCaps` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::RetiredAppIds` (r:0 w:1)
+	/// Proof: `DataAvailability::RetiredAppIds` (`max_values`: None, `max_size`: Some(12), added: 2487, mode: `MaxEncodedLen`)
+	fn release_application_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `512`
+		//  Estimated: `4011`
+		// Minimum execution time: 45_118_000 picoseconds.
+		Weight::from_parts(46_904_000, 4011)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: `DataAvailability::AppKeys` (r:1 w:0)
+	/// Proof: `DataAvailability::AppKeys` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::SubmitterPolicies` (r:0 w:1)
+	/// Proof: `DataAvailability::SubmitterPolicies` (`max_values`: None, `max_size`: Some(2134), added: 4609, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 64]`.
+	fn set_submitter_policy(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `220`
+		//  Estimated: `3599`
+		// Minimum execution time: 19_654_000 picoseconds.
+		Weight::from_parts(20_713_482, 3599)
+			// Standard Error: 1_702
+			.saturating_add(Weight::from_parts(61_319, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `DataAvailability::BlockSpaceCaps` (r:0 w:1)
+	/// Proof: `DataAvailability::BlockSpaceCaps` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+	fn set_block_space_cap() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 11_322_000 picoseconds.
+		Weight::from_parts(11_871_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `DataAvailability::ReservedLanes` (r:1 w:1)
+	/// Proof: `DataAvailability::ReservedLanes` (`max_values`: Some(1), `max_size`: Some(129), added: 624, mode: `MaxEncodedLen`)
+	fn set_reserved_block_space() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `199`
+		//  Estimated: `1614`
+		// Minimum execution time: 15_907_000 picoseconds.
+		Weight::from_parts(16_538_000, 1614)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `System::ExtrinsicIndex` (r:1 w:0)
+	/// Proof: `System::ExtrinsicIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `DataAvailability::PendingBlobs` (r:1 w:1)
+	/// Proof: `DataAvailability::PendingBlobs` (`max_values`: None, `max_size`: Some(2882), added: 5357, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `i` is `[1, 524288]`.
+	fn submit_data_chunk(i: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2906`
+		//  Estimated: `6331`
+		// Minimum execution time: 27_481_000 picoseconds.
+		Weight::from_parts(22_905_114, 6331)
+			// Standard Error: 2
+			.saturating_add(Weight::from_parts(1_671, 0).saturating_mul(i.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `DataAvailability::BlobManifests` (r:1 w:1)
+	/// Proof: `DataAvailability::BlobManifests` (`max_values`: None, `max_size`: Some(2954), added: 5429, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::PendingBlobs` (r:1 w:1)
+	/// Proof: `DataAvailability::PendingBlobs` (`max_values`: None, `max_size`: Some(2882), added: 5357, mode: `MaxEncodedLen`)
+	/// The range of component `c` is `[1, 64]`.
+	fn finalize_blob(c: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `106 + c * (44 ±0)`
+		//  Estimated: `6403`
+		// Minimum execution time: 22_118_000 picoseconds.
+		Weight::from_parts(22_640_592, 6403)
+			// Standard Error: 1_194
+			.saturating_add(Weight::from_parts(184_507, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `DataAvailability::GridUtilization` (r:0 w:1)
+	/// Proof: `DataAvailability::GridUtilization` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::DimensionAdjustmentParams` (r:0 w:1)
+	/// Proof: `DataAvailability::DimensionAdjustmentParams` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn set_dimension_adjustment() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_012_000 picoseconds.
+		Weight::from_parts(9_437_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `DataAvailability::LastBlockLenProposalId` (r:1 w:1)
+	/// Proof: `DataAvailability::LastBlockLenProposalId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::ScheduledBlockLengths` (r:1 w:1)
+	/// Proof: `DataAvailability::ScheduledBlockLengths` (`max_values`: Some(1), `max_size`: Some(321), added: 816, mode: `MaxEncodedLen`)
+	fn schedule_block_length_proposal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212`
+		//  Estimated: `1806`
+		// Minimum execution time: 14_108_000 picoseconds.
+		Weight::from_parts(14_108_000, 1806)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `DataAvailability::ScheduledBlockLengths` (r:1 w:1)
+	/// Proof: `DataAvailability::ScheduledBlockLengths` (`max_values`: Some(1), `max_size`: Some(321), added: 816, mode: `MaxEncodedLen`)
+	fn cancel_block_length_proposal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212`
+		//  Estimated: `1806`
+		// Minimum execution time: 11_573_000 picoseconds.
+		Weight::from_parts(11_573_000, 1806)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `DataAvailability::ScheduledBlockLengths` (r:1 w:1)
+	/// Proof: `DataAvailability::ScheduledBlockLengths` (`max_values`: Some(1), `max_size`: Some(321), added: 816, mode: `MaxEncodedLen`)
+	fn replace_block_length_proposal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212`
+		//  Estimated: `1806`
+		// Minimum execution time: 13_246_000 picoseconds.
+		Weight::from_parts(13_246_000, 1806)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `DataAvailability::PendingBlobs` (r:1 w:1)
+	/// Proof: `DataAvailability::PendingBlobs` (`max_values`: None, `max_size`: Some(2882), added: 5357, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn cancel_blob() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2922`
+		//  Estimated: `6347`
+		// Minimum execution time: 30_284_000 picoseconds.
+		Weight::from_parts(31_517_000, 6347)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `DataAvailability::BlobManifests` (r:1 w:1)
+	/// Proof: `DataAvailability::BlobManifests` (`max_values`: None, `max_size`: Some(2954), added: 5429, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn remove_blob() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2994`
+		//  Estimated: `6419`
+		// Minimum execution time: 31_102_000 picoseconds.
+		Weight::from_parts(32_388_000, 6419)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests.
 impl WeightInfo for () {
 	/// Storage: `DataAvailability::AppKeys` (r:1 w:1)
-	/// Proof: `DataAvailability::AppKeys` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Proof: `DataAvailability::AppKeys` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	/// Storage: `DataAvailability::NextAppId` (r:1 w:1)
 	/// Proof: `DataAvailability::NextAppId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
 	fn create_application_key() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `117`
-		//  Estimated: `3583`
-		// Minimum execution time: 26_999_000 picoseconds.
-		Weight::from_parts(28_094_000, 3583)
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
+		//  Measured:  `220`
+		//  Estimated: `3599`
+		// Minimum execution time: 44_310_000 picoseconds.
+		Weight::from_parts(45_872_000, 3599)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
 	/// Storage: `System::DynamicBlockLength` (r:1 w:1)
 	/// Proof: `System::DynamicBlockLength` (`max_values`: Some(1), `max_size`: Some(24), added: 519, mode: `MaxEncodedLen`)
@@ -172,4 +385,199 @@ impl WeightInfo for () {
 			// Standard Error: 41
 			.saturating_add(Weight::from_parts(5_154, 0).saturating_mul(i.into()))
 	}
-}
\ No newline at end of file
+	/// Storage: `DataAvailability::AppKeys` (r:1 w:1)
+	/// Proof: `DataAvailability::AppKeys` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::AppMetadataOf` (r:1 w:0)
+	/// Proof: `DataAvailability::AppMetadataOf` (`max_values`: None, `max_size`: Some(546), added: 3021, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn transfer_application_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `541`
+		//  Estimated: `6196`
+		// Minimum execution time: 62_431_000 picoseconds.
+		Weight::from_parts(64_015_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `DataAvailability::AppKeys` (r:1 w:0)
+	/// Proof: `DataAvailability::AppKeys` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::AppMetadataOf` (r:1 w:1)
+	/// Proof: `DataAvailability::AppMetadataOf` (`max_values`: None, `max_size`: Some(546), added: 3021, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn set_application_metadata() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `296`
+		//  Estimated: `4011`
+		// Minimum execution time: 41_870_000 picoseconds.
+		Weight::from_parts(43_262_000, 4011)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `DataAvailability::AppKeys` (r:1 w:1)
+	/// Proof: `DataAvailability::AppKeys` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::AppMetadataOf` (r:1 w:1)
+	/// Proof: `DataAvailability::AppMetadataOf` (`max_values`: None, `max_size`: Some(546), added: 3021, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::ReservedLanes` (r:1 w:1)
+	/// Proof: `DataAvailability::ReservedLanes` (`max_values`: Some(1), `max_size`: Some(129), added: 624, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::BlockSpaceCaps` (r:0 w:1)
+	/// Proof: `DataAvailability::BlockSpaceCaps` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::RetiredAppIds` (r:0 w:1)
+	/// Proof: `DataAvailability::RetiredAppIds` (`max_values`: None, `max_size`: Some(12), added: 2487, mode: `MaxEncodedLen`)
+	fn release_application_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `512`
+		//  Estimated: `4011`
+		// Minimum execution time: 45_118_000 picoseconds.
+		Weight::from_parts(46_904_000, 4011)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: `DataAvailability::AppKeys` (r:1 w:0)
+	/// Proof: `DataAvailability::AppKeys` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::SubmitterPolicies` (r:0 w:1)
+	/// Proof: `DataAvailability::SubmitterPolicies` (`max_values`: None, `max_size`: Some(2134), added: 4609, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 64]`.
+	fn set_submitter_policy(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `220`
+		//  Estimated: `3599`
+		// Minimum execution time: 19_654_000 picoseconds.
+		Weight::from_parts(20_713_482, 3599)
+			// Standard Error: 1_702
+			.saturating_add(Weight::from_parts(61_319, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `DataAvailability::BlockSpaceCaps` (r:0 w:1)
+	/// Proof: `DataAvailability::BlockSpaceCaps` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+	fn set_block_space_cap() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 11_322_000 picoseconds.
+		Weight::from_parts(11_871_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `DataAvailability::ReservedLanes` (r:1 w:1)
+	/// Proof: `DataAvailability::ReservedLanes` (`max_values`: Some(1), `max_size`: Some(129), added: 624, mode: `MaxEncodedLen`)
+	fn set_reserved_block_space() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `199`
+		//  Estimated: `1614`
+		// Minimum execution time: 15_907_000 picoseconds.
+		Weight::from_parts(16_538_000, 1614)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `System::ExtrinsicIndex` (r:1 w:0)
+	/// Proof: `System::ExtrinsicIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `DataAvailability::PendingBlobs` (r:1 w:1)
+	/// Proof: `DataAvailability::PendingBlobs` (`max_values`: None, `max_size`: Some(2882), added: 5357, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `i` is `[1, 524288]`.
+	fn submit_data_chunk(i: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2906`
+		//  Estimated: `6331`
+		// Minimum execution time: 27_481_000 picoseconds.
+		Weight::from_parts(22_905_114, 6331)
+			// Standard Error: 2
+			.saturating_add(Weight::from_parts(1_671, 0).saturating_mul(i.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `DataAvailability::BlobManifests` (r:1 w:1)
+	/// Proof: `DataAvailability::BlobManifests` (`max_values`: None, `max_size`: Some(2954), added: 5429, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::PendingBlobs` (r:1 w:1)
+	/// Proof: `DataAvailability::PendingBlobs` (`max_values`: None, `max_size`: Some(2882), added: 5357, mode: `MaxEncodedLen`)
+	/// The range of component `c` is `[1, 64]`.
+	fn finalize_blob(c: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `106 + c * (44 ±0)`
+		//  Estimated: `6403`
+		// Minimum execution time: 22_118_000 picoseconds.
+		Weight::from_parts(22_640_592, 6403)
+			// Standard Error: 1_194
+			.saturating_add(Weight::from_parts(184_507, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `DataAvailability::GridUtilization` (r:0 w:1)
+	/// Proof: `DataAvailability::GridUtilization` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::DimensionAdjustmentParams` (r:0 w:1)
+	/// Proof: `DataAvailability::DimensionAdjustmentParams` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn set_dimension_adjustment() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_012_000 picoseconds.
+		Weight::from_parts(9_437_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `DataAvailability::LastBlockLenProposalId` (r:1 w:1)
+	/// Proof: `DataAvailability::LastBlockLenProposalId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `DataAvailability::ScheduledBlockLengths` (r:1 w:1)
+	/// Proof: `DataAvailability::ScheduledBlockLengths` (`max_values`: Some(1), `max_size`: Some(321), added: 816, mode: `MaxEncodedLen`)
+	fn schedule_block_length_proposal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212`
+		//  Estimated: `1806`
+		// Minimum execution time: 14_108_000 picoseconds.
+		Weight::from_parts(14_108_000, 1806)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `DataAvailability::ScheduledBlockLengths` (r:1 w:1)
+	/// Proof: `DataAvailability::ScheduledBlockLengths` (`max_values`: Some(1), `max_size`: Some(321), added: 816, mode: `MaxEncodedLen`)
+	fn cancel_block_length_proposal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212`
+		//  Estimated: `1806`
+		// Minimum execution time: 11_573_000 picoseconds.
+		Weight::from_parts(11_573_000, 1806)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `DataAvailability::ScheduledBlockLengths` (r:1 w:1)
+	/// Proof: `DataAvailability::ScheduledBlockLengths` (`max_values`: Some(1), `max_size`: Some(321), added: 816, mode: `MaxEncodedLen`)
+	fn replace_block_length_proposal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212`
+		//  Estimated: `1806`
+		// Minimum execution time: 13_246_000 picoseconds.
+		Weight::from_parts(13_246_000, 1806)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `DataAvailability::PendingBlobs` (r:1 w:1)
+	/// Proof: `DataAvailability::PendingBlobs` (`max_values`: None, `max_size`: Some(2882), added: 5357, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn cancel_blob() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2922`
+		//  Estimated: `6347`
+		// Minimum execution time: 30_284_000 picoseconds.
+		Weight::from_parts(31_517_000, 6347)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `DataAvailability::BlobManifests` (r:1 w:1)
+	/// Proof: `DataAvailability::BlobManifests` (`max_values`: None, `max_size`: Some(2954), added: 5429, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn remove_blob() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2994`
+		//  Estimated: `6419`
+		// Minimum execution time: 31_102_000 picoseconds.
+		Weight::from_parts(32_388_000, 6419)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
@@ -109,6 +109,16 @@ parameter_types! {
 	// DA Control
 	pub const MaxAppKeyLength :u32 = 64;
 	pub const MaxAppDataLength :u32 = 512 * 1024; // 512 Kb
+	pub const MaxAppMetadataLength :u32 = 256;
+	pub const MaxAppSubmitters :u32 = 64;
+	pub const MaxReservedLanes :u32 = 16;
+	pub const MaxBlobChunks :u32 = 64;
+	pub const MaxScheduledBlockLengths :u32 = 16;
+	pub const AppKeyDeposit: Balance = 100;
+	pub const MetadataDepositBase: Balance = 10;
+	pub const MetadataDepositPerByte: Balance = 1;
+	pub const BlobDepositBase: Balance = 10;
+	pub const BlobDepositPerChunk: Balance = 1;
 	pub const MinBlockRows: BlockLengthRows = BlockLengthRows(32);
 	pub const MaxBlockRows: BlockLengthRows = BlockLengthRows(1024);
 	pub const MinBlockCols: BlockLengthColumns = BlockLengthColumns(32);
@@ -184,13 +194,24 @@ impl pallet_balances::Config for Runtime {
 }
 
 impl da_control::Config for Runtime {
+	type AppKeyDeposit = AppKeyDeposit;
 	type BlockLenProposalId = u32;
+	type Currency = Balances;
 	type MaxAppDataLength = MaxAppDataLength;
 	type MaxAppKeyLength = MaxAppKeyLength;
+	type MaxAppMetadataLength = MaxAppMetadataLength;
+	type MaxAppSubmitters = MaxAppSubmitters;
+	type MaxReservedLanes = MaxReservedLanes;
+	type MaxBlobChunks = MaxBlobChunks;
+	type MaxScheduledBlockLengths = MaxScheduledBlockLengths;
 	type MaxBlockCols = MaxBlockCols;
 	type MaxBlockRows = MaxBlockRows;
 	type MinBlockCols = MinBlockCols;
 	type MinBlockRows = MinBlockRows;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type BlobDepositBase = BlobDepositBase;
+	type BlobDepositPerChunk = BlobDepositPerChunk;
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = da_control::weights::SubstrateWeight<Runtime>;
 }
@@ -95,6 +95,21 @@ impl<SID: Get<u32>, STX: Get<u32>> ExtrinsicLen<SID, STX> {
 		Some(self.raw)
 	}
 
+	/// Number of padded scalars used by the extrinsics of `id`.
+	pub fn num_scalars_of(&self, id: AppId) -> u32 {
+		self.padded
+			.get(&id)
+			.map(PaddedExtrinsicLen::num_scalars)
+			.unwrap_or_default()
+	}
+
+	/// Number of padded scalars used by the extrinsics of each application.
+	pub fn num_scalars_per_app(&self) -> impl Iterator<Item = (AppId, u32)> + '_ {
+		self.padded
+			.iter()
+			.map(|(id, padded)| (*id, padded.num_scalars()))
+	}
+
 	pub fn total_num_scalars(&self) -> Option<u32> {
 		self.padded
 			.values()
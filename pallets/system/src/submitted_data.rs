@@ -382,6 +382,176 @@ where
 		.map(|proof| (proof, root, message_data))
 }
 
+/// Merkle proof of the submitted data or the bridge message of one transaction.
+#[derive(Debug, PartialEq)]
+pub struct CallProof {
+	/// Index of the transaction in the block.
+	pub transaction_index: u32,
+	/// Sub trie which contains the leaf of the transaction.
+	pub sub_trie: SubTrie,
+	/// Proof of the leaf against the root of `sub_trie`.
+	pub proof: MerkleProof<H256, Vec<u8>>,
+	/// Root of the other sub trie.
+	pub sibling_root: H256,
+	/// Last bridge message of the transaction, if any.
+	pub message: Option<Message>,
+}
+
+/// Creates the Merkle proofs of every submitted data and bridge message in `calls`.
+///
+/// It is equivalent to call `calls_proof_v2` for each transaction, but both sub tries are built
+/// only once. Proofs are sorted by transaction index, and transactions without data or messages
+/// are skipped.
+pub fn calls_proofs_v2<F, I, C>(
+	calls: I,
+	callers: Vec<AccountId32>,
+	bridge_nonce: u64,
+) -> Vec<CallProof>
+where
+	F: Filter<C>,
+	I: Iterator<Item = C>,
+{
+	let metrics = Metrics::new_shared();
+	let mut nonce = bridge_nonce;
+
+	let leaves = calls
+		.zip(callers)
+		.map(|(ext, caller)| {
+			let (l, r) = F::filter_v2(ext, Rc::clone(&metrics), caller);
+			let mut message = None;
+			let bridge_leaf = r
+				.into_iter()
+				.flat_map(|mut m| {
+					nonce += 1;
+					m.id = nonce;
+					let encoded = m.abi_encode();
+					message = Some(m);
+					encoded
+				})
+				.collect::<Vec<_>>();
+			let blob_leaf = l.into_iter().flatten().collect::<Vec<_>>();
+			(blob_leaf, bridge_leaf, message)
+		})
+		.collect::<Vec<_>>();
+
+	// Leaf index of each transaction in both sub tries.
+	let mut blob_data = Vec::new();
+	let mut bridge_data = Vec::new();
+	let mut positions = Vec::new();
+	for (tx_index, (blob_leaf, bridge_leaf, message)) in leaves.into_iter().enumerate() {
+		let Ok(tx_index) = u32::try_from(tx_index) else {
+			break;
+		};
+		if !blob_leaf.is_empty() {
+			positions.push((tx_index, SubTrie::Left, blob_data.len(), None));
+			blob_data.push(keccak_256(blob_leaf.as_slice()).to_vec());
+		}
+		if !bridge_leaf.is_empty() {
+			positions.push((tx_index, SubTrie::Right, bridge_data.len(), message));
+			bridge_data.push(bridge_leaf);
+		}
+	}
+
+	let (Some(blob_data), Some(bridge_data)) = (
+		calculate_balance_trie(blob_data),
+		calculate_balance_trie(bridge_data),
+	) else {
+		return Vec::new();
+	};
+	let blob_tree = MerkleTree::new(blob_data);
+	let bridge_tree = MerkleTree::new(bridge_data);
+	log::debug!(
+		target: LOG_TARGET,
+		"Build {} submitted data proofs, metrics: {:?}",
+		positions.len(),
+		metrics
+	);
+
+	positions
+		.into_iter()
+		.filter_map(|(transaction_index, sub_trie, leaf_index, message)| {
+			let (tree, sibling) = match sub_trie {
+				SubTrie::Left => (&blob_tree, &bridge_tree),
+				SubTrie::Right => (&bridge_tree, &blob_tree),
+			};
+			Some(CallProof {
+				transaction_index,
+				sub_trie,
+				proof: tree.proof(leaf_index)?,
+				sibling_root: sibling.root(),
+				message,
+			})
+		})
+		.collect()
+}
+
+/// Keccak binary Merkle tree kept in memory, so several proofs can be generated from it.
+///
+/// It follows the `binary_merkle_tree` construction: leaves are hashed, and the last node of a
+/// layer with an odd number of nodes is promoted to the next layer.
+struct MerkleTree {
+	leaves: Vec<Vec<u8>>,
+	layers: Vec<Vec<H256>>,
+}
+
+impl MerkleTree {
+	fn new(leaves: Vec<Vec<u8>>) -> Self {
+		let mut layer = leaves
+			.iter()
+			.map(|leaf| H256(keccak_256(leaf)))
+			.collect::<Vec<_>>();
+		let mut layers = Vec::new();
+		while layer.len() > 1 {
+			let next = layer
+				.chunks(2)
+				.map(|pair| match pair {
+					[left, right] => {
+						let mut combined = [0u8; 64];
+						combined[..32].copy_from_slice(left.as_bytes());
+						combined[32..].copy_from_slice(right.as_bytes());
+						H256(keccak_256(&combined))
+					},
+					_ => pair[0],
+				})
+				.collect();
+			layers.push(layer);
+			layer = next;
+		}
+		layers.push(layer);
+
+		Self { leaves, layers }
+	}
+
+	/// Root of the tree, or a 0-filled hash if it is empty.
+	fn root(&self) -> H256 {
+		self.layers
+			.last()
+			.and_then(|layer| layer.first())
+			.copied()
+			.unwrap_or_default()
+	}
+
+	fn proof(&self, leaf_index: usize) -> Option<MerkleProof<H256, Vec<u8>>> {
+		let leaf = self.leaves.get(leaf_index)?.clone();
+		let mut index = leaf_index;
+		let mut proof = Vec::new();
+		for layer in &self.layers[..self.layers.len() - 1] {
+			if let Some(sibling) = layer.get(index ^ 1) {
+				proof.push(*sibling);
+			}
+			index /= 2;
+		}
+
+		Some(MerkleProof {
+			root: self.root(),
+			proof,
+			number_of_leaves: self.leaves.len(),
+			leaf_index,
+			leaf,
+		})
+	}
+}
+
 /// Construct a Merkle Proof for `submit_data` given by `data_index` and stores
 /// information about the process into `metrics`.
 ///
@@ -461,7 +631,8 @@ mod test {
 	use std::vec;
 
 	use crate::submitted_data::{
-		calculate_balance_trie, calls_proof_v2, Filter, Message, MessageType, RcMetrics,
+		calculate_balance_trie, calls_proof_v2, calls_proofs_v2, Filter, Message, MessageType,
+		RcMetrics,
 	};
 
 	// dummy filter implementation that skips empty strings in vector
@@ -714,6 +885,51 @@ mod test {
 		);
 	}
 
+	#[test]
+	fn test_all_data_proofs_match_single_proofs() {
+		let submitted_data: Vec<String> = ["0", "", "1", "2", "", "3", "4"]
+			.into_iter()
+			.map(String::from)
+			.collect();
+		let callers = vec![AccountId32::new([0u8; 32]); submitted_data.len()];
+		let bridge_nonce: u64 = 0u64;
+
+		let proofs = calls_proofs_v2::<String, _, _>(
+			submitted_data.clone().into_iter(),
+			callers.clone(),
+			bridge_nonce,
+		);
+		assert_eq!(
+			proofs
+				.iter()
+				.map(|p| p.transaction_index)
+				.collect::<Vec<_>>(),
+			vec![0, 2, 3, 5, 6]
+		);
+
+		for call_proof in proofs {
+			assert_eq!(call_proof.sub_trie, SubTrie::Left);
+			let (proof, root, message) = calls_proof_v2::<String, _, _>(
+				submitted_data.clone().into_iter(),
+				callers.clone(),
+				call_proof.transaction_index,
+				bridge_nonce,
+				SubTrie::Left,
+			)
+			.unwrap();
+			assert_eq!(call_proof.proof, proof);
+			assert_eq!(call_proof.sibling_root, root);
+			assert_eq!(call_proof.message, message);
+		}
+
+		// No proofs for blocks without data.
+		let empty = vec![String::new(); 3];
+		assert!(
+			calls_proofs_v2::<String, _, _>(empty.into_iter(), callers.clone(), bridge_nonce)
+				.is_empty()
+		);
+	}
+
 	#[test]
 	fn test_pow_2_elements() {
 		let empty: Vec<Vec<u8>> = vec![];
@@ -22,20 +22,24 @@
 use codec::Codec;
 use sp_runtime::traits::MaybeDisplay;
 
-pub use pallet_transaction_payment::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
+pub use pallet_transaction_payment::{
+	FeeDetails, InclusionFee, LegacyFeeDetails, LegacyInclusionFee, RuntimeDispatchInfo,
+};
 
 sp_api::decl_runtime_apis! {
-	#[api_version(4)]
+	#[api_version(5)]
 	pub trait TransactionPaymentApi<Balance> where
 		Balance: Codec + MaybeDisplay,
 	{
 		fn query_info(uxt: Block::Extrinsic, len: u32) -> RuntimeDispatchInfo<Balance>;
+		#[changed_in(5)]
+		fn query_fee_details(uxt: Block::Extrinsic, len: u32) -> LegacyFeeDetails<Balance>;
 		fn query_fee_details(uxt: Block::Extrinsic, len: u32) -> FeeDetails<Balance>;
 		fn query_weight_to_fee(weight: sp_weights::Weight) -> Balance;
 		fn query_length_to_fee(length: u32) -> Balance;
 	}
 
-	#[api_version(3)]
+	#[api_version(4)]
 	pub trait TransactionPaymentCallApi<Balance, Call>
 	where
 		Balance: Codec + MaybeDisplay,
@@ -44,6 +48,10 @@ sp_api::decl_runtime_apis! {
 		/// Query information of a dispatch class, weight, and fee of a given encoded `Call`.
 		fn query_call_info(call: Call, len: u32) -> RuntimeDispatchInfo<Balance>;
 
+		/// Query fee details of a given encoded `Call`, without the blob fee.
+		#[changed_in(4)]
+		fn query_call_fee_details(call: Call, len: u32) -> LegacyFeeDetails<Balance>;
+
 		/// Query fee details of a given encoded `Call`.
 		fn query_call_fee_details(call: Call, len: u32) -> FeeDetails<Balance>;
 
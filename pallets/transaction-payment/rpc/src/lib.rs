@@ -26,11 +26,11 @@ use jsonrpsee::{
 	types::error::{CallError, ErrorCode, ErrorObject},
 };
 use pallet_transaction_payment_rpc_runtime_api::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::Bytes;
 use sp_rpc::number::NumberOrHex;
-use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
+use sp_runtime::traits::{Block as BlockT, MaybeDisplay, Zero};
 
 pub use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi as TransactionPaymentRuntimeApi;
 
@@ -87,7 +87,7 @@ where
 	Block: BlockT,
 	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
 	C::Api: TransactionPaymentRuntimeApi<Block, Balance>,
-	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex> + Send + Sync + 'static,
+	Balance: Codec + MaybeDisplay + Copy + Zero + TryInto<NumberOrHex> + Send + Sync + 'static,
 {
 	fn query_info(
 		&self,
@@ -143,13 +143,30 @@ where
 				Some(format!("{:?}", e)),
 			))
 		})?;
-		let fee_details = api.query_fee_details(at_hash, uxt, encoded_len).map_err(|e| {
+
+		fn map_err(error: impl ToString) -> CallError {
 			CallError::Custom(ErrorObject::owned(
 				Error::RuntimeError.into(),
 				"Unable to query fee details.",
-				Some(e.to_string()),
+				Some(error.to_string()),
 			))
-		})?;
+		}
+
+		let api_version = api
+			.api_version::<dyn TransactionPaymentRuntimeApi<Block, Balance>>(at_hash)
+			.map_err(map_err)?
+			.ok_or_else(|| map_err("Transaction payment runtime api wasn't found"))?;
+
+		// Runtimes before version 5 of the api have no blob fee.
+		let fee_details: FeeDetails<Balance> = if api_version < 5 {
+			#[allow(deprecated)]
+			api.query_fee_details_before_version_5(at_hash, uxt, encoded_len)
+				.map_err(map_err)?
+				.into()
+		} else {
+			api.query_fee_details(at_hash, uxt, encoded_len)
+				.map_err(map_err)?
+		};
 
 		let try_into_rpc_balance = |value: Balance| {
 			value.try_into().map_err(|_| {
@@ -167,6 +184,7 @@ where
 					base_fee: try_into_rpc_balance(inclusion_fee.base_fee)?,
 					len_fee: try_into_rpc_balance(inclusion_fee.len_fee)?,
 					adjusted_weight_fee: try_into_rpc_balance(inclusion_fee.adjusted_weight_fee)?,
+					blob_fee: try_into_rpc_balance(inclusion_fee.blob_fee)?,
 				})
 			} else {
 				None
@@ -23,20 +23,25 @@
 //! 	as a base _weight_ in the runtime and converted to a fee using `WeightToFee`.
 //!   - _weight fee_: A fee proportional to amount of weight a transaction consumes.
 //!   - _length fee_: A fee proportional to the encoded length of the transaction.
+//!   - _blob fee_: A fee proportional to the length of the data submitted by the transaction,
+//!     adjusted to the utilization of the padded grid instead of the block weight.
 //!   - _tip_: An optional tip. Tip increases the priority of the transaction, giving it a higher
 //!     chance to be included by the transaction queue.
 //!
-//! The base fee and adjusted weight and length fees constitute the _inclusion fee_, which is
-//! the minimum fee for a transaction to be included in a block.
+//! The base fee and adjusted weight, length and blob fees constitute the _inclusion fee_, which
+//! is the minimum fee for a transaction to be included in a block.
 //!
 //! The formula of final fee:
 //!   ```ignore
-//!   inclusion_fee = base_fee + length_fee + [targeted_fee_adjustment * weight_fee];
+//!   inclusion_fee = base_fee + length_fee + [targeted_fee_adjustment * weight_fee]
+//!       + [blob_fee_adjustment * blob_fee];
 //!   final_fee = inclusion_fee + tip;
 //!   ```
 //!
 //!   - `targeted_fee_adjustment`: This is a multiplier that can tune the final fee based on
 //! 	the congestion of the network.
+//!   - `blob_fee_adjustment`: This is a multiplier that tunes the blob fee based on the demand
+//! 	of block space for data.
 //!
 //! Additionally, this pallet allows one to configure:
 //!   - The mapping between one unit of weight to one unit of fee via [`Config::WeightToFee`].
@@ -44,6 +49,8 @@
 //!     final state of the chain at the end of the previous block. This can be configured via
 //!     [`Config::FeeMultiplierUpdate`]
 //!   - How the fees are paid via [`Config::OnChargeTransaction`].
+//!   - The length of the data submitted by a call, which is charged the blob fee, via
+//!     [`Config::BlobLength`].
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::tabs_in_doc_comments)]
@@ -55,10 +62,10 @@ use frame_support::{
 	dispatch::{
 		DispatchClass, DispatchInfo, DispatchResult, GetDispatchInfo, Pays, PostDispatchInfo,
 	},
-	traits::{Defensive, EstimateCallFee, Get},
+	traits::{Defensive, EstimateCallFee, ExtrinsicCall, Get},
 	weights::{Weight, WeightToFee},
 };
-use frame_system::pallet::DynamicBlockLength;
+use frame_system::{pallet::DynamicBlockLength, AllExtrinsicsLen};
 pub use pallet::*;
 pub use payment::*;
 use sp_runtime::{
@@ -72,7 +79,9 @@ use sp_runtime::{
 	FixedPointNumber, FixedU128, Perbill, Perquintill, RuntimeDebug,
 };
 use sp_std::prelude::*;
-pub use types::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
+pub use types::{
+	FeeDetails, InclusionFee, LegacyFeeDetails, LegacyInclusionFee, RuntimeDispatchInfo,
+};
 
 pub const LOG_TARGET: &str = "runtime::tx_payment";
 #[cfg(test)]
@@ -144,6 +153,26 @@ pub struct TargetedFeeAdjustment<T, S, V, M, X>(sp_std::marker::PhantomData<(T,
 
 pub struct LengthFeeAdjustment<T, S, V, M, X>(sp_std::marker::PhantomData<(T, S, V, M, X)>);
 
+/// A struct to update the blob multiplier per block, like [`TargetedFeeAdjustment`] does for the
+/// weight multiplier.
+///
+/// The block saturation is the utilization of the padded grid, i.e. the padded scalars used by
+/// the data of the block over the scalars of the grid, so the price of block space for data does
+/// not follow the congestion of the other resources.
+pub struct BlobFeeAdjustment<T, S, V, M, X>(sp_std::marker::PhantomData<(T, S, V, M, X)>);
+
+/// Something that gives the length of the data submitted by a call, which is charged the blob fee.
+pub trait GetBlobLength<Call> {
+	/// Length in bytes of the data submitted by `call`.
+	fn blob_len(call: &Call) -> u32;
+}
+
+impl<Call> GetBlobLength<Call> for () {
+	fn blob_len(_call: &Call) -> u32 {
+		0
+	}
+}
+
 /// Something that can convert the current multiplier to the next one.
 pub trait MultiplierUpdate: Convert<Multiplier, Multiplier> {
 	/// Minimum multiplier. Any outcome of the `convert` function should be at least this.
@@ -242,43 +271,17 @@ where
 				(normal_block_weight.ref_time(), normal_max_weight.ref_time())
 			};
 
-		let target_block_fullness = S::get();
-		let adjustment_variable = V::get();
-
-		let target_weight = (target_block_fullness * max_limiting_dimension) as u128;
+		let target_weight = (S::get() * max_limiting_dimension) as u128;
 		let block_weight = normal_limiting_dimension as u128;
 
-		// determines if the first_term is positive
-		let positive = block_weight >= target_weight;
-		let diff_abs = block_weight.max(target_weight) - block_weight.min(target_weight);
-
-		// defensive only, a test case assures that the maximum weight diff can fit in Multiplier
-		// without any saturation.
-		let diff = Multiplier::saturating_from_rational(diff_abs, max_limiting_dimension.max(1));
-		let diff_squared = diff.saturating_mul(diff);
-
-		let v_squared_2 = adjustment_variable.saturating_mul(adjustment_variable)
-			/ Multiplier::saturating_from_integer(2);
-
-		let first_term = adjustment_variable.saturating_mul(diff);
-		let second_term = v_squared_2.saturating_mul(diff_squared);
-
-		if positive {
-			let excess = first_term
-				.saturating_add(second_term)
-				.saturating_mul(previous);
-			previous
-				.saturating_add(excess)
-				.clamp(min_multiplier, max_multiplier)
-		} else {
-			// Defensive-only: first_term > second_term. Safe subtraction.
-			let negative = first_term
-				.saturating_sub(second_term)
-				.saturating_mul(previous);
-			previous
-				.saturating_sub(negative)
-				.clamp(min_multiplier, max_multiplier)
-		}
+		adjust_multiplier(
+			previous,
+			block_weight,
+			target_weight,
+			max_limiting_dimension.into(),
+			V::get(),
+		)
+		.clamp(min_multiplier, max_multiplier)
 	}
 }
 
@@ -328,47 +331,126 @@ where
 		.into();
 		let current_block_length = <frame_system::Pallet<T>>::all_padded_extrinsics_len();
 
-		let target_block_fullness = S::get();
-		let adjustment_variable = V::get();
-
-		let target_length = (target_block_fullness * max_padded_length) as u128;
+		let target_length = (S::get() * max_padded_length) as u128;
 		let block_length = current_block_length as u128;
 
 		log::debug!(target: LOG_TARGET,
 			"target_length: {}, block_length: {}  ",
 			target_length,
 			block_length);
-		// determines if the first_term is positive
-		let positive = block_length >= target_length;
-		let diff_abs = block_length.max(target_length) - block_length.min(target_length);
-
-		// defensive only, a test case assures that the maximum weight diff can fit in Multiplier
-		// without any saturation.
-		let diff = Multiplier::saturating_from_rational(diff_abs, max_padded_length.max(1));
-		let diff_squared = diff.saturating_mul(diff);
-
-		let v_squared_2 = adjustment_variable.saturating_mul(adjustment_variable)
-			/ Multiplier::saturating_from_integer(2);
-
-		let first_term = adjustment_variable.saturating_mul(diff);
-		let second_term = v_squared_2.saturating_mul(diff_squared);
-
-		if positive {
-			let excess = first_term
-				.saturating_add(second_term)
-				.saturating_mul(previous);
-			previous
-				.saturating_add(excess)
-				.clamp(min_multiplier, max_multiplier)
-		} else {
-			// Defensive-only: first_term > second_term. Safe subtraction.
-			let negative = first_term
-				.saturating_sub(second_term)
-				.saturating_mul(previous);
-			previous
-				.saturating_sub(negative)
-				.clamp(min_multiplier, max_multiplier)
-		}
+
+		adjust_multiplier(
+			previous,
+			block_length,
+			target_length,
+			max_padded_length.into(),
+			V::get(),
+		)
+		.clamp(min_multiplier, max_multiplier)
+	}
+}
+
+impl<T, S, V, M, X> MultiplierUpdate for BlobFeeAdjustment<T, S, V, M, X>
+where
+	T: frame_system::Config,
+	S: Get<Perquintill>,
+	V: Get<Multiplier>,
+	M: Get<Multiplier>,
+	X: Get<Multiplier>,
+{
+	fn min() -> Multiplier {
+		M::get()
+	}
+	fn max() -> Multiplier {
+		X::get()
+	}
+	fn target() -> Perquintill {
+		S::get()
+	}
+	fn variability() -> Multiplier {
+		V::get()
+	}
+}
+
+impl<T, S, V, M, X> Convert<Multiplier, Multiplier> for BlobFeeAdjustment<T, S, V, M, X>
+where
+	T: frame_system::Config,
+	S: Get<Perquintill>,
+	V: Get<Multiplier>,
+	M: Get<Multiplier>,
+	X: Get<Multiplier>,
+{
+	fn convert(previous: Multiplier) -> Multiplier {
+		// Defensive only. The multiplier in storage should always be at most positive. Nonetheless
+		// we recover here in case of errors, because any value below this would be stale and can
+		// never change.
+		let min_multiplier = M::get();
+		let max_multiplier = X::get();
+		let previous = previous.max(min_multiplier);
+
+		let block_length = DynamicBlockLength::<T>::get();
+		let max_scalars = u64::from(block_length.rows.0).saturating_mul(block_length.cols.0.into());
+		let used_scalars = AllExtrinsicsLen::<T>::get()
+			.and_then(|len| len.total_num_scalars())
+			.unwrap_or_default();
+
+		let target_scalars = (S::get() * max_scalars) as u128;
+		let block_scalars = u128::from(used_scalars).min(max_scalars.into());
+
+		log::debug!(target: LOG_TARGET,
+			"target_scalars: {}, block_scalars: {}  ",
+			target_scalars,
+			block_scalars);
+
+		adjust_multiplier(
+			previous,
+			block_scalars,
+			target_scalars,
+			max_scalars.into(),
+			V::get(),
+		)
+		.clamp(min_multiplier, max_multiplier)
+	}
+}
+
+/// The multiplier update shared by [`TargetedFeeAdjustment`], [`LengthFeeAdjustment`] and
+/// [`BlobFeeAdjustment`]: `previous * (1 + v * diff + v^2 * diff^2 / 2)`, where `diff` is the
+/// distance of `used` from `target`, normalized by `max` and negative below the target.
+///
+/// The result is not clamped, that is left to the caller.
+fn adjust_multiplier(
+	previous: Multiplier,
+	used: u128,
+	target: u128,
+	max: u128,
+	adjustment_variable: Multiplier,
+) -> Multiplier {
+	// determines if the first_term is positive
+	let positive = used >= target;
+	let diff_abs = used.max(target) - used.min(target);
+
+	// defensive only, a test case assures that the maximum diff can fit in Multiplier without any
+	// saturation.
+	let diff = Multiplier::saturating_from_rational(diff_abs, max.max(1));
+	let diff_squared = diff.saturating_mul(diff);
+
+	let v_squared_2 = adjustment_variable.saturating_mul(adjustment_variable)
+		/ Multiplier::saturating_from_integer(2);
+
+	let first_term = adjustment_variable.saturating_mul(diff);
+	let second_term = v_squared_2.saturating_mul(diff_squared);
+
+	if positive {
+		let excess = first_term
+			.saturating_add(second_term)
+			.saturating_mul(previous);
+		previous.saturating_add(excess)
+	} else {
+		// Defensive-only: first_term > second_term. Safe subtraction.
+		let negative = first_term
+			.saturating_sub(second_term)
+			.saturating_mul(previous);
+		previous.saturating_sub(negative)
 	}
 }
 
@@ -442,6 +524,8 @@ pub mod pallet {
 			type FeeMultiplierUpdate = ();
 			type OperationalFeeMultiplier = ();
 			type LengthMultiplierUpdate = ();
+			type BlobMultiplierUpdate = ();
+			type BlobLength = ();
 		}
 	}
 
@@ -497,6 +581,18 @@ pub mod pallet {
 
 		/// Update the multiplier of the next block, based on the previous block's length.
 		type LengthMultiplierUpdate: MultiplierUpdate;
+
+		/// Convert the length of the data submitted by a call into a deductible blob fee.
+		#[pallet::no_default]
+		type BlobLengthToFee: WeightToFee<Balance = BalanceOf<Self>>;
+
+		/// Update the blob multiplier of the next block, based on the utilization of the padded
+		/// grid by the previous block.
+		type BlobMultiplierUpdate: MultiplierUpdate;
+
+		/// The length of the data submitted by a call, which is charged the blob fee.
+		#[pallet::no_default_bounds]
+		type BlobLength: GetBlobLength<<Self as frame_system::Config>::RuntimeCall>;
 	}
 
 	#[pallet::type_value]
@@ -514,6 +610,11 @@ pub mod pallet {
 	pub type NextLengthMultiplier<T: Config> =
 		StorageValue<_, Multiplier, ValueQuery, NextFeeMultiplierOnEmpty>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn next_blob_multiplier)]
+	pub type NextBlobMultiplier<T: Config> =
+		StorageValue<_, Multiplier, ValueQuery, NextFeeMultiplierOnEmpty>;
+
 	#[pallet::storage]
 	pub(super) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
 
@@ -539,6 +640,7 @@ pub mod pallet {
 			StorageVersion::<T>::put(Releases::V2);
 			NextFeeMultiplier::<T>::put(self.multiplier);
 			NextLengthMultiplier::<T>::put(self.multiplier);
+			NextBlobMultiplier::<T>::put(self.multiplier);
 		}
 	}
 
@@ -563,6 +665,9 @@ pub mod pallet {
 			<NextLengthMultiplier<T>>::mutate(|lm| {
 				*lm = T::LengthMultiplierUpdate::convert(*lm);
 			});
+			<NextBlobMultiplier<T>>::mutate(|bm| {
+				*bm = T::BlobMultiplierUpdate::convert(*bm);
+			});
 		}
 
 		#[cfg(feature = "std")]
@@ -627,11 +732,12 @@ impl<T: Config> Pallet<T> {
 	///
 	/// All dispatchables must be annotated with weight and will have some fee info. This function
 	/// always returns.
-	pub fn query_info<Extrinsic: sp_runtime::traits::Extrinsic + GetDispatchInfo>(
+	pub fn query_info<Extrinsic>(
 		unchecked_extrinsic: Extrinsic,
 		len: u32,
 	) -> RuntimeDispatchInfo<BalanceOf<T>>
 	where
+		Extrinsic: ExtrinsicCall<Call = T::RuntimeCall> + GetDispatchInfo,
 		T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
 	{
 		// NOTE: we can actually make it understand `ChargeTransactionPayment`, but would be some
@@ -642,7 +748,8 @@ impl<T: Config> Pallet<T> {
 		let dispatch_info = <Extrinsic as GetDispatchInfo>::get_dispatch_info(&unchecked_extrinsic);
 
 		let partial_fee = if unchecked_extrinsic.is_signed().unwrap_or(false) {
-			Self::compute_fee(len, &dispatch_info, 0u32.into())
+			let call = unchecked_extrinsic.call();
+			Self::compute_call_fee_details(call, len, &dispatch_info, 0u32.into()).final_fee()
 		} else {
 			// Unsigned extrinsics have no partial fee.
 			0u32.into()
@@ -658,11 +765,12 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Query the detailed fee of a given `call`.
-	pub fn query_fee_details<Extrinsic: sp_runtime::traits::Extrinsic + GetDispatchInfo>(
+	pub fn query_fee_details<Extrinsic>(
 		unchecked_extrinsic: Extrinsic,
 		len: u32,
 	) -> FeeDetails<BalanceOf<T>>
 	where
+		Extrinsic: ExtrinsicCall<Call = T::RuntimeCall> + GetDispatchInfo,
 		T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
 	{
 		let dispatch_info = <Extrinsic as GetDispatchInfo>::get_dispatch_info(&unchecked_extrinsic);
@@ -670,7 +778,8 @@ impl<T: Config> Pallet<T> {
 		let tip = 0u32.into();
 
 		if unchecked_extrinsic.is_signed().unwrap_or(false) {
-			Self::compute_fee_details(len, &dispatch_info, tip)
+			let call = unchecked_extrinsic.call();
+			Self::compute_call_fee_details(call, len, &dispatch_info, tip)
 		} else {
 			// Unsigned extrinsics have no inclusion fee.
 			FeeDetails {
@@ -688,10 +797,13 @@ impl<T: Config> Pallet<T> {
 		let dispatch_info = <T::RuntimeCall as GetDispatchInfo>::get_dispatch_info(&call);
 		let DispatchInfo { weight, class, .. } = dispatch_info;
 
+		let partial_fee =
+			Self::compute_call_fee_details(&call, len, &dispatch_info, 0u32.into()).final_fee();
+
 		RuntimeDispatchInfo {
 			weight,
 			class,
-			partial_fee: Self::compute_fee(len, &dispatch_info, 0u32.into()),
+			partial_fee,
 		}
 	}
 
@@ -703,7 +815,7 @@ impl<T: Config> Pallet<T> {
 		let dispatch_info = <T::RuntimeCall as GetDispatchInfo>::get_dispatch_info(&call);
 		let tip = 0u32.into();
 
-		Self::compute_fee_details(len, &dispatch_info, tip)
+		Self::compute_call_fee_details(&call, len, &dispatch_info, tip)
 	}
 
 	/// Compute the final fee value for a particular transaction.
@@ -718,7 +830,25 @@ impl<T: Config> Pallet<T> {
 		Self::compute_fee_details(len, info, tip).final_fee()
 	}
 
+	/// Compute the fee details for a particular transaction, including the blob fee of `call`.
+	pub fn compute_call_fee_details(
+		call: &T::RuntimeCall,
+		len: u32,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		tip: BalanceOf<T>,
+	) -> FeeDetails<BalanceOf<T>>
+	where
+		T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+	{
+		Self::with_blob_fee(
+			Self::compute_fee_details(len, info, tip),
+			Self::blob_fee(call),
+		)
+	}
+
 	/// Compute the fee details for a particular transaction.
+	///
+	/// It does not include the blob fee, which depends on the call.
 	pub fn compute_fee_details(
 		len: u32,
 		info: &DispatchInfoOf<T::RuntimeCall>,
@@ -790,6 +920,7 @@ impl<T: Config> Pallet<T> {
 					base_fee,
 					len_fee: adjusted_length_fee,
 					adjusted_weight_fee,
+					blob_fee: Zero::zero(),
 				}),
 				tip,
 			}
@@ -801,6 +932,29 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Adds `blob_fee` to the inclusion fee of `details`, if the transaction pays one.
+	fn with_blob_fee(
+		mut details: FeeDetails<BalanceOf<T>>,
+		blob_fee: BalanceOf<T>,
+	) -> FeeDetails<BalanceOf<T>> {
+		if let Some(inclusion_fee) = details.inclusion_fee.as_mut() {
+			inclusion_fee.blob_fee = blob_fee;
+		}
+		details
+	}
+
+	/// Compute the blob fee of `call`, adjusted by the blob multiplier.
+	pub fn blob_fee(call: &T::RuntimeCall) -> BalanceOf<T> {
+		let unadjusted_blob_fee = Self::blob_length_to_fee(T::BlobLength::blob_len(call));
+		Self::next_blob_multiplier().saturating_mul_int(unadjusted_blob_fee)
+	}
+
+	/// Compute the unadjusted blob fee of `length` bytes of data by invoking the configured
+	/// `BlobLengthToFee` impl.
+	pub fn blob_length_to_fee(length: u32) -> BalanceOf<T> {
+		T::BlobLengthToFee::weight_to_fee(&Weight::from_parts(length as u64, 0))
+	}
+
 	/// Compute the length portion of a fee by invoking the configured `LengthToFee` impl.
 	pub fn length_to_fee(length: u32) -> BalanceOf<T> {
 		T::LengthToFee::weight_to_fee(&Weight::from_parts(length as u64, 0))
@@ -869,18 +1023,21 @@ where
 		len: usize,
 	) -> Result<
 		(
+			BalanceOf<T>,
 			BalanceOf<T>,
 			<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::LiquidityInfo,
 		),
 		TransactionValidityError,
 	> {
 		let tip = self.0;
-		let fee = Pallet::<T>::compute_fee(len as u32, info, tip);
+		let blob_fee = Pallet::<T>::blob_fee(call);
+		let fee_details = Pallet::<T>::compute_fee_details(len as u32, info, tip);
+		let fee = Pallet::<T>::with_blob_fee(fee_details, blob_fee).final_fee();
 
 		<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::withdraw_fee(
 			who, call, info, fee, tip,
 		)
-		.map(|i| (fee, i))
+		.map(|i| (fee, blob_fee, i))
 	}
 
 	/// Get an appropriate priority for a transaction with the given `DispatchInfo`, encoded length
@@ -988,6 +1145,8 @@ where
 		Self::AccountId,
 		// imbalance resulting from withdrawing the fee
 		<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::LiquidityInfo,
+		// blob fee, which does not depend on the actual weight
+		BalanceOf<T>,
 	);
 	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
 		Ok(())
@@ -1000,7 +1159,7 @@ where
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> TransactionValidity {
-		let (final_fee, _) = self.withdraw_fee(who, call, info, len)?;
+		let (final_fee, _, _) = self.withdraw_fee(who, call, info, len)?;
 		let tip = self.0;
 		Ok(ValidTransaction {
 			priority: Self::get_priority(info, len, tip, final_fee),
@@ -1015,8 +1174,8 @@ where
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> Result<Self::Pre, TransactionValidityError> {
-		let (_fee, imbalance) = self.withdraw_fee(who, call, info, len)?;
-		Ok((self.0, who.clone(), imbalance))
+		let (_fee, blob_fee, imbalance) = self.withdraw_fee(who, call, info, len)?;
+		Ok((self.0, who.clone(), imbalance, blob_fee))
 	}
 
 	fn post_dispatch(
@@ -1026,8 +1185,10 @@ where
 		len: usize,
 		_result: &DispatchResult,
 	) -> Result<(), TransactionValidityError> {
-		if let Some((tip, who, imbalance)) = maybe_pre {
-			let actual_fee = Pallet::<T>::compute_actual_fee(len as u32, info, post_info, tip);
+		if let Some((tip, who, imbalance, blob_fee)) = maybe_pre {
+			let actual_fee_details =
+				Pallet::<T>::compute_actual_fee_details(len as u32, info, post_info, tip);
+			let actual_fee = Pallet::<T>::with_blob_fee(actual_fee_details, blob_fee).final_fee();
 			T::OnChargeTransaction::correct_and_deposit_fee(
 				&who, info, post_info, actual_fee, tip, imbalance,
 			)?;
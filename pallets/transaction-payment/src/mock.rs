@@ -76,6 +76,7 @@ parameter_types! {
 	pub const BlockHashCount: BlockNumber = 250;
 	pub static WeightToFee: u64 = 1;
 	pub static TransactionByteFee: u64 = 1;
+	pub static BlobByteFee: u64 = 1;
 	pub static OperationalFeeMultiplier: u8 = 5;
 }
 
@@ -134,6 +135,26 @@ impl WeightToFeeT for TransactionByteFee {
 	}
 }
 
+impl WeightToFeeT for BlobByteFee {
+	type Balance = u64;
+
+	fn weight_to_fee(weight: &Weight) -> Self::Balance {
+		Self::Balance::saturated_from(weight.ref_time())
+			.saturating_mul(BLOB_BYTE_FEE.with(|v| *v.borrow()))
+	}
+}
+
+/// The remarks are the blob data of the tests.
+pub struct RemarkLength;
+impl GetBlobLength<RuntimeCall> for RemarkLength {
+	fn blob_len(call: &RuntimeCall) -> u32 {
+		match call {
+			RuntimeCall::System(system::Call::remark { remark }) => remark.len() as u32,
+			_ => 0,
+		}
+	}
+}
+
 parameter_types! {
 	pub(crate) static TipUnbalancedAmount: u64 = 0;
 	pub(crate) static FeeUnbalancedAmount: u64 = 0;
@@ -161,4 +182,7 @@ impl Config for Runtime {
 	type LengthToFee = TransactionByteFee;
 	type FeeMultiplierUpdate = ();
 	type LengthMultiplierUpdate = ();
+	type BlobLengthToFee = BlobByteFee;
+	type BlobMultiplierUpdate = ();
+	type BlobLength = RemarkLength;
 }
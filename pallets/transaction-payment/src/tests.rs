@@ -359,7 +359,8 @@ fn query_info_and_fee_details_works() {
                         adjusted_weight_fee: info
                             .weight
                             .min(BlockWeights::get().max_block)
-                            .ref_time() as u64 * 2 * 3 / 2
+                            .ref_time() as u64 * 2 * 3 / 2,
+                        blob_fee: 0,
                     }),
                     tip: 0,
                 },
@@ -407,7 +408,8 @@ fn query_call_info_and_fee_details_works() {
                         adjusted_weight_fee: info
                             .weight
                             .min(BlockWeights::get().max_block)
-                            .ref_time() as u64 * 2 * 3 / 2  /* weight * weight_fee * multipler */
+                            .ref_time() as u64 * 2 * 3 / 2, /* weight * weight_fee * multipler */
+                        blob_fee: 0,
                     }),
                     tip: 0,
                 },
@@ -885,3 +887,126 @@ fn genesis_default_works() {
 		);
 	});
 }
+
+#[test]
+fn query_call_fee_details_includes_blob_fee() {
+	let call = RuntimeCall::System(system::Call::remark {
+		remark: vec![1; 100],
+	});
+	let info = call.get_dispatch_info();
+	let len = call.encoded_size() as u32;
+
+	ExtBuilder::default().build().execute_with(|| {
+		// the blob fee is x1.5
+		<NextBlobMultiplier<Runtime>>::put(Multiplier::saturating_from_rational(3, 2));
+
+		let details = TransactionPayment::query_call_fee_details(call.clone(), len);
+		let inclusion_fee = details.inclusion_fee.clone().unwrap();
+		assert_eq!(inclusion_fee.blob_fee, 150);
+
+		let without_blob = Pallet::<Runtime>::compute_fee_details(len, &info, 0);
+		assert_eq!(details.final_fee(), without_blob.final_fee() + 150);
+		assert_eq!(
+			TransactionPayment::query_call_info(call, len).partial_fee,
+			details.final_fee()
+		);
+
+		// calls without data pay no blob fee.
+		let details = TransactionPayment::query_call_fee_details(CALL.clone(), len);
+		assert_eq!(details.inclusion_fee.unwrap().blob_fee, 0);
+	});
+}
+
+#[test]
+fn signed_extension_charges_blob_fee() {
+	let call = RuntimeCall::System(system::Call::remark {
+		remark: vec![1; 20],
+	});
+
+	ExtBuilder::default()
+		.balance_factor(10)
+		.base_weight(Weight::from_parts(5, 0))
+		.build()
+		.execute_with(|| {
+			let len = 10;
+			<NextBlobMultiplier<Runtime>>::put(Multiplier::saturating_from_integer(2));
+
+			let pre = ChargeTransactionPayment::<Runtime>::from(0)
+				.pre_dispatch(&1, &call, &info_from_weight(Weight::from_parts(50, 0)), len)
+				.unwrap();
+			// 5 base fee, 10 byte fee, 50 weight fee, 2 * 20 blob fee
+			assert_eq!(Balances::free_balance(1), 100 - 5 - 10 - 50 - 40);
+
+			assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+				Some(pre),
+				&info_from_weight(Weight::from_parts(50, 0)),
+				&post_info_from_weight(Weight::from_parts(20, 0)),
+				len,
+				&Ok(())
+			));
+			// only the unused weight is refunded, the blob fee is kept
+			assert_eq!(Balances::free_balance(1), 100 - 5 - 10 - 20 - 40);
+			assert_eq!(FeeUnbalancedAmount::get(), 5 + 10 + 20 + 40);
+		});
+}
+
+#[test]
+fn blob_multiplier_follows_grid_utilization() {
+	use avail_core::{AppId, BlockLengthColumns, BlockLengthRows, BLOCK_CHUNK_SIZE};
+	use frame_system::{limits::BlockLength, ExtrinsicLenOf};
+
+	frame_support::parameter_types! {
+		pub const TargetGridUtilization: Perquintill = Perquintill::from_percent(50);
+		pub BlobAdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 10);
+		pub MinBlobMultiplier: Multiplier = Multiplier::saturating_from_integer(1);
+		pub MaxBlobMultiplier: Multiplier = Multiplier::saturating_from_integer(10);
+	}
+	type Update = BlobFeeAdjustment<
+		Runtime,
+		TargetGridUtilization,
+		BlobAdjustmentVariable,
+		MinBlobMultiplier,
+		MaxBlobMultiplier,
+	>;
+
+	// Uses `num_scalars` of the 32 x 32 grid.
+	let fill_grid = |num_scalars: u32| {
+		let mut len = ExtrinsicLenOf::<Runtime>::default();
+		if num_scalars > 0 {
+			len.add_padded(AppId(1), num_scalars * 31 - 31).unwrap();
+		}
+		AllExtrinsicsLen::<Runtime>::put(len);
+	};
+
+	ExtBuilder::default().build().execute_with(|| {
+		let block_length = BlockLength::with_normal_ratio(
+			BlockLengthRows(32),
+			BlockLengthColumns(32),
+			BLOCK_CHUNK_SIZE,
+			Perbill::from_percent(90),
+		)
+		.unwrap();
+		DynamicBlockLength::<Runtime>::put(block_length);
+		let multiplier = Multiplier::saturating_from_integer(2);
+
+		// a full grid increases the multiplier.
+		fill_grid(1024);
+		assert!(Update::convert(multiplier) > multiplier);
+
+		// an empty grid decreases it, down to the minimum.
+		fill_grid(0);
+		assert!(Update::convert(multiplier) < multiplier);
+		assert_eq!(
+			Update::convert(MinBlobMultiplier::get()),
+			MinBlobMultiplier::get()
+		);
+
+		// a grid at the target keeps it.
+		fill_grid(512);
+		assert_eq!(Update::convert(multiplier), multiplier);
+
+		// the block weight does not matter.
+		System::set_block_consumed_resources(Weight::from_parts(1024, 0), 0);
+		assert_eq!(Update::convert(multiplier), multiplier);
+	});
+}
@@ -46,18 +46,22 @@ pub struct InclusionFee<Balance> {
 	///
 	/// adjusted_weight_fee = targeted_fee_adjustment * weight_fee
 	pub adjusted_weight_fee: Balance,
+	/// The blob fee, the amount paid for the data submitted by the transaction, adjusted to the
+	/// utilization of the padded grid.
+	pub blob_fee: Balance,
 }
 
 impl<Balance: AtLeast32BitUnsigned + Copy> InclusionFee<Balance> {
 	/// Returns the total of inclusion fee.
 	///
 	/// ```ignore
-	/// inclusion_fee = base_fee + len_fee + adjusted_weight_fee
+	/// inclusion_fee = base_fee + len_fee + adjusted_weight_fee + blob_fee
 	/// ```
 	pub fn inclusion_fee(&self) -> Balance {
 		self.base_fee
 			.saturating_add(self.len_fee)
 			.saturating_add(self.adjusted_weight_fee)
+			.saturating_add(self.blob_fee)
 	}
 }
 
@@ -91,6 +95,43 @@ impl<Balance: AtLeast32BitUnsigned + Copy> FeeDetails<Balance> {
 	}
 }
 
+/// The [`InclusionFee`] returned by runtimes without the blob fee.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct LegacyInclusionFee<Balance> {
+	pub base_fee: Balance,
+	pub len_fee: Balance,
+	pub adjusted_weight_fee: Balance,
+}
+
+impl<Balance: Zero> From<LegacyInclusionFee<Balance>> for InclusionFee<Balance> {
+	fn from(fee: LegacyInclusionFee<Balance>) -> Self {
+		Self {
+			base_fee: fee.base_fee,
+			len_fee: fee.len_fee,
+			adjusted_weight_fee: fee.adjusted_weight_fee,
+			blob_fee: Zero::zero(),
+		}
+	}
+}
+
+/// The [`FeeDetails`] returned by runtimes without the blob fee.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct LegacyFeeDetails<Balance> {
+	pub inclusion_fee: Option<LegacyInclusionFee<Balance>>,
+	pub tip: Balance,
+}
+
+impl<Balance: Zero> From<LegacyFeeDetails<Balance>> for FeeDetails<Balance> {
+	fn from(details: LegacyFeeDetails<Balance>) -> Self {
+		Self {
+			inclusion_fee: details.inclusion_fee.map(Into::into),
+			tip: details.tip,
+		}
+	}
+}
+
 /// Information related to a dispatchable's class, weight, and fee that can be queried from the
 /// runtime.
 #[derive(Eq, PartialEq, Encode, Decode, Default, TypeInfo)]
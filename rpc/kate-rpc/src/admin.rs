@@ -0,0 +1,246 @@
+//! Unsafe RPCs to inspect and manage the grid caches of the Kate RPC.
+use std::{collections::BTreeMap, hash::Hash};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+};
+use sc_rpc_api::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+use sp_runtime::traits::Block as BlockT;
+
+use crate::{
+	disk_cache::GridDiskCache,
+	grid_cache::{eval_grid_weight, poly_grid_weight, GridCaches},
+	HashOf, Kate,
+};
+
+/// Size of one grid cache.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheInfo {
+	pub entries: u64,
+	/// Weighted size, in bytes.
+	pub weighted_size: u64,
+	/// Maximum weighted size, in bytes.
+	pub max_capacity: Option<u64>,
+}
+
+/// Size of the grid caches, returned by `kate_cacheStatus`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStatus {
+	pub eval_grid: CacheInfo,
+	pub poly_grid: CacheInfo,
+}
+
+/// Cached grids of one block, with their weight in bytes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheEntry<Hash> {
+	pub block_hash: Hash,
+	pub eval_grid: Option<u32>,
+	pub poly_grid: Option<u32>,
+}
+
+#[rpc(client, server)]
+pub trait KateAdminApi<Block>
+where
+	Block: BlockT,
+{
+	/// Returns the size of the grid caches.
+	#[method(name = "kate_cacheStatus")]
+	async fn cache_status(&self) -> RpcResult<CacheStatus>;
+
+	/// Returns the blocks whose grids are cached.
+	#[method(name = "kate_cacheEntries")]
+	async fn cache_entries(&self) -> RpcResult<Vec<CacheEntry<HashOf<Block>>>>;
+
+	/// Evicts the grids of `block_hash`, or every grid if it is `None`, from the memory caches
+	/// and from the disk cache.
+	#[method(name = "kate_cacheEvict")]
+	async fn cache_evict(&self, block_hash: Option<HashOf<Block>>) -> RpcResult<CacheStatus>;
+
+	/// Resizes the grid caches, with their sizes in MiB.
+	#[method(name = "kate_cacheResize")]
+	async fn cache_resize(
+		&self,
+		eval_grid_cache_size: u64,
+		poly_grid_cache_size: u64,
+	) -> RpcResult<CacheStatus>;
+}
+
+/// Grid cache management of a [`Kate`] instance. Every method is unsafe.
+pub struct KateAdmin<Client, Block: BlockT> {
+	kate: Kate<Client, Block>,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<Client, Block: BlockT> KateAdmin<Client, Block> {
+	pub fn new(kate: Kate<Client, Block>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { kate, deny_unsafe }
+	}
+
+	async fn status(&self) -> CacheStatus {
+		let eval = self.kate.grid_caches.eval();
+		let poly = self.kate.grid_caches.poly();
+		eval.run_pending_tasks().await;
+		poly.run_pending_tasks().await;
+
+		CacheStatus {
+			eval_grid: CacheInfo {
+				entries: eval.entry_count(),
+				weighted_size: eval.weighted_size(),
+				max_capacity: eval.policy().max_capacity(),
+			},
+			poly_grid: CacheInfo {
+				entries: poly.entry_count(),
+				weighted_size: poly.weighted_size(),
+				max_capacity: poly.policy().max_capacity(),
+			},
+		}
+	}
+}
+
+#[async_trait]
+impl<Client, Block> KateAdminApiServer<Block> for KateAdmin<Client, Block>
+where
+	Block: BlockT,
+	Client: Send + Sync + 'static,
+{
+	async fn cache_status(&self) -> RpcResult<CacheStatus> {
+		self.deny_unsafe.check_if_safe()?;
+		Ok(self.status().await)
+	}
+
+	async fn cache_entries(&self) -> RpcResult<Vec<CacheEntry<HashOf<Block>>>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let mut entries = BTreeMap::new();
+		for (block_hash, grid) in self.kate.grid_caches.eval().iter() {
+			entries.entry(*block_hash).or_insert((None, None)).0 = Some(eval_grid_weight(&grid));
+		}
+		for (block_hash, grid) in self.kate.grid_caches.poly().iter() {
+			entries.entry(*block_hash).or_insert((None, None)).1 = Some(poly_grid_weight(&grid));
+		}
+
+		let entries = entries
+			.into_iter()
+			.map(|(block_hash, (eval_grid, poly_grid))| CacheEntry {
+				block_hash,
+				eval_grid,
+				poly_grid,
+			})
+			.collect();
+		Ok(entries)
+	}
+
+	async fn cache_evict(&self, block_hash: Option<HashOf<Block>>) -> RpcResult<CacheStatus> {
+		self.deny_unsafe.check_if_safe()?;
+
+		// The disk entries go first, so the evicted grids cannot be reloaded from disk.
+		if let Some(disk_cache) = self.kate.grid_disk_cache.clone() {
+			let disk_hash = block_hash.map(|block_hash| block_hash.as_ref().to_vec());
+			self.kate
+				.run_blocking("kate-rpc-grid-evict", move || {
+					evict_disk(&disk_cache, disk_hash.as_deref())
+				})
+				.await?;
+		}
+		evict_memory(&self.kate.grid_caches, block_hash).await;
+
+		Ok(self.status().await)
+	}
+
+	async fn cache_resize(
+		&self,
+		eval_grid_cache_size: u64,
+		poly_grid_cache_size: u64,
+	) -> RpcResult<CacheStatus> {
+		self.deny_unsafe.check_if_safe()?;
+
+		// Sizes are in MiB. We need Bytes.
+		self.kate
+			.grid_caches
+			.resize(
+				eval_grid_cache_size.saturating_mul(1024 * 1024),
+				poly_grid_cache_size.saturating_mul(1024 * 1024),
+			)
+			.await;
+
+		Ok(self.status().await)
+	}
+}
+
+/// Removes the persisted grids of `block_hash`, or every persisted grid if it is `None`.
+fn evict_disk(disk_cache: &GridDiskCache, block_hash: Option<&[u8]>) {
+	match block_hash {
+		Some(block_hash) => disk_cache.remove(block_hash),
+		None => disk_cache.clear(),
+	}
+}
+
+/// Invalidates the cached grids of `block_hash`, or every cached grid if it is `None`.
+async fn evict_memory<H>(grid_caches: &GridCaches<H>, block_hash: Option<H>)
+where
+	H: Hash + Eq + Send + Sync + 'static,
+{
+	let (eval, poly) = (grid_caches.eval(), grid_caches.poly());
+	match block_hash {
+		Some(block_hash) => {
+			eval.invalidate(&block_hash).await;
+			poly.invalidate(&block_hash).await;
+		},
+		None => {
+			eval.invalidate_all();
+			poly.invalidate_all();
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use avail_core::{AppExtrinsic, AppId};
+	use futures::executor::block_on;
+	use kate::gridgen::EvaluationGrid;
+	use sp_core::H256;
+
+	use super::*;
+
+	fn grid(data: &[u8]) -> EvaluationGrid {
+		let extrinsics = vec![AppExtrinsic::new(AppId(1), data.to_vec())];
+		EvaluationGrid::from_extrinsics(extrinsics, 4, 256, 256, [0u8; 32]).unwrap()
+	}
+
+	#[test]
+	fn evicted_grids_are_removed_from_disk() {
+		let dir = tempfile::tempdir().unwrap();
+		let disk_cache = GridDiskCache::open(dir.path(), 16).unwrap();
+		let caches = GridCaches::new(1 << 30, 1 << 30);
+		let (block_a, block_b) = (H256::repeat_byte(1), H256::repeat_byte(2));
+		let grid = Arc::new(grid(b"ExampleData"));
+
+		block_on(async {
+			for block_hash in [block_a, block_b] {
+				disk_cache.insert(block_hash.as_ref(), 1, &grid);
+				caches.eval().insert(block_hash, grid.clone()).await;
+			}
+
+			evict_disk(&disk_cache, Some(block_a.as_ref()));
+			evict_memory(&caches, Some(block_a)).await;
+
+			// The next query of `block_a` can neither hit the memory cache nor reload it.
+			assert!(caches.eval().get(&block_a).await.is_none());
+			assert!(disk_cache.get(block_a.as_ref(), |_| true).is_none());
+			assert!(caches.eval().get(&block_b).await.is_some());
+
+			evict_disk(&disk_cache, None);
+			evict_memory(&caches, None).await;
+
+			assert!(caches.eval().get(&block_b).await.is_none());
+			assert!(disk_cache.get(block_b.as_ref(), |_| true).is_none());
+		});
+	}
+}
@@ -0,0 +1,214 @@
+//! Node-side index from the hash of submitted data to its location on chain.
+//!
+//! `da_control::submit_data` indexes every blob under its `blake2_256` hash, but the client
+//! database only maps that hash to the raw bytes. This index keeps the block and the extrinsic
+//! index of each submission of finalized blocks, so the blob and its proof can be fetched using
+//! only the data hash.
+//!
+//! The index is stored in a key-value database, the aux store of the client on a node. Each data
+//! hash is a key whose value is the SCALE encoded list of [`BlobLocation`], as the same data can
+//! be submitted several times. The blobs of a block are written in a single transaction.
+use std::{
+	collections::{btree_map::Entry, BTreeMap},
+	sync::{Arc, Mutex},
+};
+
+use codec::{Decode, Encode};
+use sc_client_api::AuxStore;
+use sp_core::hexdisplay::HexDisplay;
+
+const LOG_TARGET: &str = "avail::kate::rpc::blob_index";
+
+/// Prefix of the keys of the index.
+const KEY_PREFIX: &[u8] = b"kate_blob_index";
+
+/// Location of one submission of a blob.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct BlobLocation {
+	pub block_hash: Vec<u8>,
+	pub block_number: u32,
+	/// Index of the `submit_data` extrinsic in the block.
+	pub transaction_index: u32,
+}
+
+/// Key-value database of the index.
+pub trait IndexDb: Send + Sync {
+	fn get(&self, key: &[u8]) -> sp_blockchain::Result<Option<Vec<u8>>>;
+
+	/// Writes every entry of `entries` atomically.
+	fn insert(&self, entries: &[(Vec<u8>, Vec<u8>)]) -> sp_blockchain::Result<()>;
+}
+
+impl<T: AuxStore + Send + Sync> IndexDb for T {
+	fn get(&self, key: &[u8]) -> sp_blockchain::Result<Option<Vec<u8>>> {
+		self.get_aux(key)
+	}
+
+	fn insert(&self, entries: &[(Vec<u8>, Vec<u8>)]) -> sp_blockchain::Result<()> {
+		let entries = entries
+			.iter()
+			.map(|(key, value)| (key.as_slice(), value.as_slice()))
+			.collect::<Vec<_>>();
+		self.insert_aux(&entries, std::iter::empty())
+	}
+}
+
+/// Persistent index of the blobs submitted in finalized blocks, keyed by data hash.
+pub struct BlobIndex {
+	db: Arc<dyn IndexDb>,
+	/// Serializes the read-modify-write of the entries, the reads need no lock.
+	lock: Mutex<()>,
+}
+
+impl BlobIndex {
+	/// Opens the index stored in `db`.
+	pub fn new(db: Arc<dyn IndexDb>) -> Self {
+		Self {
+			db,
+			lock: Mutex::new(()),
+		}
+	}
+
+	/// Returns the known locations of `data_hash`, newest first.
+	pub fn get(&self, data_hash: &[u8]) -> Vec<BlobLocation> {
+		let mut locations = self.read(&key(data_hash));
+		locations.reverse();
+		locations
+	}
+
+	/// Adds the locations of the blobs of one block, given with their data hash.
+	pub fn insert(&self, blobs: impl IntoIterator<Item = ([u8; 32], BlobLocation)>) {
+		let _guard = self.lock.lock().expect("Poisoned lock");
+
+		let mut entries = BTreeMap::new();
+		for (data_hash, location) in blobs {
+			let locations = match entries.entry(key(&data_hash)) {
+				Entry::Occupied(entry) => entry.into_mut(),
+				Entry::Vacant(entry) => {
+					let locations = self.read(entry.key());
+					entry.insert((locations, false))
+				},
+			};
+			if !locations.0.contains(&location) {
+				locations.0.push(location);
+				locations.1 = true;
+			}
+		}
+
+		let entries = entries
+			.into_iter()
+			.filter(|(_, (_, changed))| *changed)
+			.map(|(key, (locations, _))| (key, locations.encode()))
+			.collect::<Vec<_>>();
+		if entries.is_empty() {
+			return;
+		}
+		if let Err(e) = self.db.insert(&entries) {
+			log::warn!(
+				target: LOG_TARGET,
+				"Locations of {} blobs cannot be persisted: {e:?}",
+				entries.len()
+			);
+		}
+	}
+
+	fn read(&self, key: &[u8]) -> Vec<BlobLocation> {
+		let content = match self.db.get(key) {
+			Ok(Some(content)) => content,
+			Ok(None) => return Vec::new(),
+			Err(e) => {
+				log::warn!(target: LOG_TARGET, "Blob index cannot be read: {e:?}");
+				return Vec::new();
+			},
+		};
+
+		Vec::<BlobLocation>::decode(&mut content.as_slice()).unwrap_or_else(|_| {
+			log::warn!(
+				target: LOG_TARGET,
+				"Invalid index entry for blob 0x{}",
+				HexDisplay::from(&&key[KEY_PREFIX.len()..])
+			);
+			Vec::new()
+		})
+	}
+}
+
+fn key(data_hash: &[u8]) -> Vec<u8> {
+	[KEY_PREFIX, data_hash].concat()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashMap;
+
+	use super::*;
+
+	const DATA_A: [u8; 32] = [1u8; 32];
+	const DATA_B: [u8; 32] = [2u8; 32];
+
+	#[derive(Default)]
+	struct MemoryDb(Mutex<HashMap<Vec<u8>, Vec<u8>>>);
+
+	impl IndexDb for MemoryDb {
+		fn get(&self, key: &[u8]) -> sp_blockchain::Result<Option<Vec<u8>>> {
+			Ok(self.0.lock().unwrap().get(key).cloned())
+		}
+
+		fn insert(&self, entries: &[(Vec<u8>, Vec<u8>)]) -> sp_blockchain::Result<()> {
+			self.0.lock().unwrap().extend(entries.iter().cloned());
+			Ok(())
+		}
+	}
+
+	fn location(block_number: u32, transaction_index: u32) -> BlobLocation {
+		BlobLocation {
+			block_hash: vec![block_number as u8; 32],
+			block_number,
+			transaction_index,
+		}
+	}
+
+	fn index() -> (Arc<MemoryDb>, BlobIndex) {
+		let db = Arc::new(MemoryDb::default());
+		(db.clone(), BlobIndex::new(db))
+	}
+
+	#[test]
+	fn inserted_locations_are_loaded_back() {
+		let (db, index) = index();
+
+		index.insert([(DATA_A, location(1, 2)), (DATA_B, location(1, 3))]);
+
+		assert_eq!(index.get(&DATA_A), vec![location(1, 2)]);
+		assert_eq!(index.get(&DATA_B), vec![location(1, 3)]);
+		assert!(index.get(&[3u8; 32]).is_empty());
+		// The index survives reopening the database.
+		assert_eq!(BlobIndex::new(db).get(&DATA_A), vec![location(1, 2)]);
+	}
+
+	#[test]
+	fn every_submission_is_kept_newest_first() {
+		let (_, index) = index();
+
+		index.insert([(DATA_A, location(1, 2)), (DATA_A, location(1, 5))]);
+		index.insert([(DATA_A, location(4, 1))]);
+		// Blocks indexed again are not duplicated.
+		index.insert([(DATA_A, location(4, 1))]);
+
+		assert_eq!(
+			index.get(&DATA_A),
+			vec![location(4, 1), location(1, 5), location(1, 2)]
+		);
+	}
+
+	#[test]
+	fn corrupted_entry_is_replaced() {
+		let (db, index) = index();
+		db.insert(&[(key(&DATA_A), vec![0xff; 7])]).unwrap();
+
+		assert!(index.get(&DATA_A).is_empty());
+
+		index.insert([(DATA_A, location(1, 2))]);
+		assert_eq!(index.get(&DATA_A), vec![location(1, 2)]);
+	}
+}
@@ -0,0 +1,892 @@
+//! `chainHead`-style Kate RPC, bound to follow subscriptions.
+//!
+//! The follow subscriptions of `chainHead_unstable_follow` are private to the `sc-rpc-spec-v2`
+//! server, so the Kate extension has its own: `kate_chainHead_follow` reports the same events
+//! (`initialized`, `newBlock`, `bestBlockChanged`, `finalized` and `stop`) and pins every
+//! reported block in the backend until it is unpinned with `kate_chainHead_unpin`, or pruned.
+//! Pinned blocks keep their body, even on forks, so they can be queried until they are unpinned.
+//!
+//! `kate_chainHead_queryCells` and `kate_chainHead_queryRows` only accept blocks pinned by their
+//! follow subscription. They return an operation id, and the result is reported later on the
+//! follow subscription by an `operationCellsDone`, `operationRowsDone` or `operationError` event.
+use std::{
+	collections::{HashMap, HashSet},
+	marker::PhantomData,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc, Mutex,
+	},
+};
+
+use avail_core::{header::HeaderExtension, traits::ExtendedHeader, OpaqueExtrinsic};
+use da_runtime::apis::DataAvailApi;
+use futures::{
+	channel::mpsc::{self, UnboundedSender},
+	stream, FutureExt, StreamExt,
+};
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::{SubscriptionId, SubscriptionResult},
+	SubscriptionSink,
+};
+use sc_client_api::{Backend, BlockBackend, BlockchainEvents};
+use sc_rpc_spec_v2::chain_head::{BestBlockChanged, Finalized};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::{HeaderBackend, HeaderMetadata};
+use sp_runtime::{
+	generic::Digest,
+	traits::{Block as BlockT, Header, One, Saturating},
+};
+
+use crate::{
+	finality::{Finality, QueryOptions},
+	rate_limit::{self, KateCall},
+	rate_limited_error,
+	v2::{ProofsResponse, RowsResponse},
+	Cells, HashOf, Kate, KateApiServer, Rows,
+};
+
+const LOG_TARGET: &str = "avail::kate::rpc::chain_head";
+
+/// Maximum number of follow subscriptions.
+const MAX_FOLLOW_SUBSCRIPTIONS: usize = 64;
+/// Maximum number of blocks pinned by one follow subscription. The subscription is stopped when
+/// it is exceeded.
+const MAX_PINNED_BLOCKS: usize = 512;
+/// Maximum number of operations of one follow subscription running at the same time.
+const MAX_OPERATIONS: usize = 16;
+
+/// Event of `kate_chainHead_follow`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub enum FollowEvent<Hash> {
+	#[serde(rename_all = "camelCase")]
+	Initialized {
+		finalized_block_hash: Hash,
+	},
+	#[serde(rename_all = "camelCase")]
+	NewBlock {
+		block_hash: Hash,
+		parent_block_hash: Hash,
+	},
+	BestBlockChanged(BestBlockChanged<Hash>),
+	/// The pruned blocks are unpinned by the event.
+	Finalized(Finalized<Hash>),
+	/// Result of `kate_chainHead_queryCells`.
+	#[serde(rename_all = "camelCase")]
+	OperationCellsDone {
+		operation_id: String,
+		value: ProofsResponse,
+	},
+	/// Result of `kate_chainHead_queryRows`.
+	#[serde(rename_all = "camelCase")]
+	OperationRowsDone {
+		operation_id: String,
+		value: RowsResponse,
+	},
+	#[serde(rename_all = "camelCase")]
+	OperationError {
+		operation_id: String,
+		error: String,
+	},
+	/// The subscription is over, every block it pinned is unpinned.
+	Stop,
+}
+
+impl<Hash> FollowEvent<Hash> {
+	/// Block pinned by the event.
+	fn pinned(&self) -> Option<&Hash> {
+		match self {
+			Self::Initialized {
+				finalized_block_hash,
+			} => Some(finalized_block_hash),
+			Self::NewBlock { block_hash, .. } => Some(block_hash),
+			_ => None,
+		}
+	}
+}
+
+/// Response of the methods which start an operation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "camelCase")]
+pub enum MethodResponse {
+	/// The result is reported by an event of the follow subscription.
+	#[serde(rename_all = "camelCase")]
+	Started { operation_id: String },
+	/// Too many operations of the follow subscription are running.
+	LimitReached,
+}
+
+#[rpc(client, server)]
+pub trait KateChainHeadApi<Block>
+where
+	Block: BlockT,
+{
+	/// Follows the chain, pinning every reported block.
+	#[subscription(
+		name = "kate_chainHead_follow" => "kate_chainHead_followEvent",
+		unsubscribe = "kate_chainHead_unfollow",
+		item = FollowEvent<HashOf<Block>>,
+	)]
+	fn follow(&self);
+
+	/// Unpins blocks of `follow_subscription`. No block is unpinned if one of them is not
+	/// pinned.
+	#[method(name = "kate_chainHead_unpin")]
+	async fn unpin(&self, follow_subscription: String, hashes: Vec<HashOf<Block>>)
+		-> RpcResult<()>;
+
+	/// Starts building the proofs of `cells` of the pinned block `hash`, as
+	/// `kate_queryProofV2`.
+	#[method(name = "kate_chainHead_queryCells")]
+	async fn query_cells(
+		&self,
+		follow_subscription: String,
+		hash: HashOf<Block>,
+		cells: Cells,
+	) -> RpcResult<MethodResponse>;
+
+	/// Starts querying `rows` of the pinned block `hash`, as `kate_queryRowsV2`.
+	#[method(name = "kate_chainHead_queryRows")]
+	async fn query_rows(
+		&self,
+		follow_subscription: String,
+		hash: HashOf<Block>,
+		rows: Rows,
+	) -> RpcResult<MethodResponse>;
+}
+
+/// Pins of blocks in the backend, which keep their body while they are pinned.
+///
+/// Pins are reference counted: every successful [`PinBlocks::pin`] is undone by one
+/// [`PinBlocks::unpin`].
+trait PinBlocks<Hash>: Send + Sync {
+	fn pin(&self, hash: &Hash) -> sp_blockchain::Result<()>;
+
+	fn unpin(&self, hash: &Hash);
+}
+
+/// [`PinBlocks`] of a client backend.
+struct BackendPins<B, Block> {
+	backend: Arc<B>,
+	_phantom: PhantomData<Block>,
+}
+
+impl<B, Block> PinBlocks<Block::Hash> for BackendPins<B, Block>
+where
+	Block: BlockT,
+	B: Backend<Block>,
+{
+	fn pin(&self, hash: &Block::Hash) -> sp_blockchain::Result<()> {
+		self.backend.pin_block(*hash)
+	}
+
+	fn unpin(&self, hash: &Block::Hash) {
+		self.backend.unpin_block(*hash)
+	}
+}
+
+struct Subscription<Hash> {
+	pinned: HashSet<Hash>,
+	sender: UnboundedSender<FollowEvent<Hash>>,
+	operations: usize,
+}
+
+/// Follow subscriptions and their pinned blocks.
+///
+/// A block pinned by several subscriptions holds one backend pin for each of them.
+struct FollowSubscriptions<Hash> {
+	subscriptions: Mutex<HashMap<String, Subscription<Hash>>>,
+	next_operation: AtomicU64,
+	pins: Arc<dyn PinBlocks<Hash>>,
+}
+
+impl<Hash: Eq + std::hash::Hash + Clone> FollowSubscriptions<Hash> {
+	fn new(pins: Arc<dyn PinBlocks<Hash>>) -> Self {
+		Self {
+			subscriptions: Mutex::new(HashMap::new()),
+			next_operation: AtomicU64::new(0),
+			pins,
+		}
+	}
+
+	/// Registers a subscription, or returns `false` if there are too many.
+	fn insert(&self, id: String, sender: UnboundedSender<FollowEvent<Hash>>) -> bool {
+		let mut subscriptions = self.subscriptions.lock().expect("Poisoned lock");
+		if subscriptions.len() >= MAX_FOLLOW_SUBSCRIPTIONS {
+			return false;
+		}
+		let subscription = Subscription {
+			pinned: HashSet::new(),
+			sender,
+			operations: 0,
+		};
+		subscriptions.insert(id, subscription);
+		true
+	}
+
+	/// Removes a subscription, unpinning its blocks.
+	fn remove(&self, id: &str) {
+		let subscription = self.subscriptions.lock().expect("Poisoned lock").remove(id);
+		for hash in subscription
+			.iter()
+			.flat_map(|subscription| &subscription.pinned)
+		{
+			self.pins.unpin(hash);
+		}
+	}
+
+	/// Pins `hash`, or returns `false` if the backend cannot pin it or the subscription pins too
+	/// many blocks.
+	fn pin(&self, id: &str, hash: Hash) -> bool {
+		let mut subscriptions = self.subscriptions.lock().expect("Poisoned lock");
+		let Some(subscription) = subscriptions.get_mut(id) else {
+			return false;
+		};
+		if subscription.pinned.contains(&hash) {
+			return true;
+		}
+		if subscription.pinned.len() >= MAX_PINNED_BLOCKS || self.pins.pin(&hash).is_err() {
+			return false;
+		}
+		subscription.pinned.insert(hash);
+		true
+	}
+
+	fn unpin(&self, id: &str, hashes: &[Hash]) -> RpcResult<()> {
+		let mut subscriptions = self.subscriptions.lock().expect("Poisoned lock");
+		let subscription = subscriptions
+			.get_mut(id)
+			.ok_or_else(|| invalid_subscription(id))?;
+		if !hashes.iter().all(|hash| subscription.pinned.contains(hash)) {
+			return Err(JsonRpseeError::Custom(
+				"Some blocks are not pinned by the subscription".to_string(),
+			));
+		}
+		for hash in hashes {
+			if subscription.pinned.remove(hash) {
+				self.pins.unpin(hash);
+			}
+		}
+		Ok(())
+	}
+
+	/// Unpins the blocks of `hashes` pinned by the subscription, ignoring the others.
+	fn unpin_pruned(&self, id: &str, hashes: &[Hash]) {
+		let mut subscriptions = self.subscriptions.lock().expect("Poisoned lock");
+		let Some(subscription) = subscriptions.get_mut(id) else {
+			return;
+		};
+		for hash in hashes {
+			if subscription.pinned.remove(hash) {
+				self.pins.unpin(hash);
+			}
+		}
+	}
+
+	/// Starts an operation on the pinned block `hash`, and returns its id and the sender of the
+	/// subscription events, or `None` if too many operations are running.
+	fn start_operation(
+		&self,
+		id: &str,
+		hash: &Hash,
+	) -> RpcResult<Option<(String, UnboundedSender<FollowEvent<Hash>>)>> {
+		let mut subscriptions = self.subscriptions.lock().expect("Poisoned lock");
+		let subscription = subscriptions
+			.get_mut(id)
+			.ok_or_else(|| invalid_subscription(id))?;
+		if !subscription.pinned.contains(hash) {
+			return Err(JsonRpseeError::Custom(
+				"Block is not pinned by the subscription".to_string(),
+			));
+		}
+		if subscription.operations >= MAX_OPERATIONS {
+			return Ok(None);
+		}
+
+		subscription.operations += 1;
+		let operation_id = self.next_operation.fetch_add(1, Ordering::Relaxed);
+		Ok(Some((
+			operation_id.to_string(),
+			subscription.sender.clone(),
+		)))
+	}
+
+	fn finish_operation(&self, id: &str) {
+		let mut subscriptions = self.subscriptions.lock().expect("Poisoned lock");
+		if let Some(subscription) = subscriptions.get_mut(id) {
+			subscription.operations = subscription.operations.saturating_sub(1);
+		}
+	}
+}
+
+fn invalid_subscription(id: &str) -> JsonRpseeError {
+	JsonRpseeError::Custom(format!("Invalid follow subscription {id}"))
+}
+
+/// Blocks pruned by the finalization of `finalized`: every block of the forks of `stale_heads`,
+/// from their stale head back to the finalized chain, as `chainHead_unstable_follow` reports them.
+fn pruned_blocks<Block, T>(
+	blockchain: &T,
+	finalized: Block::Hash,
+	stale_heads: &[Block::Hash],
+) -> Vec<Block::Hash>
+where
+	Block: BlockT,
+	T: HeaderMetadata<Block, Error = sp_blockchain::Error> + ?Sized,
+{
+	let mut seen = HashSet::new();
+	let mut pruned = Vec::new();
+	for stale_head in stale_heads {
+		match sp_blockchain::tree_route(blockchain, finalized, *stale_head) {
+			// Forks sharing blocks are reported once.
+			Ok(route) => pruned.extend(
+				route
+					.enacted()
+					.iter()
+					.map(|block| block.hash)
+					.filter(|hash| seen.insert(*hash)),
+			),
+			Err(e) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Fork of stale head {stale_head:?} cannot be pruned: {e:?}"
+				);
+				if seen.insert(*stale_head) {
+					pruned.push(*stale_head);
+				}
+			},
+		}
+	}
+	pruned
+}
+
+/// `chainHead`-style Kate RPC of a [`Kate`] instance.
+pub struct KateChainHead<Client, Block: BlockT> {
+	kate: Kate<Client, Block>,
+	subscriptions: Arc<FollowSubscriptions<Block::Hash>>,
+}
+
+impl<Client, Block: BlockT> KateChainHead<Client, Block> {
+	/// Creates the RPC, pinning the blocks reported to its subscriptions in `backend`.
+	pub fn new<B>(kate: Kate<Client, Block>, backend: Arc<B>) -> Self
+	where
+		B: Backend<Block> + 'static,
+	{
+		let pins = BackendPins {
+			backend,
+			_phantom: PhantomData,
+		};
+		Self {
+			kate,
+			subscriptions: Arc::new(FollowSubscriptions::new(Arc::new(pins))),
+		}
+	}
+}
+
+impl<Client, Block> KateChainHead<Client, Block>
+where
+	Block: BlockT<Extrinsic = OpaqueExtrinsic>,
+	<Block as BlockT>::Header: ExtendedHeader<
+		<<Block as BlockT>::Header as Header>::Number,
+		<Block as BlockT>::Hash,
+		Digest,
+		HeaderExtension,
+	>,
+	Client: Send + Sync + 'static,
+	Client: HeaderBackend<Block>
+		+ ProvideRuntimeApi<Block>
+		+ BlockBackend<Block>
+		+ BlockchainEvents<Block>,
+	Client::Api: DataAvailApi<Block>,
+{
+	/// Events describing the current chain: the finalized head, its descendants on the best chain
+	/// and the best block.
+	fn initial_events(&self) -> Vec<FollowEvent<Block::Hash>> {
+		let client = &self.kate.client;
+		let info = client.info();
+
+		let mut events = vec![FollowEvent::Initialized {
+			finalized_block_hash: info.finalized_hash,
+		}];
+		let (mut parent, mut number) = (info.finalized_hash, info.finalized_number);
+		while number < info.best_number {
+			number = number.saturating_add(One::one());
+			let Ok(Some(hash)) = client.hash(number) else {
+				break;
+			};
+			events.push(FollowEvent::NewBlock {
+				block_hash: hash,
+				parent_block_hash: parent,
+			});
+			parent = hash;
+		}
+		events.push(FollowEvent::BestBlockChanged(BestBlockChanged {
+			best_block_hash: parent,
+		}));
+
+		events
+	}
+
+	/// Spawns `operation` on the pinned block `hash` of `follow_subscription`, and reports its
+	/// result on the subscription.
+	fn spawn_operation<F, T>(
+		&self,
+		follow_subscription: String,
+		hash: &Block::Hash,
+		operation: F,
+		done: fn(String, T) -> FollowEvent<Block::Hash>,
+	) -> RpcResult<MethodResponse>
+	where
+		F: std::future::Future<Output = RpcResult<T>> + Send + 'static,
+		T: Send + 'static,
+	{
+		let Some((operation_id, sender)) = self
+			.subscriptions
+			.start_operation(&follow_subscription, hash)?
+		else {
+			return Ok(MethodResponse::LimitReached);
+		};
+
+		let subscriptions = self.subscriptions.clone();
+		let id = operation_id.clone();
+		let fut = async move {
+			let event = match operation.await {
+				Ok(value) => done(id, value),
+				Err(e) => FollowEvent::OperationError {
+					operation_id: id,
+					error: e.to_string(),
+				},
+			};
+			// The subscription may be over, its events are then dropped.
+			let _ = sender.unbounded_send(event);
+			subscriptions.finish_operation(&follow_subscription);
+		};
+		self.kate
+			.executor
+			.spawn("kate-rpc-chain-head-operation", Some("rpc"), fut.boxed());
+
+		Ok(MethodResponse::Started { operation_id })
+	}
+}
+
+#[async_trait]
+impl<Client, Block> KateChainHeadApiServer<Block> for KateChainHead<Client, Block>
+where
+	Block: BlockT<Extrinsic = OpaqueExtrinsic>,
+	<Block as BlockT>::Header: ExtendedHeader<
+		<<Block as BlockT>::Header as Header>::Number,
+		<Block as BlockT>::Hash,
+		Digest,
+		HeaderExtension,
+	>,
+	Client: Send + Sync + 'static,
+	Client: HeaderBackend<Block>
+		+ HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ ProvideRuntimeApi<Block>
+		+ BlockBackend<Block>
+		+ BlockchainEvents<Block>,
+	Client::Api: DataAvailApi<Block>,
+{
+	fn follow(&self, mut sink: SubscriptionSink) -> SubscriptionResult {
+		if let Err(limited) = self
+			.kate
+			.charge_origin(rate_limit::current_origin(), KateCall::ChainHeadFollow)
+		{
+			let _ = sink.reject(rate_limited_error(KateCall::ChainHeadFollow, limited));
+			return Ok(());
+		}
+		sink.accept()?;
+		let id = match sink.subscription_id() {
+			Some(SubscriptionId::Num(id)) => id.to_string(),
+			Some(SubscriptionId::Str(id)) => id.into_owned(),
+			None => return Ok(()),
+		};
+
+		let (sender, operations) = mpsc::unbounded();
+		if !self.subscriptions.insert(id.clone(), sender) {
+			let _ = sink.send(&FollowEvent::<Block::Hash>::Stop);
+			return Ok(());
+		}
+
+		let client = &self.kate.client;
+		let imports = client
+			.import_notification_stream()
+			.flat_map(|notification| {
+				let mut events = vec![FollowEvent::NewBlock {
+					block_hash: notification.hash,
+					parent_block_hash: *notification.header.parent_hash(),
+				}];
+				if notification.is_new_best {
+					events.push(FollowEvent::BestBlockChanged(BestBlockChanged {
+						best_block_hash: notification.hash,
+					}));
+				}
+				stream::iter(events)
+			});
+		// Blocks finalized implicitly (`tree_route`) come before the new finalized head.
+		let blockchain = client.clone();
+		let finalizations = client
+			.finality_notification_stream()
+			.map(move |notification| {
+				let finalized_block_hashes = notification
+					.tree_route
+					.iter()
+					.cloned()
+					.chain(std::iter::once(notification.hash))
+					.collect();
+				FollowEvent::Finalized(Finalized {
+					finalized_block_hashes,
+					pruned_block_hashes: pruned_blocks(
+						&*blockchain,
+						notification.hash,
+						&notification.stale_heads,
+					),
+				})
+			});
+		let chain = stream::select(imports, finalizations);
+		let mut events = stream::iter(self.initial_events())
+			.chain(stream::select(chain, operations))
+			.boxed();
+
+		let subscriptions = self.subscriptions.clone();
+		let fut = async move {
+			while let Some(event) = events.next().await {
+				if let Some(hash) = event.pinned() {
+					if !subscriptions.pin(&id, *hash) {
+						let _ = sink.send(&FollowEvent::<Block::Hash>::Stop);
+						break;
+					}
+				}
+				if let FollowEvent::Finalized(finalized) = &event {
+					subscriptions.unpin_pruned(&id, &finalized.pruned_block_hashes);
+				}
+				// `false` once the subscription is closed.
+				if !matches!(sink.send(&event), Ok(true)) {
+					break;
+				}
+			}
+			subscriptions.remove(&id);
+		};
+		self.kate
+			.executor
+			.spawn("kate-rpc-chain-head-follow", Some("rpc"), fut.boxed());
+
+		Ok(())
+	}
+
+	async fn unpin(
+		&self,
+		follow_subscription: String,
+		hashes: Vec<HashOf<Block>>,
+	) -> RpcResult<()> {
+		self.subscriptions.unpin(&follow_subscription, &hashes)
+	}
+
+	async fn query_cells(
+		&self,
+		follow_subscription: String,
+		hash: HashOf<Block>,
+		cells: Cells,
+	) -> RpcResult<MethodResponse> {
+		// The operation runs outside of the scope of the caller, so it is charged here.
+		self.kate.charge(KateCall::ChainHeadCells(cells.len()))?;
+		let kate = self.kate.clone();
+		// Pinned blocks may not be finalized yet.
+		let options = QueryOptions {
+			finality: Some(Finality::Any),
+		};
+		let operation = async move { kate.query_proof_v2(cells, Some(hash), Some(options)).await };
+		self.spawn_operation(
+			follow_subscription,
+			&hash,
+			operation,
+			|operation_id, value| FollowEvent::OperationCellsDone {
+				operation_id,
+				value,
+			},
+		)
+	}
+
+	async fn query_rows(
+		&self,
+		follow_subscription: String,
+		hash: HashOf<Block>,
+		rows: Rows,
+	) -> RpcResult<MethodResponse> {
+		self.kate.charge(KateCall::ChainHeadRows(rows.len()))?;
+		let kate = self.kate.clone();
+		let options = QueryOptions {
+			finality: Some(Finality::Any),
+		};
+		let operation = async move { kate.query_rows_v2(rows, Some(hash), Some(options)).await };
+		self.spawn_operation(
+			follow_subscription,
+			&hash,
+			operation,
+			|operation_id, value| FollowEvent::OperationRowsDone {
+				operation_id,
+				value,
+			},
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use sp_blockchain::CachedHeaderMetadata;
+	use sp_core::H256;
+	use sp_runtime::testing::{Block as RawBlock, ExtrinsicWrapper, Header as TestHeader};
+
+	use super::*;
+
+	type TestBlock = RawBlock<ExtrinsicWrapper<u64>>;
+
+	/// Backend pin counts of blocks.
+	#[derive(Default)]
+	struct TestPins {
+		pins: Mutex<HashMap<u32, usize>>,
+	}
+
+	impl TestPins {
+		fn count(&self, hash: u32) -> usize {
+			let pins = self.pins.lock().unwrap();
+			pins.get(&hash).copied().unwrap_or_default()
+		}
+
+		fn total(&self) -> usize {
+			self.pins.lock().unwrap().values().sum()
+		}
+	}
+
+	/// Blocks from this hash have been pruned, they cannot be pinned.
+	const PRUNED: u32 = 1_000_000;
+
+	impl PinBlocks<u32> for TestPins {
+		fn pin(&self, hash: &u32) -> sp_blockchain::Result<()> {
+			if *hash >= PRUNED {
+				return Err(sp_blockchain::Error::UnknownBlock(hash.to_string()));
+			}
+			*self.pins.lock().unwrap().entry(*hash).or_default() += 1;
+			Ok(())
+		}
+
+		fn unpin(&self, hash: &u32) {
+			let mut pins = self.pins.lock().unwrap();
+			let count = pins.get_mut(hash).expect("Unpinned blocks are pinned");
+			*count -= 1;
+			if *count == 0 {
+				pins.remove(hash);
+			}
+		}
+	}
+
+	fn subscriptions(ids: &[&str]) -> (Arc<TestPins>, FollowSubscriptions<u32>) {
+		let pins = Arc::new(TestPins::default());
+		let subscriptions = FollowSubscriptions::new(pins.clone());
+		for id in ids {
+			assert!(subscriptions.insert(id.to_string(), mpsc::unbounded().0));
+		}
+		(pins, subscriptions)
+	}
+
+	/// Headers of a block tree.
+	#[derive(Default)]
+	struct TestChain(Mutex<HashMap<H256, TestHeader>>);
+
+	impl TestChain {
+		/// Adds a child of `parent` on the fork `fork`, and returns its header.
+		fn push(&self, parent: Option<&TestHeader>, fork: u8) -> TestHeader {
+			let header = TestHeader::new(
+				parent.map_or(0, |parent| parent.number + 1),
+				H256::zero(),
+				H256::repeat_byte(fork),
+				parent.map_or_else(H256::zero, |parent| parent.hash()),
+				Default::default(),
+			);
+			self.0.lock().unwrap().insert(header.hash(), header.clone());
+			header
+		}
+	}
+
+	impl HeaderMetadata<TestBlock> for TestChain {
+		type Error = sp_blockchain::Error;
+
+		fn header_metadata(
+			&self,
+			hash: H256,
+		) -> sp_blockchain::Result<CachedHeaderMetadata<TestBlock>> {
+			let headers = self.0.lock().unwrap();
+			let header = headers
+				.get(&hash)
+				.ok_or_else(|| sp_blockchain::Error::UnknownBlock(hash.to_string()))?;
+			Ok(CachedHeaderMetadata::from(header))
+		}
+
+		fn insert_header_metadata(&self, _: H256, _: CachedHeaderMetadata<TestBlock>) {}
+
+		fn remove_header_metadata(&self, _: H256) {}
+	}
+
+	#[test]
+	fn every_block_of_pruned_forks_is_reported() {
+		let chain = TestChain::default();
+		let genesis = chain.push(None, 0);
+		let a1 = chain.push(Some(&genesis), 0);
+		let a2 = chain.push(Some(&a1), 0);
+		// Two stale heads share `b1`.
+		let b1 = chain.push(Some(&genesis), 1);
+		let b2 = chain.push(Some(&b1), 1);
+		let b3 = chain.push(Some(&b1), 2);
+		let unknown = H256::repeat_byte(9);
+
+		let pruned =
+			pruned_blocks::<TestBlock, _>(&chain, a2.hash(), &[b2.hash(), b3.hash(), unknown]);
+
+		assert_eq!(pruned, vec![b1.hash(), b2.hash(), b3.hash(), unknown]);
+	}
+
+	#[test]
+	fn subscriptions_are_limited() {
+		let (_, subscriptions) = subscriptions(&[]);
+		for id in 0..MAX_FOLLOW_SUBSCRIPTIONS {
+			assert!(subscriptions.insert(id.to_string(), mpsc::unbounded().0));
+		}
+		assert!(!subscriptions.insert("extra".to_string(), mpsc::unbounded().0));
+
+		subscriptions.remove("0");
+		assert!(subscriptions.insert("extra".to_string(), mpsc::unbounded().0));
+	}
+
+	#[test]
+	fn blocks_are_pinned_once_per_subscription() {
+		let (pins, subscriptions) = subscriptions(&["a", "b"]);
+
+		assert!(subscriptions.pin("a", 1));
+		assert!(subscriptions.pin("a", 1));
+		assert_eq!(pins.count(1), 1);
+		assert!(subscriptions.pin("b", 1));
+		assert_eq!(pins.count(1), 2);
+
+		subscriptions.unpin("a", &[1]).unwrap();
+		assert_eq!(pins.count(1), 1);
+		assert!(subscriptions.unpin("a", &[1]).is_err());
+		subscriptions.unpin("b", &[1]).unwrap();
+		assert_eq!(pins.total(), 0);
+	}
+
+	#[test]
+	fn unknown_subscriptions_pin_nothing() {
+		let (pins, subscriptions) = subscriptions(&["a"]);
+
+		assert!(!subscriptions.pin("b", 1));
+		assert!(subscriptions.unpin("b", &[1]).is_err());
+		assert_eq!(pins.total(), 0);
+	}
+
+	#[test]
+	fn pruned_blocks_cannot_be_pinned() {
+		let (pins, subscriptions) = subscriptions(&["a"]);
+
+		assert!(!subscriptions.pin("a", PRUNED));
+		assert_eq!(pins.total(), 0);
+		assert!(subscriptions.start_operation("a", &PRUNED).is_err());
+	}
+
+	#[test]
+	fn unpin_is_all_or_nothing() {
+		let (pins, subscriptions) = subscriptions(&["a"]);
+		assert!(subscriptions.pin("a", 1));
+		assert!(subscriptions.pin("a", 2));
+
+		assert!(subscriptions.unpin("a", &[1, 3]).is_err());
+		assert_eq!((pins.count(1), pins.count(2)), (1, 1));
+
+		subscriptions.unpin("a", &[1, 2]).unwrap();
+		assert_eq!(pins.total(), 0);
+	}
+
+	#[test]
+	fn pinned_blocks_are_limited() {
+		let (pins, subscriptions) = subscriptions(&["a", "b"]);
+		let max = MAX_PINNED_BLOCKS as u32;
+		for hash in 0..max {
+			assert!(subscriptions.pin("a", hash));
+		}
+		// Pinned blocks are reported again without reaching the limit.
+		assert!(subscriptions.pin("a", 0));
+		assert!(!subscriptions.pin("a", max));
+		assert_eq!(pins.total(), MAX_PINNED_BLOCKS);
+
+		// The limit is per subscription.
+		assert!(subscriptions.pin("b", max));
+
+		subscriptions.unpin("a", &[0]).unwrap();
+		assert!(subscriptions.pin("a", max));
+	}
+
+	#[test]
+	fn pruned_blocks_are_unpinned() {
+		let (pins, subscriptions) = subscriptions(&["a", "b"]);
+		assert!(subscriptions.pin("a", 1));
+		assert!(subscriptions.pin("a", 2));
+		assert!(subscriptions.pin("b", 1));
+
+		subscriptions.unpin_pruned("a", &[1, 3]);
+		assert_eq!((pins.count(1), pins.count(2)), (1, 1));
+		assert!(subscriptions.unpin("a", &[1]).is_err());
+		assert!(subscriptions.start_operation("a", &1).is_err());
+		assert!(subscriptions.start_operation("b", &1).unwrap().is_some());
+	}
+
+	#[test]
+	fn removed_subscriptions_unpin_their_blocks() {
+		let (pins, subscriptions) = subscriptions(&["a", "b"]);
+		assert!(subscriptions.pin("a", 1));
+		assert!(subscriptions.pin("a", 2));
+		assert!(subscriptions.pin("b", 2));
+
+		subscriptions.remove("a");
+		assert_eq!((pins.count(1), pins.count(2)), (0, 1));
+		assert!(subscriptions.start_operation("a", &2).is_err());
+
+		subscriptions.remove("b");
+		assert_eq!(pins.total(), 0);
+	}
+
+	#[test]
+	fn operations_need_pinned_blocks() {
+		let (_, subscriptions) = subscriptions(&["a", "b"]);
+		assert!(subscriptions.pin("a", 1));
+
+		assert!(subscriptions.start_operation("a", &2).is_err());
+		assert!(subscriptions.start_operation("b", &1).is_err());
+		assert!(subscriptions.start_operation("c", &1).is_err());
+		assert!(subscriptions.start_operation("a", &1).unwrap().is_some());
+	}
+
+	#[test]
+	fn operations_are_limited() {
+		let (_, subscriptions) = subscriptions(&["a", "b"]);
+		assert!(subscriptions.pin("a", 1));
+		assert!(subscriptions.pin("b", 1));
+
+		let mut operation_ids = HashSet::new();
+		for _ in 0..MAX_OPERATIONS {
+			let (operation_id, _) = subscriptions.start_operation("a", &1).unwrap().unwrap();
+			operation_ids.insert(operation_id);
+		}
+		assert_eq!(operation_ids.len(), MAX_OPERATIONS);
+		assert!(subscriptions.start_operation("a", &1).unwrap().is_none());
+
+		// The limit is per subscription.
+		assert!(subscriptions.start_operation("b", &1).unwrap().is_some());
+
+		subscriptions.finish_operation("a");
+		let (operation_id, _) = subscriptions.start_operation("a", &1).unwrap().unwrap();
+		assert!(!operation_ids.contains(&operation_id));
+		assert!(subscriptions.start_operation("a", &1).unwrap().is_none());
+	}
+}
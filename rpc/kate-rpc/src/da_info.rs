@@ -0,0 +1,99 @@
+use avail_core::{header::HeaderExtension, AppId};
+use kate_verifier::{COMMITMENT_SIZE, ROW_EXTENSION};
+use serde::{Deserialize, Serialize};
+use sp_core::H256;
+
+/// Dimensions of a data grid, in cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GridDimensions {
+	pub rows: u16,
+	pub cols: u16,
+}
+
+/// Chunks of the original grid which contain the data of one application.
+///
+/// `start` is inclusive and `end` is exclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLookupEntry {
+	pub app_id: AppId,
+	pub start: u32,
+	pub end: u32,
+}
+
+/// Decoded data availability metadata of a block header, returned by `kate_blockDaInfo`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockDaInfo<Hash> {
+	pub block_hash: Hash,
+	/// Version of the header extension.
+	pub version: u8,
+	pub dimensions: GridDimensions,
+	/// Dimensions after the row extension.
+	pub extended_dimensions: GridDimensions,
+	pub app_lookup: Vec<AppLookupEntry>,
+	/// Commitment of each row of the extended grid, 48 bytes each.
+	pub commitments: Vec<Vec<u8>>,
+	pub data_root: H256,
+	/// Ratio of the original grid cells which are padding, from 0 to 1.
+	pub padding_ratio: f64,
+}
+
+impl<Hash> BlockDaInfo<Hash> {
+	pub fn new(block_hash: Hash, extension: &HeaderExtension) -> Self {
+		let (version, rows, cols, commitment, data_root, lookup) = match extension {
+			HeaderExtension::V1(ext) => (
+				1,
+				ext.commitment.rows,
+				ext.commitment.cols,
+				&ext.commitment.commitment,
+				ext.commitment.data_root,
+				&ext.app_lookup,
+			),
+			HeaderExtension::V2(ext) => (
+				2,
+				ext.commitment.rows,
+				ext.commitment.cols,
+				&ext.commitment.commitment,
+				ext.commitment.data_root,
+				&ext.app_lookup,
+			),
+		};
+
+		let app_lookup = lookup
+			.index()
+			.iter()
+			.map(|(app_id, range)| AppLookupEntry {
+				app_id: *app_id,
+				start: range.start,
+				end: range.end,
+			})
+			.collect::<Vec<_>>();
+
+		let cells = u32::from(rows) * u32::from(cols);
+		let data_cells = lookup.len().min(cells);
+		let padding_ratio = if cells == 0 {
+			0.0
+		} else {
+			f64::from(cells - data_cells) / f64::from(cells)
+		};
+
+		Self {
+			block_hash,
+			version,
+			dimensions: GridDimensions { rows, cols },
+			extended_dimensions: GridDimensions {
+				rows: rows.saturating_mul(ROW_EXTENSION),
+				cols,
+			},
+			app_lookup,
+			commitments: commitment
+				.chunks(COMMITMENT_SIZE)
+				.map(<[u8]>::to_vec)
+				.collect(),
+			data_root,
+			padding_ratio,
+		}
+	}
+}
@@ -0,0 +1,568 @@
+//! On-disk tier of the evaluation grid cache.
+//!
+//! Extended evaluation grids of finalized blocks are persisted under a dedicated directory, so a
+//! restarted node does not need to rebuild them on the first RPC burst. Polynomial grids are
+//! derived from the persisted evaluation grid, which is cheap compared to the grid construction.
+//!
+//! Each entry is stored in its own file, named after the block hash, which contains a
+//! `blake2_256` checksum followed by the SCALE encoded [`GridEntry`]. Corrupted or mismatching
+//! entries are removed on read. The total size of the entries is bounded and the oldest entries
+//! are evicted first.
+//!
+//! Entries of blocks which are not finalized yet are pruned by [`GridDiskCache::prune`] once their
+//! block number is finalized, if the block did not become canonical.
+use std::{
+	collections::{BTreeMap, VecDeque},
+	fs,
+	io::Read,
+	path::{Path, PathBuf},
+	sync::Mutex,
+	time::SystemTime,
+};
+
+use avail_core::DataLookup;
+use codec::{Decode, Encode};
+use kate::gridgen::{AsBytes, EvaluationGrid};
+use nalgebra::DMatrix;
+use sp_core::{blake2_256, hexdisplay::HexDisplay};
+
+const LOG_TARGET: &str = "avail::kate::rpc::disk_cache";
+
+/// Version of the on-disk format.
+const FORMAT_VERSION: u8 = 1;
+/// Extension of the entry files.
+const ENTRY_EXTENSION: &str = "grid";
+/// Size in bytes of the checksum prefix.
+const CHECKSUM_SIZE: usize = 32;
+/// Bytes read to decode the [`EntryHeader`] of an entry.
+const HEADER_READ_SIZE: u64 = 128;
+
+#[derive(Encode, Decode)]
+struct GridEntry {
+	version: u8,
+	block_hash: Vec<u8>,
+	block_number: u32,
+	lookup: DataLookup,
+	rows: u16,
+	cols: u16,
+	scalars: Vec<[u8; 32]>,
+}
+
+/// Leading fields of a [`GridEntry`], decoded without reading the grid.
+#[derive(Decode)]
+struct EntryHeader {
+	version: u8,
+	block_hash: Vec<u8>,
+	block_number: u32,
+}
+
+#[derive(Default)]
+struct Index {
+	/// Size in bytes of each stored entry.
+	entries: BTreeMap<Vec<u8>, u64>,
+	/// Entries in insertion order, the front is evicted first.
+	order: VecDeque<Vec<u8>>,
+	/// Total size in bytes of the stored entries.
+	size: u64,
+	/// Block number of the entries which are not known to be canonical yet.
+	unchecked: BTreeMap<Vec<u8>, u32>,
+}
+
+/// Size-bounded persistent storage of extended evaluation grids, keyed by block hash.
+pub struct GridDiskCache {
+	path: PathBuf,
+	max_size: u64,
+	index: Mutex<Index>,
+}
+
+impl GridDiskCache {
+	/// Opens (or creates) the cache at `path`, limited to `max_size` MiB.
+	///
+	/// Existing entries are indexed from oldest to newest, and evicted if they exceed the
+	/// limit.
+	pub fn open(path: &Path, max_size: u64) -> std::io::Result<Self> {
+		fs::create_dir_all(path)?;
+
+		let mut entries = fs::read_dir(path)?
+			.filter_map(Result::ok)
+			.filter(|entry| {
+				entry.path().extension().and_then(|ext| ext.to_str()) == Some(ENTRY_EXTENSION)
+			})
+			.filter_map(|entry| {
+				let meta = entry.metadata().ok()?;
+				let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+				let stem = entry.path().file_stem()?.to_str()?.to_owned();
+				let hash = decode_hex(&stem)?;
+				let Some(block_number) = read_block_number(&entry.path(), &hash) else {
+					let _ = fs::remove_file(entry.path());
+					return None;
+				};
+				Some((modified, hash, meta.len(), block_number))
+			})
+			.collect::<Vec<_>>();
+		entries.sort();
+
+		let cache = Self {
+			path: path.to_path_buf(),
+			max_size: max_size * 1024 * 1024,
+			index: Mutex::new(Index::default()),
+		};
+
+		{
+			let mut index = cache.index.lock().expect("Poisoned lock");
+			for (_, hash, size, block_number) in entries {
+				index.unchecked.insert(hash.clone(), block_number);
+				index.entries.insert(hash.clone(), size);
+				index.order.push_back(hash);
+				index.size += size;
+			}
+			cache.evict(&mut index);
+			log::info!(
+				target: LOG_TARGET,
+				"Opened grid disk cache at {:?} with {} entries ({} bytes)",
+				cache.path,
+				index.entries.len(),
+				index.size
+			);
+		}
+
+		Ok(cache)
+	}
+
+	/// Loads the evaluation grid of `block_hash`.
+	///
+	/// `is_canonical` is called with the stored block number, and the entry is removed if the
+	/// block is no longer part of the canonical chain.
+	pub fn get(
+		&self,
+		block_hash: &[u8],
+		is_canonical: impl FnOnce(u32) -> bool,
+	) -> Option<EvaluationGrid> {
+		if !self
+			.index
+			.lock()
+			.expect("Poisoned lock")
+			.entries
+			.contains_key(block_hash)
+		{
+			return None;
+		}
+
+		let entry = match self.read(block_hash) {
+			Ok(entry) => entry,
+			Err(reason) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Invalid grid entry for block 0x{}: {reason}",
+					HexDisplay::from(&block_hash)
+				);
+				self.remove(block_hash);
+				return None;
+			},
+		};
+
+		if !is_canonical(entry.block_number) {
+			log::debug!(
+				target: LOG_TARGET,
+				"Removing grid entry of non canonical block 0x{}",
+				HexDisplay::from(&block_hash)
+			);
+			self.remove(block_hash);
+			return None;
+		}
+
+		let grid = decode_grid(entry);
+		if grid.is_none() {
+			self.remove(block_hash);
+		}
+		grid
+	}
+
+	/// Persists the evaluation grid of `block_hash`, evicting old entries if needed.
+	pub fn insert(&self, block_hash: &[u8], block_number: u32, grid: &EvaluationGrid) {
+		let Some(entry) = encode_grid(block_hash, block_number, grid) else {
+			log::warn!(
+				target: LOG_TARGET,
+				"Grid of block 0x{} cannot be serialized",
+				HexDisplay::from(&block_hash)
+			);
+			return;
+		};
+
+		let payload = entry.encode();
+		let mut content = Vec::with_capacity(CHECKSUM_SIZE + payload.len());
+		content.extend_from_slice(&blake2_256(&payload));
+		content.extend_from_slice(&payload);
+		let size = content.len() as u64;
+
+		if size > self.max_size {
+			return;
+		}
+		if let Err(e) = fs::write(self.entry_path(block_hash), content) {
+			log::warn!(
+				target: LOG_TARGET,
+				"Grid of block 0x{} cannot be persisted: {e:?}",
+				HexDisplay::from(&block_hash)
+			);
+			return;
+		}
+
+		let mut index = self.index.lock().expect("Poisoned lock");
+		if let Some(old_size) = index.entries.insert(block_hash.to_vec(), size) {
+			index.size = index.size.saturating_sub(old_size);
+			index.order.retain(|hash| hash != block_hash);
+		}
+		index.order.push_back(block_hash.to_vec());
+		index.unchecked.insert(block_hash.to_vec(), block_number);
+		index.size += size;
+		self.evict(&mut index);
+	}
+
+	/// Removes the entry of `block_hash`, if any.
+	pub fn remove(&self, block_hash: &[u8]) {
+		let mut index = self.index.lock().expect("Poisoned lock");
+		self.remove_entry(&mut index, block_hash);
+	}
+
+	/// Removes the entries of the blocks up to `finalized_number` which are not canonical, such as
+	/// the blocks of the forks pruned by the finalization.
+	///
+	/// `canonical_hash` returns the hash of the canonical block of a number. Entries are checked
+	/// once, the canonical ones are kept until they are evicted.
+	pub fn prune(&self, finalized_number: u32, canonical_hash: impl Fn(u32) -> Option<Vec<u8>>) {
+		let mut index = self.index.lock().expect("Poisoned lock");
+		let settled = index
+			.unchecked
+			.iter()
+			.filter(|(_, block_number)| **block_number <= finalized_number)
+			.map(|(block_hash, block_number)| (block_hash.clone(), *block_number))
+			.collect::<Vec<_>>();
+
+		for (block_hash, block_number) in settled {
+			index.unchecked.remove(&block_hash);
+			if canonical_hash(block_number).as_ref() != Some(&block_hash) {
+				log::debug!(
+					target: LOG_TARGET,
+					"Pruning grid entry of non canonical block 0x{}",
+					HexDisplay::from(&block_hash)
+				);
+				self.remove_entry(&mut index, &block_hash);
+			}
+		}
+	}
+
+	/// Removes every entry of the cache directory.
+	pub fn clear(&self) {
+		let mut index = self.index.lock().expect("Poisoned lock");
+		*index = Index::default();
+
+		let Ok(entries) = fs::read_dir(&self.path) else {
+			return;
+		};
+		for entry in entries.filter_map(Result::ok) {
+			let path = entry.path();
+			if path.extension().and_then(|ext| ext.to_str()) == Some(ENTRY_EXTENSION) {
+				let _ = fs::remove_file(path);
+			}
+		}
+	}
+
+	fn read(&self, block_hash: &[u8]) -> Result<GridEntry, &'static str> {
+		let content = fs::read(self.entry_path(block_hash)).map_err(|_| "unreadable file")?;
+		if content.len() < CHECKSUM_SIZE {
+			return Err("truncated file");
+		}
+
+		let (checksum, payload) = content.split_at(CHECKSUM_SIZE);
+		if checksum != blake2_256(payload) {
+			return Err("checksum mismatch");
+		}
+
+		let entry = GridEntry::decode(&mut &payload[..]).map_err(|_| "undecodable entry")?;
+		if entry.version != FORMAT_VERSION {
+			return Err("unsupported version");
+		}
+		if entry.block_hash != block_hash {
+			return Err("block hash mismatch");
+		}
+
+		Ok(entry)
+	}
+
+	fn evict(&self, index: &mut Index) {
+		while index.size > self.max_size {
+			let Some(oldest) = index.order.front().cloned() else {
+				break;
+			};
+			self.remove_entry(index, &oldest);
+		}
+	}
+
+	fn remove_entry(&self, index: &mut Index, block_hash: &[u8]) {
+		index.unchecked.remove(block_hash);
+		if let Some(size) = index.entries.remove(block_hash) {
+			index.size = index.size.saturating_sub(size);
+			index.order.retain(|hash| hash != block_hash);
+		}
+		let _ = fs::remove_file(self.entry_path(block_hash));
+	}
+
+	fn entry_path(&self, block_hash: &[u8]) -> PathBuf {
+		self.path.join(format!(
+			"{}.{ENTRY_EXTENSION}",
+			HexDisplay::from(&block_hash)
+		))
+	}
+}
+
+/// Reads the block number of the entry stored at `path`, checking that it belongs to
+/// `block_hash`.
+fn read_block_number(path: &Path, block_hash: &[u8]) -> Option<u32> {
+	let mut content = Vec::new();
+	fs::File::open(path)
+		.ok()?
+		.take(HEADER_READ_SIZE)
+		.read_to_end(&mut content)
+		.ok()?;
+
+	let header = EntryHeader::decode(&mut content.get(CHECKSUM_SIZE..)?).ok()?;
+	(header.version == FORMAT_VERSION && header.block_hash == block_hash)
+		.then_some(header.block_number)
+}
+
+fn encode_grid(block_hash: &[u8], block_number: u32, grid: &EvaluationGrid) -> Option<GridEntry> {
+	let dims = grid.dims();
+	let scalars = (0..dims.height())
+		.map(|y| grid.row(y))
+		.collect::<Option<Vec<_>>>()?
+		.into_iter()
+		.flatten()
+		.map(|scalar| scalar.to_bytes().ok())
+		.collect::<Option<Vec<_>>>()?;
+
+	Some(GridEntry {
+		version: FORMAT_VERSION,
+		block_hash: block_hash.to_vec(),
+		block_number,
+		lookup: grid.lookup().clone(),
+		rows: dims.rows().get(),
+		cols: dims.cols().get(),
+		scalars,
+	})
+}
+
+fn decode_grid(entry: GridEntry) -> Option<EvaluationGrid> {
+	let (rows, cols) = (usize::from(entry.rows), usize::from(entry.cols));
+	if entry.scalars.len() != rows.checked_mul(cols)? {
+		return None;
+	}
+
+	let scalars = entry
+		.scalars
+		.iter()
+		.map(|raw| AsBytes::from_bytes(raw).ok())
+		.collect::<Option<Vec<_>>>()?;
+	let evals = DMatrix::from_row_iterator(rows, cols, scalars);
+
+	Some(EvaluationGrid::new(entry.lookup, evals))
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+	if hex.len() % 2 != 0 {
+		return None;
+	}
+	(0..hex.len())
+		.step_by(2)
+		.map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use avail_core::{AppExtrinsic, AppId};
+
+	use super::*;
+
+	const BLOCK_A: [u8; 32] = [1u8; 32];
+	const BLOCK_B: [u8; 32] = [2u8; 32];
+	const BLOCK_C: [u8; 32] = [3u8; 32];
+
+	fn grid(data: &[u8]) -> EvaluationGrid {
+		let extrinsics = vec![AppExtrinsic::new(AppId(1), data.to_vec())];
+		EvaluationGrid::from_extrinsics(extrinsics, 4, 256, 256, [0u8; 32]).unwrap()
+	}
+
+	fn scalars(grid: &EvaluationGrid) -> Vec<[u8; 32]> {
+		encode_grid(&[], 0, grid).unwrap().scalars
+	}
+
+	fn entry_size(cache: &GridDiskCache, block_hash: &[u8]) -> u64 {
+		cache.index.lock().unwrap().entries[block_hash]
+	}
+
+	#[test]
+	fn inserted_grid_is_loaded_back() {
+		let dir = tempfile::tempdir().unwrap();
+		let cache = GridDiskCache::open(dir.path(), 16).unwrap();
+		let grid = grid(b"ExampleData");
+
+		cache.insert(&BLOCK_A, 1, &grid);
+		let loaded = cache.get(&BLOCK_A, |_| true).unwrap();
+
+		assert_eq!(loaded.dims(), grid.dims());
+		assert_eq!(loaded.lookup(), grid.lookup());
+		assert_eq!(scalars(&loaded), scalars(&grid));
+		assert!(cache.get(&BLOCK_B, |_| true).is_none());
+	}
+
+	#[test]
+	fn entries_survive_reopening() {
+		let dir = tempfile::tempdir().unwrap();
+		let grid = grid(b"ExampleData");
+		GridDiskCache::open(dir.path(), 16)
+			.unwrap()
+			.insert(&BLOCK_A, 1, &grid);
+
+		let cache = GridDiskCache::open(dir.path(), 16).unwrap();
+		let loaded = cache.get(&BLOCK_A, |_| true).unwrap();
+
+		assert_eq!(scalars(&loaded), scalars(&grid));
+	}
+
+	#[test]
+	fn corrupted_entry_is_removed() {
+		let dir = tempfile::tempdir().unwrap();
+		let cache = GridDiskCache::open(dir.path(), 16).unwrap();
+		cache.insert(&BLOCK_A, 1, &grid(b"ExampleData"));
+
+		let path = cache.entry_path(&BLOCK_A);
+		let mut content = fs::read(&path).unwrap();
+		let last = content.len() - 1;
+		content[last] ^= 0xff;
+		fs::write(&path, content).unwrap();
+
+		assert!(cache.get(&BLOCK_A, |_| true).is_none());
+		assert!(!path.exists());
+		assert_eq!(cache.index.lock().unwrap().size, 0);
+	}
+
+	#[test]
+	fn entry_of_another_block_is_rejected() {
+		let dir = tempfile::tempdir().unwrap();
+		let cache = GridDiskCache::open(dir.path(), 16).unwrap();
+		cache.insert(&BLOCK_A, 1, &grid(b"ExampleData"));
+		cache.insert(&BLOCK_B, 2, &grid(b"OtherData"));
+		fs::copy(cache.entry_path(&BLOCK_A), cache.entry_path(&BLOCK_B)).unwrap();
+
+		assert!(cache.get(&BLOCK_B, |_| true).is_none());
+		assert!(cache.get(&BLOCK_A, |_| true).is_some());
+	}
+
+	#[test]
+	fn non_canonical_entry_is_invalidated() {
+		let dir = tempfile::tempdir().unwrap();
+		let cache = GridDiskCache::open(dir.path(), 16).unwrap();
+		cache.insert(&BLOCK_A, 7, &grid(b"ExampleData"));
+
+		let mut checked_number = None;
+		let loaded = cache.get(&BLOCK_A, |number| {
+			checked_number = Some(number);
+			false
+		});
+
+		assert!(loaded.is_none());
+		assert_eq!(checked_number, Some(7));
+		assert!(!cache.entry_path(&BLOCK_A).exists());
+		assert!(cache.get(&BLOCK_A, |_| true).is_none());
+	}
+
+	#[test]
+	fn oldest_entries_are_evicted_first() {
+		let dir = tempfile::tempdir().unwrap();
+		let mut cache = GridDiskCache::open(dir.path(), 16).unwrap();
+		let grid = grid(b"ExampleData");
+		cache.insert(&BLOCK_A, 1, &grid);
+		let size = entry_size(&cache, &BLOCK_A);
+		cache.max_size = 2 * size;
+
+		cache.insert(&BLOCK_B, 2, &grid);
+		cache.insert(&BLOCK_C, 3, &grid);
+
+		assert!(cache.get(&BLOCK_A, |_| true).is_none());
+		assert!(!cache.entry_path(&BLOCK_A).exists());
+		assert!(cache.get(&BLOCK_B, |_| true).is_some());
+		assert!(cache.get(&BLOCK_C, |_| true).is_some());
+		assert_eq!(cache.index.lock().unwrap().size, 2 * size);
+	}
+
+	#[test]
+	fn reinsertion_refreshes_eviction_order() {
+		let dir = tempfile::tempdir().unwrap();
+		let mut cache = GridDiskCache::open(dir.path(), 16).unwrap();
+		let grid = grid(b"ExampleData");
+		cache.insert(&BLOCK_A, 1, &grid);
+		cache.max_size = 2 * entry_size(&cache, &BLOCK_A);
+
+		cache.insert(&BLOCK_B, 2, &grid);
+		cache.insert(&BLOCK_A, 1, &grid);
+		cache.insert(&BLOCK_C, 3, &grid);
+
+		assert!(cache.get(&BLOCK_A, |_| true).is_some());
+		assert!(cache.get(&BLOCK_B, |_| true).is_none());
+		assert!(cache.get(&BLOCK_C, |_| true).is_some());
+	}
+
+	#[test]
+	fn non_canonical_entries_are_pruned_once_finalized() {
+		let dir = tempfile::tempdir().unwrap();
+		let cache = GridDiskCache::open(dir.path(), 16).unwrap();
+		let grid = grid(b"ExampleData");
+		cache.insert(&BLOCK_A, 1, &grid);
+		cache.insert(&BLOCK_B, 2, &grid);
+		cache.insert(&BLOCK_C, 3, &grid);
+		// `BLOCK_B` is on a fork, `BLOCK_C` is not finalized yet.
+		let canonical = |number| match number {
+			1 => Some(BLOCK_A.to_vec()),
+			2 => Some([9u8; 32].to_vec()),
+			_ => None,
+		};
+
+		cache.prune(2, canonical);
+
+		assert!(cache.entry_path(&BLOCK_A).exists());
+		assert!(!cache.entry_path(&BLOCK_B).exists());
+		assert!(cache.entry_path(&BLOCK_C).exists());
+		assert_eq!(cache.index.lock().unwrap().unchecked.len(), 1);
+
+		// Entries indexed when the cache is reopened are checked again.
+		let cache = GridDiskCache::open(dir.path(), 16).unwrap();
+		cache.prune(3, canonical);
+
+		assert!(cache.get(&BLOCK_A, |_| true).is_some());
+		assert!(cache.get(&BLOCK_C, |_| true).is_none());
+		assert!(cache.index.lock().unwrap().unchecked.is_empty());
+	}
+
+	#[test]
+	fn clear_removes_every_entry() {
+		let dir = tempfile::tempdir().unwrap();
+		let cache = GridDiskCache::open(dir.path(), 16).unwrap();
+		let grid = grid(b"ExampleData");
+		cache.insert(&BLOCK_A, 1, &grid);
+		cache.insert(&BLOCK_B, 2, &grid);
+
+		cache.clear();
+
+		assert!(cache.get(&BLOCK_A, |_| true).is_none());
+		assert!(cache.get(&BLOCK_B, |_| true).is_none());
+		assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+		assert_eq!(cache.index.lock().unwrap().size, 0);
+	}
+
+	#[test]
+	fn hex_file_names_are_decoded() {
+		assert_eq!(decode_hex("00ff10"), Some(vec![0x00, 0xff, 0x10]));
+		assert_eq!(decode_hex("0"), None);
+		assert_eq!(decode_hex("zz"), None);
+	}
+}
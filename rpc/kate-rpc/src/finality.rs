@@ -0,0 +1,67 @@
+//! Finality options of the Kate RPC queries.
+//!
+//! Every query takes an optional `{ "finality": "finalized" | "best" | "any" }` object after the
+//! block hash. Without it, each method keeps its historical behaviour: grid queries (rows,
+//! columns, application data and proofs) require a finalized block, the other ones accept any
+//! block.
+//!
+//! It also provides the stream of finalized blocks shared by the Kate subscriptions and the node
+//! tasks of the Kate RPC.
+use futures::{stream, Stream, StreamExt};
+use sc_client_api::BlockchainEvents;
+use serde::{Deserialize, Serialize};
+use sp_runtime::traits::Block as BlockT;
+
+/// Finality required from the queried block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Finality {
+	/// The block must be finalized. Queries without a block use the finalized head.
+	Finalized,
+	/// The block must be on the best chain. Queries without a block use the best block.
+	Best,
+	/// Any imported block. Queries without a block use the best block.
+	Any,
+}
+
+/// Options common to every Kate RPC query.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryOptions {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub finality: Option<Finality>,
+}
+
+impl QueryOptions {
+	/// Options which require a finalized block.
+	pub const FINALIZED: Self = Self {
+		finality: Some(Finality::Finalized),
+	};
+
+	/// Finality requested by `options`, or `default` if none is.
+	pub fn finality_or(options: Option<Self>, default: Finality) -> Finality {
+		options.and_then(|o| o.finality).unwrap_or(default)
+	}
+}
+
+/// Every block finalized after the call, in order.
+///
+/// A finality notification can finalize several blocks at once, so the implicitly finalized ones
+/// (`tree_route`) come before the new finalized head.
+pub fn finalized_blocks<Block, Client>(client: &Client) -> impl Stream<Item = Block::Hash>
+where
+	Block: BlockT,
+	Client: BlockchainEvents<Block>,
+{
+	client
+		.finality_notification_stream()
+		.flat_map(|notification| {
+			let hashes = notification
+				.tree_route
+				.iter()
+				.cloned()
+				.chain(std::iter::once(notification.hash))
+				.collect::<Vec<_>>();
+			stream::iter(hashes)
+		})
+}
@@ -0,0 +1,538 @@
+//! Binary HTTP gateway of the Kate RPC.
+//!
+//! JSON-RPC responses encode every byte as a JSON number, so large rows and blobs take roughly
+//! three times their size on the wire. The gateway serves the same data as raw bytes or SCALE,
+//! using the same [`Kate`] instance and caches as the RPC server:
+//!
+//! - `GET /v1/blocks/{block_hash}/rows?rows=0,1`: rows of the extended grid, as
+//!   `kate_queryRows`.
+//! - `GET /v1/blocks/{block_hash}/apps/{app_id}/data`: rows of an application, as
+//!   `kate_queryAppData`.
+//! - `GET /v1/blocks/{block_hash}/proofs?cells=0:1,1:7`: proofs of `row:col` cells, as
+//!   `kate_queryProof`.
+//! - `GET /v1/blobs/{data_hash}?block={block_hash}`: submitted blob, as `kate_getBlobByHash`.
+//!
+//! Every connection and IP address of the gateway gets its own rate limit buckets.
+//!
+//! The format is negotiated with the `Accept` header: `application/octet-stream` (the default)
+//! returns the concatenated bytes, and `application/scale` returns the SCALE encoded RPC result
+//! (the block hash, block number, transaction index and data of blobs).
+//! Row indexes of raw responses are listed in the `X-Kate-Rows` header.
+//!
+//! Only data of finalized blocks is served, which never changes, so every response carries an
+//! `ETag` derived from the block hash and the request, and `If-None-Match` is answered with
+//! `304 Not Modified`. Blocks which are not finalized yet are answered with `404 Not Found`.
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+
+use avail_core::{
+	header::HeaderExtension, traits::ExtendedHeader, AppId, BlockLengthColumns, BlockLengthRows,
+	OpaqueExtrinsic,
+};
+use codec::{Decode, DecodeAll, Encode};
+use da_runtime::apis::DataAvailApi;
+use hyper::{
+	header::{self, HeaderValue},
+	server::conn::AddrStream,
+	service::{make_service_fn, service_fn},
+	Body, Method, Request, Response, StatusCode,
+};
+use jsonrpsee::{core::Error as JsonRpseeError, types::error::CallError};
+use kate::com::Cell;
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{blake2_256, bytes::from_hex, hexdisplay::HexDisplay};
+use sp_runtime::{
+	generic::Digest,
+	traits::{Block as BlockT, Header},
+};
+
+use crate::{
+	finality::QueryOptions,
+	rate_limit::{Connection, CALL_ORIGIN},
+	Cells, Error, Kate, KateApiServer, Rows,
+};
+
+const LOG_TARGET: &str = "avail::kate::rpc::gateway";
+
+const OCTET_STREAM: &str = "application/octet-stream";
+const SCALE: &str = "application/scale";
+/// Header with the row indexes of raw row responses.
+const ROWS_HEADER: &str = "x-kate-rows";
+/// Finalized data never changes.
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+
+/// Format of a gateway response.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+	Raw,
+	Scale,
+}
+
+impl Format {
+	/// Picks the first supported media type of the `Accept` header.
+	fn negotiate(accept: Option<&HeaderValue>) -> Option<Self> {
+		let Some(accept) = accept else {
+			return Some(Self::Raw);
+		};
+
+		accept
+			.to_str()
+			.ok()?
+			.split(',')
+			.filter_map(|media| media.split(';').next())
+			.find_map(|media| match media.trim() {
+				OCTET_STREAM | "application/*" | "*/*" => Some(Self::Raw),
+				SCALE | "application/x-scale" => Some(Self::Scale),
+				_ => None,
+			})
+	}
+
+	fn content_type(&self) -> &'static str {
+		match self {
+			Self::Raw => OCTET_STREAM,
+			Self::Scale => SCALE,
+		}
+	}
+}
+
+/// Serves the gateway at `addr` until the node stops.
+pub async fn run<Client, Block>(kate: Kate<Client, Block>, addr: SocketAddr)
+where
+	Block: BlockT<Extrinsic = OpaqueExtrinsic>,
+	<Block as BlockT>::Header: ExtendedHeader<
+		<<Block as BlockT>::Header as Header>::Number,
+		<Block as BlockT>::Hash,
+		Digest,
+		HeaderExtension,
+	>,
+	Client: Send + Sync + 'static,
+	Client: HeaderBackend<Block>
+		+ ProvideRuntimeApi<Block>
+		+ BlockBackend<Block>
+		+ BlockchainEvents<Block>,
+	Client::Api: DataAvailApi<Block>,
+{
+	let make_service = make_service_fn(move |stream: &AddrStream| {
+		let kate = kate.clone();
+		let connection = Arc::new(Connection::open(
+			stream.remote_addr().ip(),
+			kate.rate_limiter(),
+		));
+
+		async move {
+			Ok::<_, Infallible>(service_fn(move |request| {
+				let (kate, connection) = (kate.clone(), connection.clone());
+				async move {
+					let response = CALL_ORIGIN
+						.scope(connection.origin(), respond(&kate, request))
+						.await;
+					Ok::<_, Infallible>(response.unwrap_or_else(|response| response))
+				}
+			}))
+		}
+	});
+
+	let server = match hyper::Server::try_bind(&addr) {
+		Ok(builder) => builder.serve(make_service),
+		Err(e) => {
+			log::error!(target: LOG_TARGET, "Kate gateway cannot bind {addr}: {e:?}");
+			return;
+		},
+	};
+
+	log::info!(target: LOG_TARGET, "Kate gateway listening on http://{addr}");
+	if let Err(e) = server.await {
+		log::error!(target: LOG_TARGET, "Kate gateway stopped: {e:?}");
+	}
+}
+
+/// Serves `request`. Errors are already mapped into responses.
+async fn respond<Client, Block>(
+	kate: &Kate<Client, Block>,
+	request: Request<Body>,
+) -> Result<Response<Body>, Response<Body>>
+where
+	Block: BlockT<Extrinsic = OpaqueExtrinsic>,
+	<Block as BlockT>::Header: ExtendedHeader<
+		<<Block as BlockT>::Header as Header>::Number,
+		<Block as BlockT>::Hash,
+		Digest,
+		HeaderExtension,
+	>,
+	Client: Send + Sync + 'static,
+	Client: HeaderBackend<Block>
+		+ ProvideRuntimeApi<Block>
+		+ BlockBackend<Block>
+		+ BlockchainEvents<Block>,
+	Client::Api: DataAvailApi<Block>,
+{
+	if request.method() != Method::GET {
+		return Err(error(
+			StatusCode::METHOD_NOT_ALLOWED,
+			"Only GET is supported",
+		));
+	}
+	let Some(format) = Format::negotiate(request.headers().get(header::ACCEPT)) else {
+		return Err(error(
+			StatusCode::NOT_ACCEPTABLE,
+			"Supported formats are application/octet-stream and application/scale",
+		));
+	};
+
+	let path = request.uri().path().trim_matches('/').to_string();
+	let query = request.uri().query().unwrap_or_default().to_string();
+	let if_none_match = request.headers().get(header::IF_NONE_MATCH).cloned();
+	let segments = path.split('/').collect::<Vec<_>>();
+
+	let result = match segments.as_slice() {
+		["v1", "blocks", block_hash, "rows"] => {
+			let at = parse_hash::<Block::Hash>(block_hash)?;
+			let tag = etag(at.as_ref(), &path, &query, format);
+			if is_not_modified(if_none_match.as_ref(), &tag) {
+				return Ok(not_modified(&tag));
+			}
+			let rows = Rows::try_from(parse_list(query_param(&query, "rows"), parse_u32)?)
+				.map_err(|_| bad_request("Too many rows"))?;
+			let indexes = rows.iter().map(|&row| Some(row)).collect::<Vec<_>>();
+			kate.query_rows(rows, Some(at), Some(QueryOptions::FINALIZED))
+				.await
+				.map(|rows| (tag, rows_body(format, &indexes, rows.into_iter().map(Some))))
+		},
+		["v1", "blocks", block_hash, "apps", app_id, "data"] => {
+			let at = parse_hash::<Block::Hash>(block_hash)?;
+			let tag = etag(at.as_ref(), &path, &query, format);
+			if is_not_modified(if_none_match.as_ref(), &tag) {
+				return Ok(not_modified(&tag));
+			}
+			let app_id = AppId(parse_u32(app_id)?);
+			kate.query_app_data(app_id, Some(at), Some(QueryOptions::FINALIZED))
+				.await
+				.map(|rows| {
+					let indexes = (0..rows.len() as u32).map(Some).collect::<Vec<_>>();
+					(tag, rows_body(format, &indexes, rows.into_iter()))
+				})
+		},
+		["v1", "blocks", block_hash, "proofs"] => {
+			let at = parse_hash::<Block::Hash>(block_hash)?;
+			let tag = etag(at.as_ref(), &path, &query, format);
+			if is_not_modified(if_none_match.as_ref(), &tag) {
+				return Ok(not_modified(&tag));
+			}
+			let cells = Cells::try_from(parse_list(query_param(&query, "cells"), parse_cell)?)
+				.map_err(|_| bad_request("Too many cells"))?;
+			kate.query_proof(cells, Some(at), Some(QueryOptions::FINALIZED))
+				.await
+				.map(|proof| {
+					let body = match format {
+						Format::Raw => (proof, None),
+						Format::Scale => (proof.encode(), None),
+					};
+					(tag, body)
+				})
+		},
+		["v1", "blobs", data_hash] => {
+			let data_hash = parse_hash::<Block::Hash>(data_hash)?;
+			let at = query_param(&query, "block")
+				.map(parse_hash::<Block::Hash>)
+				.transpose()?;
+			kate.get_blob_by_hash(data_hash, at, Some(QueryOptions::FINALIZED))
+				.await
+				.map(|blob| {
+					let tag = etag(blob.block_hash.as_ref(), &path, &query, format);
+					// The SCALE response carries the location of the blob, its proof is served by
+					// `kate_queryDataProofV2`.
+					let body = match format {
+						Format::Raw => blob.data,
+						Format::Scale => (
+							blob.block_hash,
+							blob.block_number,
+							blob.transaction_index,
+							blob.data,
+						)
+							.encode(),
+					};
+					(tag, (body, None))
+				})
+		},
+		_ => return Err(error(StatusCode::NOT_FOUND, "Unknown resource")),
+	};
+
+	let (tag, (body, rows)) = result.map_err(rpc_error)?;
+	if is_not_modified(if_none_match.as_ref(), &tag) {
+		return Ok(not_modified(&tag));
+	}
+
+	let mut response = Response::builder()
+		.header(header::CONTENT_TYPE, format.content_type())
+		.header(header::ETAG, &tag)
+		.header(header::CACHE_CONTROL, IMMUTABLE);
+	if let Some(rows) = rows {
+		response = response.header(ROWS_HEADER, rows);
+	}
+	response
+		.body(Body::from(body))
+		.map_err(|_| error(StatusCode::INTERNAL_SERVER_ERROR, "Invalid response"))
+}
+
+/// Body of a rows response, and the row indexes of raw responses.
+///
+/// Raw responses only contain the rows which are present, so their indexes are listed.
+fn rows_body(
+	format: Format,
+	indexes: &[Option<u32>],
+	rows: impl Iterator<Item = Option<Vec<u8>>>,
+) -> (Vec<u8>, Option<String>) {
+	let rows = rows.collect::<Vec<_>>();
+	match format {
+		Format::Scale => (rows.encode(), None),
+		Format::Raw => {
+			let present = indexes
+				.iter()
+				.zip(&rows)
+				.filter_map(|(index, row)| row.as_ref().and(*index))
+				.map(|index| index.to_string())
+				.collect::<Vec<_>>()
+				.join(",");
+			(
+				rows.into_iter().flatten().flatten().collect(),
+				Some(present),
+			)
+		},
+	}
+}
+
+/// Strong entity tag of a response. Finalized data never changes, so the block hash and the
+/// request identify the response.
+fn etag(block_hash: &[u8], path: &str, query: &str, format: Format) -> String {
+	let request = (block_hash, path, query, format.content_type()).encode();
+	format!("\"{}\"", HexDisplay::from(&blake2_256(&request)))
+}
+
+fn is_not_modified(if_none_match: Option<&HeaderValue>, tag: &str) -> bool {
+	if_none_match
+		.and_then(|value| value.to_str().ok())
+		.map_or(false, |value| {
+			value.split(',').any(|candidate| {
+				let candidate = candidate.trim();
+				candidate == "*" || candidate.trim_start_matches("W/") == tag
+			})
+		})
+}
+
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+	query
+		.split('&')
+		.filter_map(|pair| pair.split_once('='))
+		.find_map(|(key, value)| (key == name).then_some(value))
+}
+
+fn parse_hash<Hash: Decode>(hex: &str) -> Result<Hash, Response<Body>> {
+	let bytes = from_hex(hex).map_err(|_| bad_request("Invalid hash"))?;
+	Hash::decode_all(&mut bytes.as_slice()).map_err(|_| bad_request("Invalid hash"))
+}
+
+fn parse_u32(value: &str) -> Result<u32, Response<Body>> {
+	value
+		.parse()
+		.map_err(|_| bad_request(&format!("Invalid number {value}")))
+}
+
+fn parse_cell(value: &str) -> Result<Cell, Response<Body>> {
+	let (row, col) = value
+		.split_once(':')
+		.ok_or_else(|| bad_request(&format!("Invalid cell {value}, expected row:col")))?;
+	Ok(Cell::new(
+		BlockLengthRows(parse_u32(row)?),
+		BlockLengthColumns(parse_u32(col)?),
+	))
+}
+
+fn parse_list<T>(
+	value: Option<&str>,
+	parse: impl Fn(&str) -> Result<T, Response<Body>>,
+) -> Result<Vec<T>, Response<Body>> {
+	value
+		.unwrap_or_default()
+		.split(',')
+		.filter(|item| !item.is_empty())
+		.map(parse)
+		.collect()
+}
+
+/// Maps a Kate RPC error into a response. Rate limited calls get `429 Too Many Requests`.
+fn rpc_error(e: JsonRpseeError) -> Response<Body> {
+	let JsonRpseeError::Call(CallError::Custom(object)) = &e else {
+		return error(StatusCode::BAD_REQUEST, &e.to_string());
+	};
+	let code = i64::from(object.code());
+	if code == i64::from(Error::NotFinal) {
+		// The resource exists once the block is finalized.
+		return error(StatusCode::NOT_FOUND, object.message());
+	}
+	if code != i64::from(Error::RateLimited) {
+		return error(StatusCode::BAD_REQUEST, object.message());
+	}
+
+	let mut response = error(StatusCode::TOO_MANY_REQUESTS, object.message());
+	let retry_after = object
+		.data()
+		.and_then(|data| data.get().parse::<u64>().ok())
+		.map_or(1, |millis| (millis + 999) / 1000);
+	if let Ok(value) = HeaderValue::from_str(&retry_after.to_string()) {
+		response.headers_mut().insert(header::RETRY_AFTER, value);
+	}
+	response
+}
+
+fn not_modified(tag: &str) -> Response<Body> {
+	Response::builder()
+		.status(StatusCode::NOT_MODIFIED)
+		.header(header::ETAG, tag)
+		.header(header::CACHE_CONTROL, IMMUTABLE)
+		.body(Body::empty())
+		.unwrap_or_default()
+}
+
+fn bad_request(message: &str) -> Response<Body> {
+	error(StatusCode::BAD_REQUEST, message)
+}
+
+fn error(status: StatusCode, message: &str) -> Response<Body> {
+	let mut response = Response::new(Body::from(message.to_string()));
+	*response.status_mut() = status;
+	response
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn accept(value: &'static str) -> Option<HeaderValue> {
+		Some(HeaderValue::from_static(value))
+	}
+
+	#[test]
+	fn format_is_negotiated() {
+		assert_eq!(Format::negotiate(None), Some(Format::Raw));
+		assert_eq!(
+			Format::negotiate(accept(OCTET_STREAM).as_ref()),
+			Some(Format::Raw)
+		);
+		assert_eq!(Format::negotiate(accept("*/*").as_ref()), Some(Format::Raw));
+		assert_eq!(
+			Format::negotiate(accept("application/scale; q=1").as_ref()),
+			Some(Format::Scale)
+		);
+		assert_eq!(
+			Format::negotiate(accept("application/x-scale").as_ref()),
+			Some(Format::Scale)
+		);
+		// The first supported media type wins.
+		assert_eq!(
+			Format::negotiate(accept("text/html, application/scale, */*").as_ref()),
+			Some(Format::Scale)
+		);
+		assert_eq!(Format::negotiate(accept("application/json").as_ref()), None);
+	}
+
+	#[test]
+	fn etag_depends_on_block_request_and_format() {
+		let tag = etag(&[1; 32], "v1/blocks/0x01/rows", "rows=0,1", Format::Raw);
+		assert!(tag.starts_with('"') && tag.ends_with('"'));
+		assert_eq!(
+			tag,
+			etag(&[1; 32], "v1/blocks/0x01/rows", "rows=0,1", Format::Raw)
+		);
+
+		for other in [
+			etag(&[2; 32], "v1/blocks/0x01/rows", "rows=0,1", Format::Raw),
+			etag(&[1; 32], "v1/blocks/0x01/proofs", "rows=0,1", Format::Raw),
+			etag(&[1; 32], "v1/blocks/0x01/rows", "rows=0", Format::Raw),
+			etag(&[1; 32], "v1/blocks/0x01/rows", "rows=0,1", Format::Scale),
+		] {
+			assert_ne!(tag, other);
+		}
+	}
+
+	#[test]
+	fn if_none_match_is_checked() {
+		let tag = etag(&[1; 32], "v1/blobs/0x01", "", Format::Raw);
+		let header = |value: String| HeaderValue::from_str(&value).unwrap();
+
+		assert!(!is_not_modified(None, &tag));
+		assert!(is_not_modified(Some(&header(tag.clone())), &tag));
+		assert!(is_not_modified(Some(&header(format!("W/{tag}"))), &tag));
+		assert!(is_not_modified(
+			Some(&header(format!("\"other\", {tag}"))),
+			&tag
+		));
+		assert!(is_not_modified(Some(&header("*".to_string())), &tag));
+		assert!(!is_not_modified(
+			Some(&header("\"other\"".to_string())),
+			&tag
+		));
+	}
+
+	#[test]
+	fn not_modified_keeps_the_etag() {
+		let response = not_modified("\"tag\"");
+		assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+		assert_eq!(response.headers()[header::ETAG], "\"tag\"");
+		assert_eq!(response.headers()[header::CACHE_CONTROL], IMMUTABLE);
+	}
+
+	#[test]
+	fn raw_rows_list_the_present_rows() {
+		let rows = vec![Some(vec![1, 2]), None, Some(vec![3])];
+		let indexes = [Some(4), Some(5), Some(6)];
+
+		let (body, header) = rows_body(Format::Raw, &indexes, rows.clone().into_iter());
+		assert_eq!(body, vec![1, 2, 3]);
+		assert_eq!(header.as_deref(), Some("4,6"));
+
+		let (body, header) = rows_body(Format::Scale, &indexes, rows.clone().into_iter());
+		assert_eq!(body, rows.encode());
+		assert_eq!(header, None);
+	}
+
+	#[test]
+	fn query_is_parsed() {
+		assert_eq!(query_param("rows=0,1&at=2", "rows"), Some("0,1"));
+		assert_eq!(query_param("rows=0,1&at=2", "at"), Some("2"));
+		assert_eq!(query_param("rows=0,1", "cells"), None);
+
+		assert_eq!(
+			parse_list(Some("1,2,,3"), parse_u32).unwrap(),
+			vec![1, 2, 3]
+		);
+		assert!(parse_list(None, parse_u32).unwrap().is_empty());
+		assert!(parse_list(Some("1,x"), parse_u32).is_err());
+
+		let cell = parse_cell("1:7").unwrap();
+		assert_eq!((cell.row.0, cell.col.0), (1, 7));
+		assert!(parse_cell("1").is_err());
+
+		let hash = parse_hash::<sp_core::H256>(&format!("0x{}", "01".repeat(32))).unwrap();
+		assert_eq!(hash, sp_core::H256::repeat_byte(1));
+		assert!(parse_hash::<sp_core::H256>("0x01").is_err());
+		assert!(parse_hash::<sp_core::H256>(&format!("0x{}", "01".repeat(33))).is_err());
+	}
+
+	#[test]
+	fn rpc_errors_are_mapped_to_statuses() {
+		let call_error =
+			|error: Error, data: u64| error.into_rpc_error("message".to_string(), data);
+
+		let response = rpc_error(call_error(Error::RateLimited, 1_500));
+		assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+		assert_eq!(response.headers()[header::RETRY_AFTER], "2");
+
+		let response = rpc_error(call_error(Error::NotFinal, 0));
+		assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+		let response = rpc_error(call_error(Error::RuntimeError, 0));
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+		assert!(response.headers().get(header::RETRY_AFTER).is_none());
+	}
+}
@@ -0,0 +1,305 @@
+//! In-memory grid caches of the Kate RPC.
+//!
+//! The evaluation and polynomial grids are cached by block hash, weighted by their size in bytes.
+//! The caches can be resized at runtime, which replaces them by new caches holding the same
+//! entries, so they are kept behind a lock and cloned (which is cheap) by every user.
+use std::{
+	future::Future,
+	hash::Hash,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, RwLock,
+	},
+	time::Instant,
+};
+
+use avail_base::metrics::avail::KateRpcMetrics;
+use kate::gridgen::{EvaluationGrid, PolynomialGrid};
+use kate_recovery::matrix::Dimensions;
+use moka::{future::Cache, notification::RemovalCause};
+
+pub type EvalGrid = Arc<EvaluationGrid>;
+// Have to put dimensions here b/c it's not public in polynomialgrid
+pub type PolyGrid = Arc<(Dimensions, PolynomialGrid)>;
+
+/// Label of the evaluation grid cache metrics.
+pub const EVAL_GRID: &str = "eval_grid";
+/// Label of the polynomial grid cache metrics.
+pub const POLY_GRID: &str = "poly_grid";
+
+/// Weight of an evaluation grid in its cache, in bytes.
+pub fn eval_grid_weight(grid: &EvaluationGrid) -> u32 {
+	let n_cells: u32 = grid.dims().size();
+	n_cells * 32 + 8
+}
+
+/// Weight of a polynomial grid in its cache, in bytes.
+pub fn poly_grid_weight(grid: &(Dimensions, PolynomialGrid)) -> u32 {
+	let n_cells: u32 = grid.0.size();
+	let n_points: u32 = grid
+		.0
+		.width()
+		.try_into()
+		.expect("Never more than 2^32 points");
+	n_cells * 32 + n_points * 32
+}
+
+/// Evaluation and polynomial grid caches.
+pub struct GridCaches<H> {
+	eval: RwLock<Cache<H, EvalGrid>>,
+	poly: RwLock<Cache<H, PolyGrid>>,
+}
+
+impl<H> GridCaches<H>
+where
+	H: Hash + Eq + Clone + Send + Sync + 'static,
+{
+	/// Creates the caches, with their capacity in bytes.
+	pub fn new(eval_capacity: u64, poly_capacity: u64) -> Self {
+		Self {
+			eval: RwLock::new(eval_cache(eval_capacity)),
+			poly: RwLock::new(poly_cache(poly_capacity)),
+		}
+	}
+
+	pub fn eval(&self) -> Cache<H, EvalGrid> {
+		self.eval.read().expect("Poisoned lock").clone()
+	}
+
+	pub fn poly(&self) -> Cache<H, PolyGrid> {
+		self.poly.read().expect("Poisoned lock").clone()
+	}
+
+	/// Replaces the caches by caches of the given capacity, in bytes, keeping as many entries as
+	/// they fit.
+	///
+	/// Entries inserted in the old caches during the resize are lost.
+	pub async fn resize(&self, eval_capacity: u64, poly_capacity: u64) {
+		let eval = eval_cache(eval_capacity);
+		for (key, value) in self.eval().iter() {
+			eval.insert(H::clone(&key), value).await;
+		}
+		let poly = poly_cache(poly_capacity);
+		for (key, value) in self.poly().iter() {
+			poly.insert(H::clone(&key), value).await;
+		}
+
+		*self.eval.write().expect("Poisoned lock") = eval;
+		*self.poly.write().expect("Poisoned lock") = poly;
+	}
+}
+
+fn eval_cache<H>(capacity: u64) -> Cache<H, EvalGrid>
+where
+	H: Hash + Eq + Send + Sync + 'static,
+{
+	Cache::builder()
+		.weigher(|_, grid: &EvalGrid| eval_grid_weight(grid))
+		.max_capacity(capacity)
+		.eviction_listener(|_, _, cause| observe_eviction(EVAL_GRID, cause))
+		.build()
+}
+
+fn poly_cache<H>(capacity: u64) -> Cache<H, PolyGrid>
+where
+	H: Hash + Eq + Send + Sync + 'static,
+{
+	Cache::builder()
+		.weigher(|_, grid: &PolyGrid| poly_grid_weight(grid))
+		.max_capacity(capacity)
+		.eviction_listener(|_, _, cause| observe_eviction(POLY_GRID, cause))
+		.build()
+}
+
+fn observe_eviction(cache: &str, cause: RemovalCause) {
+	if cause.was_evicted() {
+		KateRpcMetrics::observe_grid_cache_eviction(cache);
+	}
+}
+
+/// Returns the entry of `key`, building it with `init` on a miss, and records the hit or miss,
+/// the build time and the size of `cache` (labelled `name`).
+pub async fn get_or_build<H, V, E, F>(
+	cache: &Cache<H, V>,
+	name: &str,
+	key: H,
+	init: F,
+) -> Result<V, Arc<E>>
+where
+	H: Hash + Eq + Send + Sync + 'static,
+	V: Clone + Send + Sync + 'static,
+	E: Send + Sync + 'static,
+	F: Future<Output = Result<V, E>>,
+{
+	let missed = AtomicBool::new(false);
+	let result = cache
+		.try_get_with(key, async {
+			missed.store(true, Ordering::Relaxed);
+			let build_start = Instant::now();
+			let value = init.await;
+			KateRpcMetrics::observe_grid_build_time(name, build_start.elapsed());
+			value
+		})
+		.await;
+
+	if missed.load(Ordering::Relaxed) {
+		KateRpcMetrics::observe_grid_cache_miss(name);
+	} else {
+		KateRpcMetrics::observe_grid_cache_hit(name);
+	}
+	KateRpcMetrics::observe_grid_cache_size(name, cache.entry_count(), cache.weighted_size());
+
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::atomic::AtomicUsize;
+
+	use avail_core::{AppExtrinsic, AppId};
+	use futures::executor::block_on;
+
+	use super::*;
+
+	fn grid(data: &[u8]) -> EvalGrid {
+		let extrinsics = vec![AppExtrinsic::new(AppId(1), data.to_vec())];
+		Arc::new(EvaluationGrid::from_extrinsics(extrinsics, 4, 256, 256, [0u8; 32]).unwrap())
+	}
+
+	fn poly(grid: &EvalGrid) -> PolyGrid {
+		Arc::new((grid.dims(), grid.make_polynomial_grid().unwrap()))
+	}
+
+	/// Inserts a grid and its polynomials for each key, returning the weight of one of each.
+	async fn fill(caches: &GridCaches<u32>, keys: &[u32]) -> (u64, u64) {
+		let mut weights = (0, 0);
+		for &key in keys {
+			let grid = grid(&[key as u8; 64]);
+			let poly = poly(&grid);
+			weights = (
+				u64::from(eval_grid_weight(&grid)),
+				u64::from(poly_grid_weight(&poly)),
+			);
+			caches.eval().insert(key, grid).await;
+			caches.poly().insert(key, poly).await;
+		}
+		weights
+	}
+
+	async fn sizes(caches: &GridCaches<u32>) -> ((u64, u64), (u64, u64)) {
+		let (eval, poly) = (caches.eval(), caches.poly());
+		eval.run_pending_tasks().await;
+		poly.run_pending_tasks().await;
+		(
+			(eval.entry_count(), eval.weighted_size()),
+			(poly.entry_count(), poly.weighted_size()),
+		)
+	}
+
+	#[test]
+	fn weights_are_the_grid_sizes() {
+		let grid = grid(&[1; 64]);
+		let cells: u32 = grid.dims().size();
+		assert_eq!(eval_grid_weight(&grid), cells * 32 + 8);
+
+		let poly = poly(&grid);
+		let width = u32::try_from(grid.dims().width()).unwrap();
+		assert_eq!(poly_grid_weight(&poly), cells * 32 + width * 32);
+	}
+
+	#[test]
+	fn capacity_evicts_entries() {
+		block_on(async {
+			let (eval_weight, poly_weight) = fill(&GridCaches::new(u64::MAX, u64::MAX), &[0]).await;
+			let caches = GridCaches::new(2 * eval_weight, 2 * poly_weight);
+			fill(&caches, &[1, 2, 3]).await;
+
+			let (eval, poly) = sizes(&caches).await;
+			assert_eq!(eval, (2, 2 * eval_weight));
+			assert_eq!(poly, (2, 2 * poly_weight));
+		});
+	}
+
+	#[test]
+	fn growing_keeps_all_entries() {
+		block_on(async {
+			let caches = GridCaches::new(u64::MAX / 2, u64::MAX / 2);
+			let (eval_weight, poly_weight) = fill(&caches, &[1, 2, 3]).await;
+
+			caches.resize(u64::MAX, u64::MAX).await;
+
+			let (eval, poly) = sizes(&caches).await;
+			assert_eq!(eval, (3, 3 * eval_weight));
+			assert_eq!(poly, (3, 3 * poly_weight));
+			for key in [1, 2, 3] {
+				assert!(caches.eval().contains_key(&key));
+				assert!(caches.poly().contains_key(&key));
+			}
+		});
+	}
+
+	#[test]
+	fn shrinking_keeps_the_entries_that_fit() {
+		block_on(async {
+			let caches = GridCaches::new(u64::MAX, u64::MAX);
+			let (eval_weight, poly_weight) = fill(&caches, &[1, 2, 3]).await;
+
+			caches.resize(eval_weight, poly_weight).await;
+
+			let (eval, poly) = sizes(&caches).await;
+			assert_eq!(eval, (1, eval_weight));
+			assert_eq!(poly, (1, poly_weight));
+		});
+	}
+
+	#[test]
+	fn shrinking_to_zero_empties_the_caches() {
+		block_on(async {
+			let caches = GridCaches::new(u64::MAX, u64::MAX);
+			fill(&caches, &[1, 2]).await;
+
+			caches.resize(0, 0).await;
+
+			assert_eq!(sizes(&caches).await, ((0, 0), (0, 0)));
+		});
+	}
+
+	#[test]
+	fn grids_are_built_once() {
+		block_on(async {
+			let cache = eval_cache::<u32>(u64::MAX);
+			let builds = AtomicUsize::new(0);
+			let build = || async {
+				builds.fetch_add(1, Ordering::Relaxed);
+				Ok::<_, ()>(grid(&[1; 64]))
+			};
+
+			let first = get_or_build(&cache, EVAL_GRID, 1, build()).await.unwrap();
+			let second = get_or_build(&cache, EVAL_GRID, 1, build()).await.unwrap();
+			assert!(Arc::ptr_eq(&first, &second));
+			assert_eq!(builds.load(Ordering::Relaxed), 1);
+
+			get_or_build(&cache, EVAL_GRID, 2, build()).await.unwrap();
+			assert_eq!(builds.load(Ordering::Relaxed), 2);
+		});
+	}
+
+	#[test]
+	fn failed_builds_are_not_cached() {
+		block_on(async {
+			let cache = eval_cache::<u32>(u64::MAX);
+
+			let failed =
+				get_or_build(&cache, EVAL_GRID, 1, async { Err::<EvalGrid, _>("failed") }).await;
+			assert_eq!(failed.err().as_deref(), Some(&"failed"));
+			assert!(!cache.contains_key(&1));
+
+			let built = get_or_build(&cache, EVAL_GRID, 1, async {
+				Ok::<_, &str>(grid(&[1; 64]))
+			})
+			.await;
+			assert!(built.is_ok());
+			assert!(cache.contains_key(&1));
+		});
+	}
+}
@@ -1,6 +1,6 @@
 #![deny(unused_crate_dependencies)]
 use core::num::NonZeroU16;
-use std::{marker::Sync, sync::Arc};
+use std::{collections::BTreeMap, marker::Sync, sync::Arc};
 
 use avail_base::metrics::avail::KateRpcMetrics;
 use avail_core::data_proof_v2::{ProofResponse, SubTrie};
@@ -8,45 +8,189 @@ use avail_core::{
 	header::HeaderExtension, traits::ExtendedHeader, AppExtrinsic, AppId, DataProof, DataProofV2,
 	OpaqueExtrinsic,
 };
+use codec::{Decode, Encode};
 use da_runtime::RuntimeCall;
 use da_runtime::{apis::DataAvailApi, Runtime, UncheckedExtrinsic};
 use frame_support::BoundedVec;
 use frame_system::{limits::BlockLength, submitted_data};
+use futures::{channel::oneshot, FutureExt, StreamExt};
 use jsonrpsee::{
 	core::{async_trait, Error as JsonRpseeError, RpcResult},
 	proc_macros::rpc,
+	types::{
+		error::{CallError, ErrorObject, ErrorObjectOwned},
+		SubscriptionResult,
+	},
+	SubscriptionSink,
 };
 
 use kate::gridgen::AsBytes;
 use kate::{
 	com::Cell,
 	config::{COL_EXTENSION, ROW_EXTENSION},
-	gridgen::{EvaluationGrid, PolynomialGrid},
+	gridgen::{multiproof_block, EvaluationGrid},
 	pmp::m1_blst,
 	Seed,
 };
 
-use kate_recovery::matrix::Dimensions;
-use moka::future::Cache;
+use kate_recovery::{
+	data::DataCell,
+	matrix::{Dimensions, Position},
+};
+use kate_verifier::{extrinsics::extrinsic_proof, reconstruction};
 use rayon::prelude::*;
-use sc_client_api::BlockBackend;
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use serde::{Deserialize, Serialize};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
+use sp_core::{blake2_256, hexdisplay::HexDisplay, traits::SpawnNamed, H256};
 use sp_runtime::{
 	generic::{Digest, SignedBlock},
-	traits::{Block as BlockT, ConstU32, Header},
-	{AccountId32, MultiAddress},
+	traits::{Block as BlockT, ConstU32, Header, NumberFor},
+	SaturatedConversion, {AccountId32, MultiAddress},
 };
 
 pub type HashOf<Block> = <Block as BlockT>::Hash;
 
+/// Executor used to drive the subscriptions of the Kate RPC.
+pub type SubscriptionTaskExecutor = Arc<dyn SpawnNamed>;
+
 pub type MaxRows = ConstU32<64>;
 pub type Rows = BoundedVec<u32, MaxRows>;
 
+pub type MaxColumns = ConstU32<64>;
+pub type Columns = BoundedVec<u32, MaxColumns>;
+
 pub type MaxCells = ConstU32<10_000>;
 pub type Cells = BoundedVec<Cell, MaxCells>;
-
+pub type DataCells = BoundedVec<GDataCell, MaxCells>;
+
+pub mod admin;
+pub mod blob_index;
+pub mod chain_head;
+pub mod da_info;
+pub mod disk_cache;
+pub mod finality;
+pub mod gateway;
+pub mod grid_cache;
 pub mod metrics;
+pub mod multiproof;
+pub mod rate_limit;
+pub mod server;
+pub mod v2;
+
+use blob_index::{BlobIndex, BlobLocation};
+use da_info::BlockDaInfo;
+use disk_cache::GridDiskCache;
+use finality::{Finality, QueryOptions};
+use grid_cache::{EvalGrid, GridCaches, PolyGrid};
+use multiproof::{GCellBlock, GMultiProof};
+use rate_limit::{KateCall, Origin, RateLimited, RateLimiter};
+use v2::{GCellPosition, ProofsResponse, RowsResponse};
+
+/// Notification sent by `kate_subscribeAppData` for each finalized block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AppDataNotification<Hash, Number> {
+	/// The block contains data of the application.
+	///
+	/// `rows` follows the same layout as `kate_queryAppData`.
+	#[serde(rename_all = "camelCase")]
+	Data {
+		block_hash: Hash,
+		number: Number,
+		rows: Vec<Option<Vec<u8>>>,
+	},
+	/// The block does not contain any data of the application.
+	#[serde(rename_all = "camelCase")]
+	Empty { block_hash: Hash, number: Number },
+	/// The data of the block could not be served, e.g. because its body was pruned.
+	///
+	/// The subscription stays open, so the block can be fetched again with
+	/// `kate_queryAppData` at `block_hash`.
+	#[serde(rename_all = "camelCase")]
+	Error {
+		block_hash: Hash,
+		number: Option<Number>,
+		message: String,
+	},
+}
+
+/// Cell of the extended grid with its scalar, used by `kate_reconstructAppRows`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GDataCell {
+	pub row: u32,
+	pub col: u16,
+	/// Serialized scalar (32 bytes).
+	pub scalar: Vec<u8>,
+}
+
+/// Blob returned by `kate_getBlobByHash`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobResponse<Hash, Number> {
+	pub block_hash: Hash,
+	pub block_number: Number,
+	/// Index of the `submit_data` extrinsic in the block.
+	pub transaction_index: u32,
+	pub data: Vec<u8>,
+	/// Data proof of the blob, or `None` if it cannot be built, e.g. the block body was pruned.
+	pub proof: Option<ProofResponse>,
+}
+
+/// Blob assembled from chunks, returned by `kate_getAssembledBlob`.
+///
+/// The blob is proven by the data proof of each of its chunks, and by the hashes of the chunks
+/// deriving `blob_root`, see `da_control::blob_root`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssembledBlobResponse<Hash, Number> {
+	pub blob_root: Hash,
+	/// Block whose state contains the manifest of the blob.
+	pub manifest_block_hash: Hash,
+	/// Account which submitted the chunks.
+	pub owner: AccountId32,
+	/// Concatenated data of the chunks.
+	pub data: Vec<u8>,
+	/// Chunks of the blob, in order.
+	pub chunks: Vec<BlobChunkResponse<Hash, Number>>,
+}
+
+/// Chunk of an assembled blob, with its location and data proof.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobChunkResponse<Hash, Number> {
+	pub block_hash: Hash,
+	pub block_number: Number,
+	/// Index of the `submit_data_chunk` extrinsic in the block.
+	pub transaction_index: u32,
+	pub data_hash: Hash,
+	pub len: u32,
+	/// Data proof of the chunk, or `None` if it cannot be built, e.g. the block body was pruned.
+	pub proof: Option<ProofResponse>,
+}
+
+/// Inclusion proof of an extrinsic, returned by `kate_queryExtrinsicProof`.
+///
+/// The proof can be checked with `kate_verifier::extrinsics::verify_extrinsic_proof`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtrinsicProof<Hash> {
+	pub block_hash: Hash,
+	/// Extrinsics root of the block header.
+	pub extrinsics_root: Hash,
+	/// Index of the extrinsic in the block.
+	pub transaction_index: u32,
+	/// SCALE encoded extrinsic.
+	pub extrinsic: Vec<u8>,
+	/// Compact trie proof of the extrinsic against `extrinsics_root`.
+	pub proof: Vec<Vec<u8>>,
+	/// Signer of the extrinsic, if it is signed by an account id.
+	pub signer: Option<AccountId32>,
+	/// Application id of the extrinsic, if it is signed.
+	pub app_id: Option<AppId>,
+}
 
 /// # TODO
 /// - [ ] Update type definitions for RPCs in our subxt & explorer.
@@ -56,26 +200,99 @@ where
 	Block: BlockT,
 {
 	#[method(name = "kate_queryRows")]
-	async fn query_rows(&self, rows: Rows, at: Option<HashOf<Block>>) -> RpcResult<Vec<Vec<u8>>>;
+	async fn query_rows(
+		&self,
+		rows: Rows,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<Vec<Vec<u8>>>;
+
+	/// Same as `kate_queryRows`, but every row is labelled with its index.
+	#[method(name = "kate_queryRowsV2")]
+	async fn query_rows_v2(
+		&self,
+		rows: Rows,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<RowsResponse>;
+
+	#[method(name = "kate_queryColumns")]
+	async fn query_columns(
+		&self,
+		columns: Columns,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<Vec<Vec<u8>>>;
 
 	#[method(name = "kate_queryAppData")]
 	async fn query_app_data(
 		&self,
 		app_id: AppId,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<Vec<Option<Vec<u8>>>>;
 
 	#[method(name = "kate_queryProof")]
-	async fn query_proof(&self, cells: Cells, at: Option<HashOf<Block>>) -> RpcResult<Vec<u8>>;
+	async fn query_proof(
+		&self,
+		cells: Cells,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<Vec<u8>>;
+
+	/// Same as `kate_queryProof`, but every proof is split from its scalar and labelled with its
+	/// cell.
+	#[method(name = "kate_queryProofV2")]
+	async fn query_proof_v2(
+		&self,
+		cells: Cells,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<ProofsResponse>;
+
+	/// Rebuilds the original rows of `app_id` from a subset of the extended grid cells, and
+	/// checks them against the header commitments.
+	///
+	/// At least half of the cells of each extended column are required. The result follows the
+	/// layout of `kate_queryAppData`.
+	#[method(name = "kate_reconstructAppRows")]
+	async fn reconstruct_app_rows(
+		&self,
+		app_id: AppId,
+		cells: DataCells,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<Vec<Option<Vec<u8>>>>;
+
+	#[method(name = "kate_queryMultiProof")]
+	async fn query_multiproof(
+		&self,
+		cells: Cells,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<Vec<GMultiProof>>;
 
 	#[method(name = "kate_blockLength")]
-	async fn query_block_length(&self, at: Option<HashOf<Block>>) -> RpcResult<BlockLength>;
+	async fn query_block_length(
+		&self,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<BlockLength>;
+
+	/// Returns the decoded data availability metadata of the block header.
+	#[method(name = "kate_blockDaInfo")]
+	async fn query_block_da_info(
+		&self,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<BlockDaInfo<HashOf<Block>>>;
 
 	#[method(name = "kate_queryDataProof")]
 	async fn query_data_proof(
 		&self,
 		transaction_index: u32,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<DataProof>;
 
 	#[method(name = "kate_queryDataProofV2")]
@@ -83,22 +300,97 @@ where
 		&self,
 		transaction_index: u32,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<ProofResponse>;
+
+	/// Returns the data proofs of every successful `submit_data` and `send_message` call of the
+	/// block, as `(transaction_index, proof)` pairs.
+	#[method(name = "kate_queryAllDataProofsV2")]
+	async fn query_all_data_proofs_v2(
+		&self,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<Vec<(u32, ProofResponse)>>;
+
+	/// Returns the blob submitted with `data_hash`, its location and its data proof.
+	///
+	/// The blob is looked up in block `at` if it is given, otherwise in the node-side blob index
+	/// (`--enable-kate-blob-index`), which only contains finalized blocks.
+	#[method(name = "kate_getBlobByHash")]
+	async fn get_blob_by_hash(
+		&self,
+		data_hash: HashOf<Block>,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<BlobResponse<HashOf<Block>, NumberFor<Block>>>;
+
+	/// Returns the blob assembled with `da_control::finalize_blob` whose root is `blob_root`,
+	/// along with the location and the data proof of each of its chunks.
+	///
+	/// The manifest of the blob is read from the state of block `at`. The call fails if the
+	/// hashes of the chunks do not derive `blob_root`.
+	#[method(name = "kate_getAssembledBlob")]
+	async fn get_assembled_blob(
+		&self,
+		blob_root: HashOf<Block>,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<AssembledBlobResponse<HashOf<Block>, NumberFor<Block>>>;
+
+	/// Returns the proof of the extrinsic `transaction_index` of the block against the
+	/// `extrinsics_root` of its header.
+	#[method(name = "kate_queryExtrinsicProof")]
+	async fn query_extrinsic_proof(
+		&self,
+		transaction_index: u32,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<ExtrinsicProof<HashOf<Block>>>;
+
+	/// Streams the data of `app_id` for every newly finalized block.
+	///
+	/// Blocks without data of `app_id` are notified as empty, so every finalized block is
+	/// notified exactly once and in order. A block whose data cannot be served is notified as an
+	/// error and the stream carries on with the next block.
+	#[subscription(
+		name = "kate_subscribeAppData" => "kate_appData",
+		unsubscribe = "kate_unsubscribeAppData",
+		item = AppDataNotification<HashOf<Block>, NumberFor<Block>>,
+	)]
+	fn subscribe_app_data(&self, app_id: AppId);
 }
 
 #[allow(clippy::type_complexity)]
 pub struct Kate<Client, Block: BlockT> {
 	client: Arc<Client>,
-	eval_grid_cache: Cache<Block::Hash, Arc<EvaluationGrid>>,
-	// Have to put dimensions here b/c it's not public in polynomialgrid
-	poly_grid_cache: Cache<Block::Hash, Arc<(Dimensions, PolynomialGrid)>>,
-	multiproof_srs: m1_blst::M1NoPrecomp,
+	grid_caches: Arc<GridCaches<Block::Hash>>,
+	multiproof_srs: Arc<m1_blst::M1NoPrecomp>,
 	max_cells_size: usize,
+	executor: SubscriptionTaskExecutor,
+	grid_disk_cache: Option<Arc<GridDiskCache>>,
+	blob_index: Option<Arc<BlobIndex>>,
+	rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+impl<Client, Block: BlockT> Clone for Kate<Client, Block> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+			grid_caches: self.grid_caches.clone(),
+			multiproof_srs: self.multiproof_srs.clone(),
+			max_cells_size: self.max_cells_size,
+			executor: self.executor.clone(),
+			grid_disk_cache: self.grid_disk_cache.clone(),
+			blob_index: self.blob_index.clone(),
+			rate_limiter: self.rate_limiter.clone(),
+		}
+	}
 }
 
 impl<Client, Block: BlockT> Kate<Client, Block> {
 	pub fn new(
 		client: Arc<Client>,
+		executor: SubscriptionTaskExecutor,
 		max_cells_size: usize,
 		eval_grid_cache_size: u64,
 		poly_grid_cach_size: u64,
@@ -109,34 +401,121 @@ impl<Client, Block: BlockT> Kate<Client, Block> {
 
 		Self {
 			client,
-			eval_grid_cache: Cache::<_, Arc<EvaluationGrid>>::builder()
-				.weigher(|_, v| {
-					let n_cells: u32 = v.dims().size();
-					n_cells * 32 + 8
-				})
-				.max_capacity(eval_grid_cache_size)
-				.build(),
-			poly_grid_cache: Cache::<_, Arc<(Dimensions, PolynomialGrid)>>::builder()
-				.weigher(|_, v| {
-					let n_cells: u32 = v.0.size();
-					let n_points: u32 =
-						v.0.width().try_into().expect("Never more than 2^32 points");
-					n_cells * 32 + n_points * 32
-				})
-				.max_capacity(poly_grid_cach_size)
-				.build(),
-			multiproof_srs: kate::couscous::multiproof_params(),
+			grid_caches: Arc::new(GridCaches::new(eval_grid_cache_size, poly_grid_cach_size)),
+			multiproof_srs: Arc::new(kate::couscous::multiproof_params()),
 			max_cells_size,
+			executor,
+			grid_disk_cache: None,
+			blob_index: None,
+			rate_limiter: None,
 		}
 	}
+
+	/// Persists the evaluation grids into `grid_disk_cache`, which is used as a second tier
+	/// when the in-memory cache misses.
+	pub fn with_grid_disk_cache(mut self, grid_disk_cache: Option<Arc<GridDiskCache>>) -> Self {
+		self.grid_disk_cache = grid_disk_cache;
+		self
+	}
+
+	/// Uses `blob_index` to locate the blobs of `kate_getBlobByHash`.
+	pub fn with_blob_index(mut self, blob_index: Option<Arc<BlobIndex>>) -> Self {
+		self.blob_index = blob_index;
+		self
+	}
+
+	/// Charges every call to `rate_limiter`, which rejects the calls over its limits.
+	pub fn with_rate_limiter(mut self, rate_limiter: Option<Arc<RateLimiter>>) -> Self {
+		self.rate_limiter = rate_limiter;
+		self
+	}
+
+	/// Rate limiter charged by the calls, if any.
+	pub fn rate_limiter(&self) -> Option<Arc<RateLimiter>> {
+		self.rate_limiter.clone()
+	}
+
+	/// Runs the blocking `f` as a blocking task of the executor, and waits for its result.
+	pub(crate) async fn run_blocking<R>(
+		&self,
+		name: &'static str,
+		f: impl FnOnce() -> R + Send + 'static,
+	) -> RpcResult<R>
+	where
+		R: Send + 'static,
+	{
+		let (tx, rx) = oneshot::channel();
+		let fut = async move {
+			let _ = tx.send(f());
+		};
+		self.executor.spawn_blocking(name, Some("rpc"), fut.boxed());
+		rx.await
+			.map_err(|_| JsonRpseeError::Custom(format!("Task {name} was cancelled")))
+	}
+
+	/// Charges the cost of `call` to the buckets of the caller.
+	///
+	/// Calls whose caller is unknown are not charged, see [`rate_limit`].
+	fn charge(&self, call: KateCall) -> RpcResult<()> {
+		self.charge_origin(rate_limit::current_origin(), call)
+			.map_err(|limited| CallError::Custom(rate_limited_error(call, limited)).into())
+	}
+
+	/// Charges the cost of `call` to the buckets of `origin`.
+	fn charge_origin(&self, origin: Option<Origin>, call: KateCall) -> Result<(), RateLimited> {
+		let (Some(rate_limiter), Some(origin)) = (&self.rate_limiter, origin) else {
+			return Ok(());
+		};
+
+		let cost = call.cost();
+		let result = rate_limiter.check(origin, cost);
+		let (connections, ips) = rate_limiter.tracked();
+		KateRpcMetrics::observe_rate_limit_buckets(connections, ips);
+
+		match &result {
+			Ok(()) => KateRpcMetrics::observe_rate_limit_charged(call.method(), cost),
+			Err(limited) => {
+				KateRpcMetrics::observe_rate_limit_rejected(call.method(), limited.scope.as_str())
+			},
+		}
+		result
+	}
+
+	/// Waits until `origin` can pay for `call`, so a limited subscription is slowed down instead
+	/// of skipping notifications.
+	async fn throttle(&self, origin: Option<Origin>, call: KateCall) {
+		while let Err(limited) = self.charge_origin(origin, call) {
+			tokio::time::sleep(limited.retry_after).await;
+		}
+	}
+}
+
+/// Error of a call rejected by the rate limiter. The retry delay, in milliseconds, is the error
+/// data.
+fn rate_limited_error(call: KateCall, limited: RateLimited) -> ErrorObjectOwned {
+	let retry_after = limited.retry_after.as_millis() as u64;
+	Error::RateLimited.into_error_object(
+		format!(
+			"{} exceeds the rate limit per {} (cost {}), retry in {retry_after} ms",
+			call.method(),
+			limited.scope.as_str(),
+			call.cost(),
+		),
+		retry_after,
+	)
 }
 
 /// Error type of this RPC api.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Error {
 	/// The transaction was not decodable.
 	DecodeError,
 	/// The call to runtime failed.
 	RuntimeError,
+	/// The caller exceeded its rate limit.
+	RateLimited,
+	/// The requested block does not have the requested finality yet.
+	NotFinal,
 }
 
 impl From<Error> for i64 {
@@ -144,10 +523,24 @@ impl From<Error> for i64 {
 		match e {
 			Error::RuntimeError => 1,
 			Error::DecodeError => 2,
+			Error::RateLimited => 3,
+			Error::NotFinal => 4,
 		}
 	}
 }
 
+impl Error {
+	/// JSON-RPC error with the code of `self`.
+	fn into_rpc_error<D: Serialize>(self, message: String, data: D) -> JsonRpseeError {
+		CallError::Custom(self.into_error_object(message, data)).into()
+	}
+
+	fn into_error_object<D: Serialize>(self, message: String, data: D) -> ErrorObjectOwned {
+		let code = i64::from(self) as i32;
+		ErrorObject::owned(code, message, Some(data))
+	}
+}
+
 macro_rules! internal_err {
 	($($arg:tt)*) => {{
 		JsonRpseeError::Custom(format!($($arg)*))
@@ -172,17 +565,44 @@ where
 		at.unwrap_or_else(|| self.client.info().best_hash)
 	}
 
-	fn is_block_finalized(&self, block: &SignedBlock<Block>) -> Result<(), JsonRpseeError> {
-		let block_header = block.block.header();
-		let (block_hash, block_number) = (block_header.hash(), *block_header.number());
+	/// Returns the block `at`, or the default block of `finality`, after checking that it has the
+	/// requested `finality`.
+	///
+	/// Blocks which are not final yet are rejected with [`Error::NotFinal`], whose data is the
+	/// number of the finalized head.
+	fn resolve_block(&self, at: Option<Block::Hash>, finality: Finality) -> RpcResult<Block::Hash> {
+		let info = self.client.info();
+		let at = match (at, finality) {
+			(Some(at), Finality::Any) => return Ok(at),
+			(Some(at), _) => at,
+			(None, Finality::Finalized) => return Ok(info.finalized_hash),
+			(None, Finality::Best | Finality::Any) => return Ok(info.best_hash),
+		};
+
+		let number = self
+			.client
+			.number(at)
+			.map_err(|e| internal_err!("Invalid block number: {:?}", e))?
+			.ok_or_else(|| internal_err!("Missing block {}", at))?;
+		let canonical = self
+			.client
+			.hash(number)
+			.map_err(|e| internal_err!("Invalid block number: {:?}", e))?;
 
-		if self.client.info().finalized_number < block_number {
+		if canonical != Some(at) {
 			return Err(internal_err!(
-				"Requested block {block_hash} is not finalized"
+				"Requested block {at} is not on the best chain"
+			));
+		}
+		if finality == Finality::Finalized && number > info.finalized_number {
+			let finalized_number: u32 = info.finalized_number.saturated_into();
+			return Err(Error::NotFinal.into_rpc_error(
+				format!("Requested block {at} is not finalized"),
+				finalized_number,
 			));
 		}
 
-		Ok(())
+		Ok(at)
 	}
 
 	fn get_signed_block(&self, at: Option<Block::Hash>) -> RpcResult<SignedBlock<Block>> {
@@ -193,13 +613,155 @@ where
 			.ok_or_else(|| internal_err!("Missing block {}", at))
 	}
 
-	fn get_signed_and_finalized_block(
+	/// Builds the data proof of `transaction_index`, used by `kate_queryDataProofV2` and
+	/// `kate_getBlobByHash`.
+	fn data_proof_v2(
+		&self,
+		transaction_index: u32,
+		at: Option<HashOf<Block>>,
+	) -> RpcResult<ProofResponse> {
+		let block = self.get_signed_block(at)?.block;
+		let (calls, callers, bridge_nonce) = self.data_proof_calls(&block, at)?;
+
+		let transaction_call = calls.get(transaction_index as usize).ok_or_else(|| {
+			internal_err!(
+				"Cannot to fetch transaction call at index {:?}: {:?}",
+				transaction_index,
+				at
+			)
+		})?;
+
+		let call_type: SubTrie;
+		let root_side: SubTrie;
+		match transaction_call {
+			RuntimeCall::DataAvailability(
+				da_control::Call::submit_data { .. } | da_control::Call::submit_data_chunk { .. },
+			) => {
+				call_type = SubTrie::Left;
+				root_side = SubTrie::Right;
+			},
+			RuntimeCall::Vector(pallet_vector::Call::send_message { .. }) => {
+				call_type = SubTrie::Right;
+				root_side = SubTrie::Left;
+			},
+			_ => {
+				return Err(internal_err!(
+					"Data proof cannot be generated for transaction index={} at block {:?}",
+					transaction_index,
+					at
+				));
+			},
+		}
+
+		// Build the proof.
+		let (proof, root, message) = submitted_data::calls_proof_v2::<Runtime, _, _>(
+			calls.into_iter(),
+			callers,
+			transaction_index,
+			bridge_nonce,
+			call_type,
+		)
+		.ok_or_else(|| {
+			internal_err!(
+				"Data proof cannot be generated for transaction index={} at block {:?}",
+				transaction_index,
+				at
+			)
+		})?;
+
+		let data_proof = DataProofV2::try_from((&proof, root, root_side))
+			.map_err(|e| internal_err!("Data proof cannot be loaded from merkle root: {:?}", e))?;
+
+		Ok(ProofResponse {
+			data_proof,
+			message,
+		})
+	}
+
+	fn get_header(&self, at: Block::Hash) -> RpcResult<Block::Header> {
+		self.client
+			.header(at)
+			.map_err(|e| internal_err!("Invalid block number: {:?}", e))?
+			.ok_or_else(|| internal_err!("Missing block {}", at))
+	}
+
+	/// Returns the block `at` after checking that it has the requested `finality`.
+	fn get_signed_block_with(
 		&self,
 		at: Option<Block::Hash>,
+		finality: Finality,
 	) -> RpcResult<SignedBlock<Block>> {
-		let signed_block = self.get_signed_block(at)?;
-		self.is_block_finalized(&signed_block)?;
-		Ok(signed_block)
+		let at = self.resolve_block(at, finality)?;
+		self.get_signed_block(Some(at))
+	}
+
+	/// Loads the calls, their callers and the bridge nonce used to build the data proofs of
+	/// `block`.
+	///
+	/// Calls of failed extrinsics are replaced by an empty remark, so they are not part of the
+	/// data tries.
+	#[allow(clippy::type_complexity)]
+	fn data_proof_calls(
+		&self,
+		block: &Block,
+		at: Option<Block::Hash>,
+	) -> RpcResult<(Vec<RuntimeCall>, Vec<AccountId32>, u64)> {
+		// We can't query DataProofV2 on older blocks which has a V1 header
+		if let HeaderExtension::V1(_) = block.header().extension() {
+			return Err(internal_err!(
+				"The block {:?} has V1 header, which doesn't support DataProofV2",
+				at
+			));
+		}
+
+		let successfull_indices = self
+			.client
+			.runtime_api()
+			.successful_extrinsic_indices(block.hash())
+			.map_err(|e| {
+				internal_err!("Failed to fetch successfull indices at ({:?}): {:?}", at, e)
+			})?;
+
+		let extrinsics = block
+			.extrinsics()
+			.iter()
+			.enumerate()
+			.flat_map(|(index, extrinsic)| {
+				UncheckedExtrinsic::try_from(extrinsic.clone())
+					.ok()
+					.map(|unchecked_extrinsic| (index, unchecked_extrinsic))
+			})
+			.collect::<Vec<_>>();
+
+		let callers: Vec<AccountId32> = extrinsics
+			.iter()
+			.map(
+				|(_, extrinsic)| match extrinsic.signature.as_ref().map(|s| &s.0) {
+					Some(MultiAddress::Id(id)) => id.clone(),
+					_ => AccountId32::new([0u8; 32]),
+				},
+			)
+			.collect();
+
+		let calls = extrinsics
+			.into_iter()
+			.map(|(index, unchecked_extrinsic)| {
+				if successfull_indices.contains(&(index as u32)) {
+					unchecked_extrinsic.function
+				} else {
+					// Some dummy Call
+					RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+				}
+			})
+			.collect();
+
+		let bridge_nonce = self
+			.client
+			.runtime_api()
+			.bridge_nonce(*block.header().parent_hash())
+			.map_err(|e| internal_err!("Failed to fetch bridge_nonce at ({:?}): {:?}", at, e))?;
+
+		Ok((calls, callers, bridge_nonce))
 	}
 
 	/// If feature `secure_padding_fill` is enabled then the returned seed is generated using Babe VRF.
@@ -215,69 +777,324 @@ where
 		}
 	}
 
-	/// The signed_block needs to be finalized.
-	async fn get_eval_grid(
+	/// Loads the evaluation grid of `block_hash` from the disk cache, if it is enabled. The file
+	/// is read and checked by a blocking task.
+	///
+	/// Entries of blocks which are not finalized on the canonical chain are discarded.
+	async fn load_persisted_grid(&self, block_hash: Block::Hash) -> Option<EvaluationGrid> {
+		let disk_cache = self.grid_disk_cache.clone()?;
+		let client = self.client.clone();
+		let load = move || {
+			disk_cache.get(block_hash.as_ref(), |number| {
+				let info = client.info();
+				let number = NumberFor::<Block>::from(number);
+				number <= info.finalized_number
+					&& client.hash(number).ok().flatten() == Some(block_hash)
+			})
+		};
+		self.run_blocking("kate-rpc-grid-load", load)
+			.await
+			.ok()
+			.flatten()
+	}
+
+	/// Removes the persisted grids of the finalized block numbers whose block is not canonical,
+	/// see [`GridDiskCache::prune`].
+	pub async fn prune_persisted_grids(&self) {
+		let Some(disk_cache) = self.grid_disk_cache.clone() else {
+			return;
+		};
+		let client = self.client.clone();
+		let prune = move || {
+			let finalized_number = client.info().finalized_number.saturated_into::<u32>();
+			disk_cache.prune(finalized_number, |number| {
+				let hash = client.hash(NumberFor::<Block>::from(number)).ok()??;
+				Some(hash.as_ref().to_vec())
+			})
+		};
+		let _ = self.run_blocking("kate-rpc-grid-prune", prune).await;
+	}
+
+	/// Persists the evaluation grid of `block_hash` in background, if the disk cache is enabled.
+	fn persist_grid(
 		&self,
-		signed_block: &SignedBlock<Block>,
-	) -> RpcResult<Arc<EvaluationGrid>> {
+		block_hash: Block::Hash,
+		block_number: NumberFor<Block>,
+		grid: Arc<EvaluationGrid>,
+	) {
+		let Some(disk_cache) = self.grid_disk_cache.clone() else {
+			return;
+		};
+		let block_number = block_number.saturated_into::<u32>();
+		let fut = async move {
+			disk_cache.insert(block_hash.as_ref(), block_number, &grid);
+		};
+		self.executor
+			.spawn_blocking("kate-rpc-grid-persist", Some("rpc"), fut.boxed());
+	}
+
+	/// The signed_block needs to be finalized.
+	async fn get_eval_grid(&self, signed_block: &SignedBlock<Block>) -> RpcResult<EvalGrid> {
 		let block_header = signed_block.block.header();
 		let block_hash = block_header.hash();
+		let block_number = *block_header.number();
 
-		self.eval_grid_cache
-			.try_get_with(block_hash, async move {
-				// build block data extension and cache it
-				let xts_by_id: Vec<AppExtrinsic> = signed_block
-					.block
-					.extrinsics()
-					.iter()
-					.cloned()
-					.filter_map(|opaque| UncheckedExtrinsic::try_from(opaque).ok())
-					.map(AppExtrinsic::from)
-					.collect();
-
-				// Use Babe's VRF
-				let seed = self.get_seed(block_hash)?;
-				let block_length: BlockLength = self
-					.client
-					.runtime_api()
-					.block_length(block_hash)
-					.map_err(|e| internal_err!("Block Length cannot be fetched: {:?}", e))?;
-
-				let mut evals = kate::gridgen::EvaluationGrid::from_extrinsics(
-					xts_by_id.clone(),
-					4,
-					block_length.cols.0.try_into().expect("TODO"), // 'cols' is the # of cols, so width
-					block_length.rows.0.try_into().expect("TODO"), // 'rows' is the # of rows, so height
-					seed,
+		let cache = self.grid_caches.eval();
+		grid_cache::get_or_build(&cache, grid_cache::EVAL_GRID, block_hash, async move {
+			if let Some(evals) = self.load_persisted_grid(block_hash).await {
+				return Ok(Arc::new(evals));
+			}
+
+			// build block data extension and cache it
+			let xts_by_id: Vec<AppExtrinsic> = signed_block
+				.block
+				.extrinsics()
+				.iter()
+				.cloned()
+				.filter_map(|opaque| UncheckedExtrinsic::try_from(opaque).ok())
+				.map(AppExtrinsic::from)
+				.collect();
+
+			// Use Babe's VRF
+			let seed = self.get_seed(block_hash)?;
+			let block_length: BlockLength = self
+				.client
+				.runtime_api()
+				.block_length(block_hash)
+				.map_err(|e| internal_err!("Block Length cannot be fetched: {:?}", e))?;
+
+			let mut evals = kate::gridgen::EvaluationGrid::from_extrinsics(
+				xts_by_id.clone(),
+				4,
+				block_length.cols.0.try_into().expect("TODO"), // 'cols' is the # of cols, so width
+				block_length.rows.0.try_into().expect("TODO"), // 'rows' is the # of rows, so height
+				seed,
+			)
+			.map_err(|e| internal_err!("Building evals grid failed: {:?}", e))?;
+
+			evals = evals
+				.extend_columns(NonZeroU16::new(2).expect("2>0"))
+				.map_err(|e| internal_err!("Error extending grid {:?}", e))?;
+
+			let evals = Arc::new(evals);
+			self.persist_grid(block_hash, block_number, evals.clone());
+
+			Ok::<_, JsonRpseeError>(evals)
+		})
+		.await
+		.map_err(|e: Arc<_>| internal_err!("failed to construct block: {}", e)) // Deref the arc into a reference, clone the ref
+	}
+
+	/// Builds and caches the evaluation and polynomial grids of the block `at`, so the following
+	/// queries on that block hit the caches.
+	pub async fn prewarm_grids(&self, at: Block::Hash) -> RpcResult<()> {
+		let signed_block = self.get_signed_block_with(Some(at), Finality::Finalized)?;
+		// The polynomial grid is built from the evaluation grid, so both get cached.
+		self.get_poly_grid(&signed_block).await?;
+		Ok(())
+	}
+
+	/// Adds the blobs of the finalized block `at` to the blob index.
+	///
+	/// It reads the block and writes the index, so it should run on a blocking task.
+	pub fn index_blobs(&self, at: Block::Hash) -> RpcResult<()> {
+		let Some(blob_index) = self.blob_index.as_ref() else {
+			return Ok(());
+		};
+		let signed_block = self.get_signed_block_with(Some(at), Finality::Finalized)?;
+		let block_number: u32 = (*signed_block.block.header().number()).saturated_into();
+
+		let blobs = self.submitted_blobs(&signed_block.block)?.into_iter().map(
+			|(transaction_index, data)| {
+				let location = BlobLocation {
+					block_hash: at.as_ref().to_vec(),
+					block_number,
+					transaction_index,
+				};
+				(blake2_256(&data), location)
+			},
+		);
+		blob_index.insert(blobs);
+
+		Ok(())
+	}
+
+	/// Returns the data of the successful `submit_data` and `submit_data_chunk` extrinsics of
+	/// `block`, with their extrinsic index.
+	fn submitted_blobs(&self, block: &Block) -> RpcResult<Vec<(u32, Vec<u8>)>> {
+		let successfull_indices = self
+			.client
+			.runtime_api()
+			.successful_extrinsic_indices(block.hash())
+			.map_err(|e| {
+				internal_err!(
+					"Failed to fetch successfull indices at ({:?}): {:?}",
+					block.hash(),
+					e
 				)
-				.map_err(|e| internal_err!("Building evals grid failed: {:?}", e))?;
+			})?;
+
+		let blobs = block
+			.extrinsics()
+			.iter()
+			.enumerate()
+			.filter(|(index, _)| successfull_indices.contains(&(*index as u32)))
+			.filter_map(|(index, extrinsic)| {
+				match UncheckedExtrinsic::try_from(extrinsic.clone())
+					.ok()?
+					.function
+				{
+					RuntimeCall::DataAvailability(
+						da_control::Call::submit_data { data }
+						| da_control::Call::submit_data_chunk { data },
+					) => Some((index as u32, data.into_inner())),
+					_ => None,
+				}
+			})
+			.collect();
 
-				evals = evals
-					.extend_columns(NonZeroU16::new(2).expect("2>0"))
-					.map_err(|e| internal_err!("Error extending grid {:?}", e))?;
+		Ok(blobs)
+	}
 
-				Ok::<_, JsonRpseeError>(Arc::new(evals))
+	/// Returns the blob `data_hash` submitted by the extrinsic `transaction_index` of
+	/// `block_hash`.
+	fn blob_data(
+		&self,
+		data_hash: Block::Hash,
+		block_hash: Block::Hash,
+		transaction_index: u32,
+	) -> RpcResult<Vec<u8>> {
+		// The indexed transaction storage keeps the blob even if the block body is pruned.
+		let indexed = self
+			.client
+			.indexed_transaction(data_hash)
+			.map_err(|e| internal_err!("Failed to fetch indexed blob {data_hash:?}: {e:?}"))?;
+		if let Some(data) = indexed {
+			return Ok(data);
+		}
+
+		let block = self.get_signed_block(Some(block_hash))?.block;
+		self.submitted_blobs(&block)?
+			.into_iter()
+			.find(|(index, _)| *index == transaction_index)
+			.map(|(_, data)| data)
+			.filter(|data| blake2_256(data) == data_hash.as_ref())
+			.ok_or_else(|| internal_err!("Blob {data_hash:?} is not available"))
+	}
+
+	/// Returns the block hash and the extrinsic index of the newest canonical submission of
+	/// `data_hash` in the blob index.
+	fn indexed_blob_location(&self, data_hash: &[u8]) -> RpcResult<(Block::Hash, u32)> {
+		let blob_index = self.blob_index.as_ref().ok_or_else(|| {
+			internal_err!("Blob index is disabled, the block of the blob is required")
+		})?;
+
+		blob_index
+			.get(data_hash)
+			.into_iter()
+			.find_map(|location| {
+				let number = location.block_number.saturated_into();
+				let hash = self.client.hash(number).ok().flatten()?;
+				(hash.as_ref() == location.block_hash.as_slice())
+					.then_some((hash, location.transaction_index))
 			})
-			.await
-			.map_err(|e: Arc<_>| internal_err!("failed to construct block: {}", e)) // Deref the arc into a reference, clone the ref
+			.ok_or_else(|| internal_err!("Blob 0x{} not found", HexDisplay::from(&data_hash)))
 	}
 
-	// TODO: We should probably have a metrics item for this
-	async fn get_poly_grid(
+	/// Returns the original (non extended) rows of `app_id`, or `None` if the block does not
+	/// contain any data of that application.
+	async fn get_app_rows(
 		&self,
+		app_id: AppId,
 		signed_block: &SignedBlock<Block>,
-	) -> RpcResult<Arc<(Dimensions, PolynomialGrid)>> {
-		let block_hash = signed_block.block.header().hash();
-		self.poly_grid_cache
-			.try_get_with(block_hash, async move {
-				let evals = self.get_eval_grid(signed_block).await?;
-				let polys = evals
-					.make_polynomial_grid()
-					.map_err(|e| internal_err!("Error getting polynomial grid {:?}", e))?;
-				Ok::<_, JsonRpseeError>(Arc::new((evals.dims(), polys)))
-			})
-			.await
-			.map_err(|e: Arc<_>| internal_err!("failed to construct block: {}", e)) // Deref the arc into a reference, clone the ref
+	) -> RpcResult<Option<Vec<Option<Vec<u8>>>>> {
+		let evals = self.get_eval_grid(signed_block).await?;
+
+		let extended_dims = evals.dims();
+		let orig_dims = non_extended_dimensions(extended_dims)?;
+
+		let rows = evals
+			.app_rows(app_id, Some(orig_dims))
+			.map_err(|e| internal_err!("Failed to get app rows: {:?}", e))?;
+		let Some(rows) = rows else {
+			return Ok(None);
+		};
+
+		let mut div = 1;
+		if extended_dims.height() == 2 * orig_dims.height() {
+			div = 2;
+		}
+
+		let mut all_rows = vec![None; orig_dims.height()];
+		for (mut row_y, row) in rows {
+			row_y /= div;
+			all_rows[row_y] = Some(
+				row.into_iter()
+					.flat_map(|s| s.to_bytes().expect("Ser cannot fail"))
+					.collect::<Vec<u8>>(),
+			);
+		}
+
+		Ok(Some(all_rows))
+	}
+
+	/// Builds the `kate_subscribeAppData` notification of `app_id` for the finalized block `at`.
+	async fn app_data_notification(
+		&self,
+		app_id: AppId,
+		at: Block::Hash,
+	) -> RpcResult<AppDataNotification<Block::Hash, NumberFor<Block>>> {
+		let execution_start = std::time::Instant::now();
+
+		let signed_block = self.get_signed_block(Some(at))?;
+		let number = *signed_block.block.header().number();
+		let notification = match self.get_app_rows(app_id, &signed_block).await? {
+			Some(rows) => AppDataNotification::Data {
+				block_hash: at,
+				number,
+				rows,
+			},
+			None => AppDataNotification::Empty {
+				block_hash: at,
+				number,
+			},
+		};
+
+		// Execution Time Metric
+		KateRpcMetrics::observe_subscribe_app_data_execution_time(execution_start.elapsed());
+
+		Ok(notification)
+	}
+
+	/// Builds the `kate_subscribeAppData` notification of `app_id` for the finalized block `at`,
+	/// turning a failure into an error notification instead of ending the subscription.
+	async fn app_data_notification_or_error(
+		&self,
+		app_id: AppId,
+		at: Block::Hash,
+	) -> AppDataNotification<Block::Hash, NumberFor<Block>> {
+		match self.app_data_notification(app_id, at).await {
+			Ok(notification) => notification,
+			Err(e) => AppDataNotification::Error {
+				block_hash: at,
+				number: self.client.number(at).ok().flatten(),
+				message: e.to_string(),
+			},
+		}
+	}
+
+	async fn get_poly_grid(&self, signed_block: &SignedBlock<Block>) -> RpcResult<PolyGrid> {
+		let block_hash = signed_block.block.header().hash();
+		let cache = self.grid_caches.poly();
+		grid_cache::get_or_build(&cache, grid_cache::POLY_GRID, block_hash, async move {
+			let evals = self.get_eval_grid(signed_block).await?;
+			let polys = evals
+				.make_polynomial_grid()
+				.map_err(|e| internal_err!("Error getting polynomial grid {:?}", e))?;
+			Ok::<_, JsonRpseeError>(Arc::new((evals.dims(), polys)))
+		})
+		.await
+		.map_err(|e: Arc<_>| internal_err!("failed to construct block: {}", e)) // Deref the arc into a reference, clone the ref
 	}
 }
 
@@ -292,13 +1109,23 @@ where
 		HeaderExtension,
 	>,
 	Client: Send + Sync + 'static,
-	Client: HeaderBackend<Block> + ProvideRuntimeApi<Block> + BlockBackend<Block>,
+	Client: HeaderBackend<Block>
+		+ ProvideRuntimeApi<Block>
+		+ BlockBackend<Block>
+		+ BlockchainEvents<Block>,
 	Client::Api: DataAvailApi<Block>,
 {
-	async fn query_rows(&self, rows: Rows, at: Option<HashOf<Block>>) -> RpcResult<Vec<Vec<u8>>> {
+	async fn query_rows(
+		&self,
+		rows: Rows,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<Vec<Vec<u8>>> {
+		self.charge(KateCall::Rows(rows.len()))?;
 		let execution_start = std::time::Instant::now();
 
-		let signed_block = self.get_signed_and_finalized_block(at)?;
+		let signed_block = self
+			.get_signed_block_with(at, QueryOptions::finality_or(options, Finality::Finalized))?;
 		let evals = self.get_eval_grid(&signed_block).await?;
 
 		let mut data_rows = Vec::with_capacity(rows.len());
@@ -320,40 +1147,67 @@ where
 		Ok(data_rows)
 	}
 
-	async fn query_app_data(
+	async fn query_rows_v2(
 		&self,
-		app_id: AppId,
+		rows: Rows,
 		at: Option<HashOf<Block>>,
-	) -> RpcResult<Vec<Option<Vec<u8>>>> {
+		options: Option<QueryOptions>,
+	) -> RpcResult<RowsResponse> {
+		let indexes = rows.to_vec();
+		let rows = self.query_rows(rows, at, options).await?;
+
+		Ok(RowsResponse::new(&indexes, rows))
+	}
+
+	async fn query_columns(
+		&self,
+		columns: Columns,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<Vec<Vec<u8>>> {
+		self.charge(KateCall::Columns(columns.len()))?;
 		let execution_start = std::time::Instant::now();
 
-		let signed_block = self.get_signed_and_finalized_block(at)?;
+		let signed_block = self
+			.get_signed_block_with(at, QueryOptions::finality_or(options, Finality::Finalized))?;
 		let evals = self.get_eval_grid(&signed_block).await?;
+		let height = evals.dims().height();
+
+		let mut data_columns = Vec::with_capacity(columns.len());
+		for index in columns {
+			let col = index as usize;
+			let data = (0..height)
+				.map(|row| evals.get::<usize, usize>(row, col))
+				.collect::<Option<Vec<_>>>()
+				.ok_or_else(|| internal_err!("Non existing column: {:?}", index))?;
+			let data: Vec<u8> = data
+				.iter()
+				.flat_map(|a| a.to_bytes().expect("Ser cannot fail"))
+				.collect();
 
-		let extended_dims = evals.dims();
-		let orig_dims = non_extended_dimensions(extended_dims)?;
+			data_columns.push(data);
+		}
 
-		let rows = evals
-			.app_rows(app_id, Some(orig_dims))
-			.map_err(|e| internal_err!("Failed to get app rows: {:?}", e))?;
-		let Some(rows) = rows else {
-			return Err(internal_err!("No rows found"));
-		};
+		// Execution Time Metric
+		KateRpcMetrics::observe_query_columns_execution_time(execution_start.elapsed());
 
-		let mut div = 1;
-		if extended_dims.height() == 2 * orig_dims.height() {
-			div = 2;
-		}
+		Ok(data_columns)
+	}
 
-		let mut all_rows = vec![None; orig_dims.height()];
-		for (mut row_y, row) in rows {
-			row_y /= div;
-			all_rows[row_y] = Some(
-				row.into_iter()
-					.flat_map(|s| s.to_bytes().expect("Ser cannot fail"))
-					.collect::<Vec<u8>>(),
-			);
-		}
+	async fn query_app_data(
+		&self,
+		app_id: AppId,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<Vec<Option<Vec<u8>>>> {
+		self.charge(KateCall::AppData)?;
+		let execution_start = std::time::Instant::now();
+
+		let signed_block = self
+			.get_signed_block_with(at, QueryOptions::finality_or(options, Finality::Finalized))?;
+		let Some(all_rows) = self.get_app_rows(app_id, &signed_block).await? else {
+			return Err(internal_err!("No rows found"));
+		};
 
 		// Execution Time Metric
 		KateRpcMetrics::observe_query_app_data_execution_time(execution_start.elapsed());
@@ -361,7 +1215,12 @@ where
 		Ok(all_rows)
 	}
 
-	async fn query_proof(&self, cells: Cells, at: Option<HashOf<Block>>) -> RpcResult<Vec<u8>> {
+	async fn query_proof(
+		&self,
+		cells: Cells,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<Vec<u8>> {
 		use crate::JsonRpseeError::Custom;
 
 		if cells.len() > self.max_cells_size {
@@ -372,10 +1231,12 @@ where
 			));
 			return Err(err);
 		}
+		self.charge(KateCall::Proof(cells.len()))?;
 
 		let execution_start = std::time::Instant::now();
 
-		let signed_block = self.get_signed_and_finalized_block(at)?;
+		let signed_block = self
+			.get_signed_block_with(at, QueryOptions::finality_or(options, Finality::Finalized))?;
 		let evals = self.get_eval_grid(&signed_block).await?;
 		let polys = self.get_poly_grid(&signed_block).await?;
 
@@ -411,10 +1272,134 @@ where
 		Ok(proof)
 	}
 
-	async fn query_block_length(&self, at: Option<HashOf<Block>>) -> RpcResult<BlockLength> {
+	async fn query_proof_v2(
+		&self,
+		cells: Cells,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<ProofsResponse> {
+		let positions = cells
+			.iter()
+			.map(|cell| GCellPosition {
+				row: cell.row.0,
+				col: cell.col.0,
+			})
+			.collect::<Vec<_>>();
+		let proof = self.query_proof(cells, at, options).await?;
+
+		ProofsResponse::new(&positions, &proof)
+			.ok_or_else(|| internal_err!("Invalid proof length {}", proof.len()))
+	}
+
+	async fn reconstruct_app_rows(
+		&self,
+		app_id: AppId,
+		cells: DataCells,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<Vec<Option<Vec<u8>>>> {
+		self.charge(KateCall::Reconstruction(cells.len()))?;
 		let execution_start = std::time::Instant::now();
 
-		let at = self.at_or_best(at);
+		let at = self.resolve_block(at, QueryOptions::finality_or(options, Finality::Any))?;
+		let header = self.get_header(at)?;
+
+		let cells = cells
+			.into_iter()
+			.map(|cell| {
+				let data = cell.scalar.as_slice().try_into().map_err(|_| {
+					internal_err!("Invalid scalar of cell ({}, {})", cell.row, cell.col)
+				})?;
+				let position = Position {
+					row: cell.row,
+					col: cell.col,
+				};
+				Ok(DataCell { position, data })
+			})
+			.collect::<RpcResult<Vec<_>>>()?;
+
+		let rows = reconstruction::reconstruct_app_rows(
+			&self.multiproof_srs,
+			header.extension(),
+			app_id,
+			&cells,
+		)
+		.map_err(|e| internal_err!("Data of block {} cannot be reconstructed: {:?}", at, e))?;
+
+		// Execution Time Metric
+		KateRpcMetrics::observe_reconstruct_app_rows_execution_time(execution_start.elapsed());
+
+		Ok(rows)
+	}
+
+	async fn query_multiproof(
+		&self,
+		cells: Cells,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<Vec<GMultiProof>> {
+		if cells.len() > self.max_cells_size {
+			return Err(internal_err!(
+				"Cannot query ({}) more than {} amount of cells per request. Either increase the max cells size (--kate-max-cells-size) or query less amount of cells per request.",
+				cells.len(),
+				self.max_cells_size
+			));
+		}
+		self.charge(KateCall::MultiProof(cells.len()))?;
+
+		let execution_start = std::time::Instant::now();
+
+		let signed_block = self
+			.get_signed_block_with(at, QueryOptions::finality_or(options, Finality::Finalized))?;
+		let evals = self.get_eval_grid(&signed_block).await?;
+		let polys = self.get_poly_grid(&signed_block).await?;
+
+		// Cells in the same tile share one multiproof, so only the first cell of each tile is kept.
+		let target_dims = multiproof::target_dimensions();
+		let mut tiles = BTreeMap::new();
+		for cell in cells.iter() {
+			let (Ok(row), Ok(col)) = (usize::try_from(cell.row.0), usize::try_from(cell.col.0))
+			else {
+				return Err(internal_err!("cell {:?} did not fit in usize", cell));
+			};
+			let Some(block) = multiproof_block(col, row, evals.dims(), target_dims) else {
+				let e = internal_err!("Invalid cell {:?} for dims {:?}", cell, evals.dims());
+				return Err(e);
+			};
+			tiles
+				.entry(GCellBlock::from(block))
+				.or_insert_with(|| cell.clone());
+		}
+
+		let proofs = tiles
+			.into_values()
+			.collect::<Vec<_>>()
+			.par_iter()
+			.map(|cell| {
+				let mp = polys
+					.1
+					.multiproof(&self.multiproof_srs, cell, &evals, target_dims)
+					.map_err(|e| internal_err!("Unable to make multiproof: {:?}", e))?;
+				GMultiProof::try_from(mp)
+					.map_err(|e| internal_err!("Unable to serialize multiproof: {:?}", e))
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		// Execution Time Metric
+		KateRpcMetrics::observe_query_multiproof_execution_time(execution_start.elapsed());
+
+		Ok(proofs)
+	}
+
+	async fn query_block_length(
+		&self,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<BlockLength> {
+		self.charge(KateCall::BlockLength)?;
+		let execution_start = std::time::Instant::now();
+
+		let at = self.resolve_block(at, QueryOptions::finality_or(options, Finality::Any))?;
 		let api = self.client.runtime_api();
 		let block_length = api
 			.block_length(at)
@@ -426,14 +1411,36 @@ where
 		Ok(block_length)
 	}
 
+	async fn query_block_da_info(
+		&self,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<BlockDaInfo<HashOf<Block>>> {
+		self.charge(KateCall::BlockDaInfo)?;
+		let execution_start = std::time::Instant::now();
+
+		let at = self.resolve_block(at, QueryOptions::finality_or(options, Finality::Any))?;
+		let header = self.get_header(at)?;
+		let da_info = BlockDaInfo::new(at, header.extension());
+
+		// Execution Time Metric
+		KateRpcMetrics::observe_query_block_da_info_execution_time(execution_start.elapsed());
+
+		Ok(da_info)
+	}
+
 	async fn query_data_proof(
 		&self,
 		transaction_index: u32,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<DataProof> {
+		self.charge(KateCall::DataProof)?;
 		let execution_start = std::time::Instant::now();
 
-		let block = self.get_signed_block(at)?.block;
+		let block = self
+			.get_signed_block_with(at, QueryOptions::finality_or(options, Finality::Any))?
+			.block;
 		// We can quey data_proof only on V1 headers
 		if let HeaderExtension::V1(_) = block.header().extension() {
 			let calls = block
@@ -474,119 +1481,288 @@ where
 		&self,
 		transaction_index: u32,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<ProofResponse> {
+		self.charge(KateCall::DataProofV2)?;
 		let execution_start = std::time::Instant::now();
 
-		let block = self.get_signed_block(at)?.block;
-		// We can't query DataProofV2 on older blocks which has a V1 header
-		if let HeaderExtension::V1(_) = block.header().extension() {
-			return Err(internal_err!(
-				"The block {:?} has V1 header, which doesn't support DataProofV2",
-				at
-			));
-		}
+		let at = self.resolve_block(at, QueryOptions::finality_or(options, Finality::Any))?;
+		let response = self.data_proof_v2(transaction_index, Some(at))?;
 
-		let successfull_indices = self
-			.client
-			.runtime_api()
-			.successful_extrinsic_indices(block.hash())
-			.map_err(|e| {
-				internal_err!("Failed to fetch successfull indices at ({:?}): {:?}", at, e)
-			})?;
+		// Execution Time Metric
+		KateRpcMetrics::observe_query_data_proof_v2_execution_time(execution_start.elapsed());
 
-		let calls = block
-			.extrinsics()
-			.iter()
-			.enumerate()
-			.flat_map(|(index, extrinsic)| {
-				UncheckedExtrinsic::try_from(extrinsic.clone())
-					.ok()
-					.map(|unchecked_extrinsic| {
-						if successfull_indices.contains(&(index as u32)) {
-							unchecked_extrinsic.function
-						} else {
-							// Some dummy Call
-							RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
-						}
-					})
-			});
+		Ok(response)
+	}
 
-		let callers: Vec<AccountId32> = block
-			.extrinsics()
+	async fn query_all_data_proofs_v2(
+		&self,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<Vec<(u32, ProofResponse)>> {
+		self.charge(KateCall::AllDataProofsV2)?;
+		let execution_start = std::time::Instant::now();
+
+		let block = self
+			.get_signed_block_with(at, QueryOptions::finality_or(options, Finality::Any))?
+			.block;
+		let (calls, callers, bridge_nonce) = self.data_proof_calls(&block, at)?;
+
+		// Only direct `submit_data`, `submit_data_chunk` and `send_message` calls are provable,
+		// like in `kate_queryDataProofV2`.
+		let call_types = calls
 			.iter()
-			.flat_map(|extrinsic| UncheckedExtrinsic::try_from(extrinsic).ok())
-			.map(
-				|extrinsic| match extrinsic.signature.as_ref().map(|s| &s.0) {
-					Some(MultiAddress::Id(id)) => id.clone(),
-					_ => AccountId32::new([0u8; 32]),
+			.map(|call| match call {
+				RuntimeCall::DataAvailability(
+					da_control::Call::submit_data { .. }
+					| da_control::Call::submit_data_chunk { .. },
+				) => Some(SubTrie::Left),
+				RuntimeCall::Vector(pallet_vector::Call::send_message { .. }) => {
+					Some(SubTrie::Right)
 				},
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+
+		let proofs = submitted_data::calls_proofs_v2::<Runtime, _, _>(
+			calls.into_iter(),
+			callers,
+			bridge_nonce,
+		)
+		.into_iter()
+		.filter(|call_proof| {
+			matches!(
+				(
+					call_types.get(call_proof.transaction_index as usize),
+					&call_proof.sub_trie
+				),
+				(Some(Some(SubTrie::Left)), SubTrie::Left)
+					| (Some(Some(SubTrie::Right)), SubTrie::Right)
 			)
-			.collect();
+		})
+		.map(|call_proof| {
+			let root_side = match call_proof.sub_trie {
+				SubTrie::Left => SubTrie::Right,
+				SubTrie::Right => SubTrie::Left,
+			};
+			let data_proof =
+				DataProofV2::try_from((&call_proof.proof, call_proof.sibling_root, root_side))
+					.map_err(|e| {
+						internal_err!("Data proof cannot be loaded from merkle root: {:?}", e)
+					})?;
+
+			Ok((
+				call_proof.transaction_index,
+				ProofResponse {
+					data_proof,
+					message: call_proof.message,
+				},
+			))
+		})
+		.collect::<RpcResult<Vec<_>>>()?;
 
-		let bridge_nonce = self
-			.client
-			.runtime_api()
-			.bridge_nonce(*block.header().parent_hash())
-			.map_err(|e| internal_err!("Failed to fetch bridge_nonce at ({:?}): {:?}", at, e))?;
+		// Execution Time Metric
+		KateRpcMetrics::observe_query_all_data_proofs_v2_execution_time(execution_start.elapsed());
 
-		let transaction_call = calls
-			.clone()
-			.nth(transaction_index as usize)
-			.ok_or_else(|| {
-				internal_err!(
-					"Cannot to fetch transaction call at index {:?}: {:?}",
-					transaction_index,
-					at
-				)
-			})?;
+		Ok(proofs)
+	}
 
-		let call_type: SubTrie;
-		let root_side: SubTrie;
-		match transaction_call {
-			RuntimeCall::DataAvailability(da_control::Call::submit_data { .. }) => {
-				call_type = SubTrie::Left;
-				root_side = SubTrie::Right;
-			},
-			RuntimeCall::Vector(pallet_vector::Call::send_message { .. }) => {
-				call_type = SubTrie::Right;
-				root_side = SubTrie::Left;
-			},
-			_ => {
-				return Err(internal_err!(
-					"Data proof cannot be generated for transaction index={} at block {:?}",
-					transaction_index,
-					at
-				));
+	async fn get_blob_by_hash(
+		&self,
+		data_hash: HashOf<Block>,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<BlobResponse<HashOf<Block>, NumberFor<Block>>> {
+		self.charge(KateCall::BlobByHash)?;
+		let execution_start = std::time::Instant::now();
+
+		let (block_hash, transaction_index) = match at {
+			Some(at) => {
+				let finality = QueryOptions::finality_or(options, Finality::Any);
+				let at = self.resolve_block(Some(at), finality)?;
+				let block = self.get_signed_block(Some(at))?.block;
+				let transaction_index = self
+					.submitted_blobs(&block)?
+					.into_iter()
+					.find(|(_, data)| blake2_256(data) == data_hash.as_ref())
+					.map(|(index, _)| index)
+					.ok_or_else(|| internal_err!("Blob {data_hash:?} not found in block {at:?}"))?;
+				(at, transaction_index)
 			},
-		}
+			None => self.indexed_blob_location(data_hash.as_ref())?,
+		};
 
-		// Build the proof.
-		let (proof, root, message) = submitted_data::calls_proof_v2::<Runtime, _, _>(
-			calls,
-			callers,
+		let block_number = self
+			.client
+			.number(block_hash)
+			.map_err(|e| internal_err!("Invalid block number: {:?}", e))?
+			.ok_or_else(|| internal_err!("Missing block {}", block_hash))?;
+
+		let data = self.blob_data(data_hash, block_hash, transaction_index)?;
+
+		let proof = self.data_proof_v2(transaction_index, Some(block_hash)).ok();
+
+		// Execution Time Metric
+		KateRpcMetrics::observe_get_blob_by_hash_execution_time(execution_start.elapsed());
+
+		Ok(BlobResponse {
+			block_hash,
+			block_number,
 			transaction_index,
-			bridge_nonce,
-			call_type,
-		)
-		.ok_or_else(|| {
+			data,
+			proof,
+		})
+	}
+
+	async fn get_assembled_blob(
+		&self,
+		blob_root: HashOf<Block>,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<AssembledBlobResponse<HashOf<Block>, NumberFor<Block>>> {
+		let execution_start = std::time::Instant::now();
+
+		let at = self.resolve_block(at, QueryOptions::finality_or(options, Finality::Any))?;
+		let manifest = self
+			.client
+			.runtime_api()
+			.blob_manifest(at, H256::from_slice(blob_root.as_ref()))
+			.map_err(|e| {
+				internal_err!("Failed to fetch the manifest of blob {blob_root:?} at {at:?}: {e:?}")
+			})?
+			.ok_or_else(|| internal_err!("Blob {blob_root:?} is not assembled at {at:?}"))?;
+		self.charge(KateCall::AssembledBlob(manifest.chunks.len()))?;
+
+		let mut data = Vec::with_capacity(manifest.total_len.saturated_into());
+		let mut chunks = Vec::with_capacity(manifest.chunks.len());
+		let mut data_hashes = Vec::with_capacity(manifest.chunks.len());
+		for chunk in manifest.chunks {
+			let block_number: NumberFor<Block> = chunk.block_number.saturated_into();
+			let block_hash = self
+				.client
+				.hash(block_number)
+				.map_err(|e| internal_err!("Invalid block number: {:?}", e))?
+				.ok_or_else(|| internal_err!("Missing block {}", block_number))?;
+			let data_hash = Block::Hash::decode(&mut chunk.data_hash.as_bytes())
+				.map_err(|e| internal_err!("Invalid chunk hash {:?}: {e:?}", chunk.data_hash))?;
+
+			let chunk_data = self.blob_data(data_hash, block_hash, chunk.extrinsic_index)?;
+			data_hashes.push(H256(blake2_256(&chunk_data)));
+			data.extend(chunk_data);
+			let proof = self
+				.data_proof_v2(chunk.extrinsic_index, Some(block_hash))
+				.ok();
+			chunks.push(BlobChunkResponse {
+				block_hash,
+				block_number,
+				transaction_index: chunk.extrinsic_index,
+				data_hash,
+				len: chunk.len,
+				proof,
+			});
+		}
+
+		let root = da_control::blob_root(&data_hashes);
+		if data.len() as u64 != manifest.total_len || root.as_bytes() != blob_root.as_ref() {
+			return Err(internal_err!(
+				"Chunks of blob {blob_root:?} do not match its root"
+			));
+		}
+
+		// Execution Time Metric
+		KateRpcMetrics::observe_get_assembled_blob_execution_time(execution_start.elapsed());
+
+		Ok(AssembledBlobResponse {
+			blob_root,
+			manifest_block_hash: at,
+			owner: manifest.owner,
+			data,
+			chunks,
+		})
+	}
+
+	async fn query_extrinsic_proof(
+		&self,
+		transaction_index: u32,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<ExtrinsicProof<HashOf<Block>>> {
+		self.charge(KateCall::ExtrinsicProof)?;
+		let execution_start = std::time::Instant::now();
+
+		let block = self
+			.get_signed_block_with(at, QueryOptions::finality_or(options, Finality::Any))?
+			.block;
+		let extrinsics = block
+			.extrinsics()
+			.iter()
+			.map(Encode::encode)
+			.collect::<Vec<_>>();
+		let proof = extrinsic_proof(&extrinsics, transaction_index).ok_or_else(|| {
 			internal_err!(
-				"Data proof cannot be generated for transaction index={} at block {:?}",
+				"Extrinsic proof cannot be generated for transaction index={} at block {:?}",
 				transaction_index,
 				at
 			)
 		})?;
 
-		let data_proof = DataProofV2::try_from((&proof, root, root_side))
-			.map_err(|e| internal_err!("Data proof cannot be loaded from merkle root: {:?}", e))?;
+		let opaque = block.extrinsics()[transaction_index as usize].clone();
+		let (signer, app_id) = match UncheckedExtrinsic::try_from(opaque) {
+			Ok(extrinsic) => {
+				let signer = match extrinsic.signature.as_ref().map(|s| &s.0) {
+					Some(MultiAddress::Id(id)) => Some(id.clone()),
+					_ => None,
+				};
+				let app_id = extrinsic
+					.signature
+					.is_some()
+					.then(|| AppExtrinsic::from(extrinsic).app_id);
+				(signer, app_id)
+			},
+			Err(_) => (None, None),
+		};
 
 		// Execution Time Metric
-		KateRpcMetrics::observe_query_data_proof_v2_execution_time(execution_start.elapsed());
+		KateRpcMetrics::observe_query_extrinsic_proof_execution_time(execution_start.elapsed());
 
-		Ok(ProofResponse {
-			data_proof,
-			message,
+		Ok(ExtrinsicProof {
+			block_hash: block.hash(),
+			extrinsics_root: *block.header().extrinsics_root(),
+			transaction_index,
+			extrinsic: extrinsics[transaction_index as usize].clone(),
+			proof,
+			signer,
+			app_id,
 		})
 	}
+
+	fn subscribe_app_data(&self, mut sink: SubscriptionSink, app_id: AppId) -> SubscriptionResult {
+		let origin = rate_limit::current_origin();
+		if let Err(limited) = self.charge_origin(origin, KateCall::SubscribeAppData) {
+			let _ = sink.reject(rate_limited_error(KateCall::SubscribeAppData, limited));
+			return Ok(());
+		}
+		let kate = self.clone();
+
+		let notifications = finality::finalized_blocks(&*self.client)
+			.then(move |hash| {
+				let kate = kate.clone();
+				async move {
+					// The notification task does not run in the scope of the caller.
+					kate.throttle(origin, KateCall::AppData).await;
+					kate.app_data_notification_or_error(app_id, hash).await
+				}
+			})
+			.boxed();
+
+		let fut = async move {
+			sink.pipe_from_stream(notifications).await;
+		};
+		self.executor
+			.spawn("kate-rpc-subscription", Some("rpc"), fut.boxed());
+
+		Ok(())
+	}
 }
 
 fn non_extended_dimensions(ext_dims: Dimensions) -> RpcResult<Dimensions> {
@@ -1,4 +1,10 @@
-use crate::{Cells, HashOf, Kate, KateApiServer, ProofResponse, Rows};
+use crate::{
+	da_info::BlockDaInfo,
+	finality::QueryOptions,
+	v2::{ProofsResponse, RowsResponse},
+	AssembledBlobResponse, BlobResponse, Cells, Columns, DataCells, ExtrinsicProof, GMultiProof,
+	HashOf, Kate, KateApiServer, ProofResponse, Rows,
+};
 
 use avail_core::{
 	header::HeaderExtension, traits::ExtendedHeader, AppId, DataProof, OpaqueExtrinsic,
@@ -10,12 +16,12 @@ use jsonrpsee::{
 	core::{async_trait, RpcResult},
 	proc_macros::rpc,
 };
-use sc_client_api::BlockBackend;
+use sc_client_api::{BlockBackend, BlockchainEvents};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{
 	generic::Digest,
-	traits::{Block as BlockT, Header},
+	traits::{Block as BlockT, Header, NumberFor},
 };
 
 #[rpc(client, server)]
@@ -28,6 +34,23 @@ where
 		&self,
 		rows: Rows,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(Vec<Vec<u8>>, u128)>;
+
+	#[method(name = "kate_queryRowsV2Metrics")]
+	async fn query_rows_v2_metrics(
+		&self,
+		rows: Rows,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(RowsResponse, u128)>;
+
+	#[method(name = "kate_queryColumnsMetrics")]
+	async fn query_columns_metrics(
+		&self,
+		columns: Columns,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<(Vec<Vec<u8>>, u128)>;
 
 	#[method(name = "kate_queryAppDataMetrics")]
@@ -35,6 +58,7 @@ where
 		&self,
 		app_id: AppId,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<(Vec<Option<Vec<u8>>>, u128)>;
 
 	#[method(name = "kate_queryProofMetrics")]
@@ -42,19 +66,54 @@ where
 		&self,
 		cells: Cells,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<(Vec<u8>, u128)>;
 
+	#[method(name = "kate_reconstructAppRowsMetrics")]
+	async fn reconstruct_app_rows_metrics(
+		&self,
+		app_id: AppId,
+		cells: DataCells,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(Vec<Option<Vec<u8>>>, u128)>;
+
+	#[method(name = "kate_queryProofV2Metrics")]
+	async fn query_proof_v2_metrics(
+		&self,
+		cells: Cells,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(ProofsResponse, u128)>;
+
+	#[method(name = "kate_queryMultiProofMetrics")]
+	async fn query_multiproof_metrics(
+		&self,
+		cells: Cells,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(Vec<GMultiProof>, u128)>;
+
 	#[method(name = "kate_blockLengthMetrics")]
 	async fn query_block_length_metrics(
 		&self,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<(BlockLength, u128)>;
 
+	#[method(name = "kate_blockDaInfoMetrics")]
+	async fn query_block_da_info_metrics(
+		&self,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(BlockDaInfo<HashOf<Block>>, u128)>;
+
 	#[method(name = "kate_queryDataProofMetrics")]
 	async fn query_data_proof_metrics(
 		&self,
 		transaction_index: u32,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<(DataProof, u128)>;
 
 	#[method(name = "kate_queryDataProofV2Metrics")]
@@ -62,7 +121,39 @@ where
 		&self,
 		transaction_index: u32,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<(ProofResponse, u128)>;
+
+	#[method(name = "kate_queryAllDataProofsV2Metrics")]
+	async fn query_all_data_proofs_v2_metrics(
+		&self,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(Vec<(u32, ProofResponse)>, u128)>;
+
+	#[method(name = "kate_getBlobByHashMetrics")]
+	async fn get_blob_by_hash_metrics(
+		&self,
+		data_hash: HashOf<Block>,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(BlobResponse<HashOf<Block>, NumberFor<Block>>, u128)>;
+
+	#[method(name = "kate_getAssembledBlobMetrics")]
+	async fn get_assembled_blob_metrics(
+		&self,
+		blob_root: HashOf<Block>,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(AssembledBlobResponse<HashOf<Block>, NumberFor<Block>>, u128)>;
+
+	#[method(name = "kate_queryExtrinsicProofMetrics")]
+	async fn query_extrinsic_proof_metrics(
+		&self,
+		transaction_index: u32,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(ExtrinsicProof<HashOf<Block>>, u128)>;
 }
 
 #[async_trait]
@@ -76,16 +167,85 @@ where
 		HeaderExtension,
 	>,
 	Client: Send + Sync + 'static,
-	Client: HeaderBackend<Block> + ProvideRuntimeApi<Block> + BlockBackend<Block>,
+	Client: HeaderBackend<Block>
+		+ ProvideRuntimeApi<Block>
+		+ BlockBackend<Block>
+		+ BlockchainEvents<Block>,
 	Client::Api: DataAvailApi<Block>,
 {
 	async fn query_rows_metrics(
 		&self,
 		rows: Rows,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<(Vec<Vec<u8>>, u128)> {
 		let start = std::time::Instant::now();
-		let result = self.query_rows(rows, at).await;
+		let result = self.query_rows(rows, at, options).await;
+		let elapsed = start.elapsed();
+
+		result.map(|r| (r, elapsed.as_micros()))
+	}
+
+	async fn reconstruct_app_rows_metrics(
+		&self,
+		app_id: AppId,
+		cells: DataCells,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(Vec<Option<Vec<u8>>>, u128)> {
+		let start = std::time::Instant::now();
+		let result = self.reconstruct_app_rows(app_id, cells, at, options).await;
+		let elapsed = start.elapsed();
+
+		result.map(|r| (r, elapsed.as_micros()))
+	}
+
+	async fn query_rows_v2_metrics(
+		&self,
+		rows: Rows,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(RowsResponse, u128)> {
+		let start = std::time::Instant::now();
+		let result = self.query_rows_v2(rows, at, options).await;
+		let elapsed = start.elapsed();
+
+		result.map(|r| (r, elapsed.as_micros()))
+	}
+
+	async fn query_proof_v2_metrics(
+		&self,
+		cells: Cells,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(ProofsResponse, u128)> {
+		let start = std::time::Instant::now();
+		let result = self.query_proof_v2(cells, at, options).await;
+		let elapsed = start.elapsed();
+
+		result.map(|r| (r, elapsed.as_micros()))
+	}
+
+	async fn query_block_da_info_metrics(
+		&self,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(BlockDaInfo<HashOf<Block>>, u128)> {
+		let start = std::time::Instant::now();
+		let result = self.query_block_da_info(at, options).await;
+		let elapsed = start.elapsed();
+
+		result.map(|r| (r, elapsed.as_micros()))
+	}
+
+	async fn query_columns_metrics(
+		&self,
+		columns: Columns,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(Vec<Vec<u8>>, u128)> {
+		let start = std::time::Instant::now();
+		let result = self.query_columns(columns, at, options).await;
 		let elapsed = start.elapsed();
 
 		result.map(|r| (r, elapsed.as_micros()))
@@ -95,9 +255,10 @@ where
 		&self,
 		app_id: AppId,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<(Vec<Option<Vec<u8>>>, u128)> {
 		let start = std::time::Instant::now();
-		let result = self.query_app_data(app_id, at).await;
+		let result = self.query_app_data(app_id, at, options).await;
 		let elapsed = start.elapsed();
 
 		result.map(|r| (r, elapsed.as_micros()))
@@ -107,9 +268,23 @@ where
 		&self,
 		cells: Cells,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<(Vec<u8>, u128)> {
 		let start = std::time::Instant::now();
-		let result = self.query_proof(cells, at).await;
+		let result = self.query_proof(cells, at, options).await;
+		let elapsed = start.elapsed();
+
+		result.map(|r| (r, elapsed.as_micros()))
+	}
+
+	async fn query_multiproof_metrics(
+		&self,
+		cells: Cells,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(Vec<GMultiProof>, u128)> {
+		let start = std::time::Instant::now();
+		let result = self.query_multiproof(cells, at, options).await;
 		let elapsed = start.elapsed();
 
 		result.map(|r| (r, elapsed.as_micros()))
@@ -118,9 +293,10 @@ where
 	async fn query_block_length_metrics(
 		&self,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<(BlockLength, u128)> {
 		let start = std::time::Instant::now();
-		let result = self.query_block_length(at).await;
+		let result = self.query_block_length(at, options).await;
 		let elapsed = start.elapsed();
 
 		result.map(|r| (r, elapsed.as_micros()))
@@ -130,9 +306,10 @@ where
 		&self,
 		transaction_index: u32,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<(DataProof, u128)> {
 		let start = std::time::Instant::now();
-		let result = self.query_data_proof(transaction_index, at).await;
+		let result = self.query_data_proof(transaction_index, at, options).await;
 		let elapsed = start.elapsed();
 
 		result.map(|r| (r, elapsed.as_micros()))
@@ -142,9 +319,65 @@ where
 		&self,
 		transaction_index: u32,
 		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
 	) -> RpcResult<(ProofResponse, u128)> {
 		let start = std::time::Instant::now();
-		let result = self.query_data_proof_v2(transaction_index, at).await;
+		let result = self
+			.query_data_proof_v2(transaction_index, at, options)
+			.await;
+		let elapsed = start.elapsed();
+
+		result.map(|r| (r, elapsed.as_micros()))
+	}
+
+	async fn query_all_data_proofs_v2_metrics(
+		&self,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(Vec<(u32, ProofResponse)>, u128)> {
+		let start = std::time::Instant::now();
+		let result = self.query_all_data_proofs_v2(at, options).await;
+		let elapsed = start.elapsed();
+
+		result.map(|r| (r, elapsed.as_micros()))
+	}
+
+	async fn get_blob_by_hash_metrics(
+		&self,
+		data_hash: HashOf<Block>,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(BlobResponse<HashOf<Block>, NumberFor<Block>>, u128)> {
+		let start = std::time::Instant::now();
+		let result = self.get_blob_by_hash(data_hash, at, options).await;
+		let elapsed = start.elapsed();
+
+		result.map(|r| (r, elapsed.as_micros()))
+	}
+
+	async fn get_assembled_blob_metrics(
+		&self,
+		blob_root: HashOf<Block>,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(AssembledBlobResponse<HashOf<Block>, NumberFor<Block>>, u128)> {
+		let start = std::time::Instant::now();
+		let result = self.get_assembled_blob(blob_root, at, options).await;
+		let elapsed = start.elapsed();
+
+		result.map(|r| (r, elapsed.as_micros()))
+	}
+
+	async fn query_extrinsic_proof_metrics(
+		&self,
+		transaction_index: u32,
+		at: Option<HashOf<Block>>,
+		options: Option<QueryOptions>,
+	) -> RpcResult<(ExtrinsicProof<HashOf<Block>>, u128)> {
+		let start = std::time::Instant::now();
+		let result = self
+			.query_extrinsic_proof(transaction_index, at, options)
+			.await;
 		let elapsed = start.elapsed();
 
 		result.map(|r| (r, elapsed.as_micros()))
@@ -0,0 +1,120 @@
+use kate::{
+	gridgen::{AsBytes, CellBlock as KCellBlock, Multiproof},
+	pmp::m1_blst::M1NoPrecomp,
+};
+use kate_recovery::matrix::Dimensions;
+use kate_verifier::{CellBlock, GridCommitments};
+use serde::{Deserialize, Serialize};
+
+pub use kate_verifier::{COMMITMENT_SIZE, SCALAR_SIZE};
+
+/// Dimensions of the tiles used to aggregate cell proofs.
+///
+/// Every requested cell is mapped to the tile of the extended grid which contains it, and one
+/// multiproof is generated per tile.
+pub fn target_dimensions() -> Dimensions {
+	Dimensions::new(16, 64).expect("16,64>0")
+}
+
+/// Cell block covered by a multiproof, using extended grid coordinates.
+///
+/// `start_*` are inclusive and `end_*` are exclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GCellBlock {
+	pub start_x: u32,
+	pub start_y: u32,
+	pub end_x: u32,
+	pub end_y: u32,
+}
+
+impl From<KCellBlock> for GCellBlock {
+	fn from(block: KCellBlock) -> Self {
+		Self {
+			start_x: block.start_x as u32,
+			start_y: block.start_y as u32,
+			end_x: block.end_x as u32,
+			end_y: block.end_y as u32,
+		}
+	}
+}
+
+impl GCellBlock {
+	pub fn width(&self) -> usize {
+		self.end_x.saturating_sub(self.start_x) as usize
+	}
+
+	pub fn height(&self) -> usize {
+		self.end_y.saturating_sub(self.start_y) as usize
+	}
+}
+
+/// Aggregated proof for all the cells of one grid tile.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GMultiProof {
+	/// Serialized multiproof (48 bytes).
+	pub proof: Vec<u8>,
+	/// Scalars of the tile, one entry per row, 32 bytes per scalar.
+	pub evals: Vec<Vec<u8>>,
+	/// Tile of the extended grid covered by this proof.
+	pub block: GCellBlock,
+}
+
+impl TryFrom<Multiproof> for GMultiProof {
+	type Error = kate::com::Error;
+
+	fn try_from(mp: Multiproof) -> Result<Self, Self::Error> {
+		let proof = mp.proof.to_bytes()?.to_vec();
+		let evals = mp
+			.evals
+			.iter()
+			.map(|row| {
+				row.iter()
+					.map(|s| s.to_bytes().map(|b| b.to_vec()))
+					.collect::<Result<Vec<_>, _>>()
+					.map(|row| row.concat())
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		Ok(Self {
+			proof,
+			evals,
+			block: mp.block.into(),
+		})
+	}
+}
+
+pub use kate_verifier::Error as VerificationError;
+
+/// Verifies a multiproof returned by `kate_queryMultiProof`.
+///
+/// `commitments` is the flat commitment vector of the block header extension, which contains
+/// one 48-byte commitment per row of the extended grid, and `extended_dims` are the dimensions
+/// of the extended grid (i.e. header rows multiplied by the row extension factor).
+pub fn verify(
+	srs: &M1NoPrecomp,
+	commitments: &[u8],
+	extended_dims: Dimensions,
+	multiproof: &GMultiProof,
+) -> Result<bool, VerificationError> {
+	let commitments = GridCommitments {
+		commitments,
+		extended_dims,
+	};
+	let block = multiproof.block;
+	let block = CellBlock {
+		start_x: block.start_x,
+		start_y: block.start_y,
+		end_x: block.end_x,
+		end_y: block.end_y,
+	};
+
+	kate_verifier::verify_multiproof(
+		srs,
+		&commitments,
+		block,
+		&multiproof.proof,
+		&multiproof.evals,
+	)
+}
@@ -0,0 +1,539 @@
+//! Cost-based rate limiting of the Kate RPC.
+//!
+//! Every call is charged a cost, in tokens, which reflects the work it triggers on the node:
+//! building the grid of a block, reading rows or columns, computing cell proofs or data proofs.
+//! The tokens are taken from the token buckets of the connection and of the IP address of the
+//! caller, which are refilled at a constant rate. A call is rejected when any of its buckets
+//! cannot pay its cost.
+//!
+//! Transports run the calls of a caller inside [`CALL_ORIGIN`]. The node JSON-RPC server does
+//! not give the caller of a call to the method handlers, so rate limited nodes serve the Kate RPC
+//! from the server of [`crate::server`], which does.
+use std::{
+	collections::HashMap,
+	hash::Hash,
+	net::IpAddr,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc, Mutex,
+	},
+	time::{Duration, Instant},
+};
+
+/// Cost of building (or loading) the grid of a block.
+pub const GRID_COST: u64 = 50;
+/// Cost of reading one row or column of the grid.
+pub const ROW_COST: u64 = 1;
+/// Cost of the proof of one cell.
+pub const CELL_PROOF_COST: u64 = 2;
+/// Cost of one multiproof, which covers a whole tile of cells.
+///
+/// Cells are charged as single proofs, up to one multiproof per tile of
+/// [`crate::multiproof::target_dimensions`], so a multiproof never costs more than the proofs
+/// of its cells.
+pub const MULTIPROOF_COST: u64 = 10;
+/// Cost of rebuilding one row from the cells of the extended grid.
+pub const RECONSTRUCTED_ROW_COST: u64 = 4;
+/// Cost of loading a block body and building its data tries or its extrinsics trie.
+pub const DATA_PROOF_COST: u64 = 20;
+/// Cost of the calls which only read the header or the runtime state.
+pub const METADATA_COST: u64 = 1;
+
+/// Buckets which are full and have not been used for this long are dropped.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+/// Number of buckets of one kind which triggers the clean up of idle buckets.
+const MAX_BUCKETS: usize = 4096;
+
+/// Kate RPC call, with the sizes which determine its cost.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KateCall {
+	Rows(usize),
+	Columns(usize),
+	AppData,
+	Proof(usize),
+	MultiProof(usize),
+	Reconstruction(usize),
+	BlockLength,
+	BlockDaInfo,
+	DataProof,
+	DataProofV2,
+	AllDataProofsV2,
+	BlobByHash,
+	AssembledBlob(usize),
+	ExtrinsicProof,
+	/// Start of a `kate_subscribeAppData` subscription, each notification is charged as
+	/// [`KateCall::AppData`].
+	SubscribeAppData,
+	/// Start of a `kate_chainHead_follow` subscription.
+	ChainHeadFollow,
+	ChainHeadCells(usize),
+	ChainHeadRows(usize),
+}
+
+impl KateCall {
+	/// Cost of the call, in tokens.
+	pub fn cost(&self) -> u64 {
+		let count = |n: usize| u64::try_from(n).unwrap_or(u64::MAX);
+		match *self {
+			Self::Rows(rows) | Self::ChainHeadRows(rows) => {
+				GRID_COST.saturating_add(count(rows).saturating_mul(ROW_COST))
+			},
+			Self::Columns(cols) => GRID_COST.saturating_add(count(cols).saturating_mul(ROW_COST)),
+			Self::AppData | Self::SubscribeAppData => GRID_COST,
+			Self::Proof(cells) | Self::ChainHeadCells(cells) => {
+				GRID_COST.saturating_add(count(cells).saturating_mul(CELL_PROOF_COST))
+			},
+			Self::MultiProof(cells) => {
+				let tiles: u32 = crate::multiproof::target_dimensions().size();
+				let proofs = count(cells)
+					.saturating_mul(CELL_PROOF_COST)
+					.min(u64::from(tiles).saturating_mul(MULTIPROOF_COST));
+				GRID_COST.saturating_add(proofs)
+			},
+			Self::Reconstruction(cells) => count(cells).saturating_mul(RECONSTRUCTED_ROW_COST),
+			Self::BlockLength | Self::BlockDaInfo | Self::ChainHeadFollow => METADATA_COST,
+			Self::DataProof | Self::DataProofV2 | Self::BlobByHash | Self::ExtrinsicProof => {
+				DATA_PROOF_COST
+			},
+			// Every data proof of the block shares the same tries.
+			Self::AllDataProofsV2 => DATA_PROOF_COST.saturating_mul(2),
+			Self::AssembledBlob(chunks) => count(chunks).saturating_mul(DATA_PROOF_COST),
+		}
+	}
+
+	/// Name of the RPC method.
+	pub fn method(&self) -> &'static str {
+		match self {
+			Self::Rows(_) => "kate_queryRows",
+			Self::Columns(_) => "kate_queryColumns",
+			Self::AppData => "kate_queryAppData",
+			Self::Proof(_) => "kate_queryProof",
+			Self::MultiProof(_) => "kate_queryMultiProof",
+			Self::Reconstruction(_) => "kate_reconstructAppRows",
+			Self::BlockLength => "kate_blockLength",
+			Self::BlockDaInfo => "kate_blockDaInfo",
+			Self::DataProof => "kate_queryDataProof",
+			Self::DataProofV2 => "kate_queryDataProofV2",
+			Self::AllDataProofsV2 => "kate_queryAllDataProofsV2",
+			Self::BlobByHash => "kate_getBlobByHash",
+			Self::AssembledBlob(_) => "kate_getAssembledBlob",
+			Self::ExtrinsicProof => "kate_queryExtrinsicProof",
+			Self::SubscribeAppData => "kate_subscribeAppData",
+			Self::ChainHeadFollow => "kate_chainHead_follow",
+			Self::ChainHeadCells(_) => "kate_chainHead_queryCells",
+			Self::ChainHeadRows(_) => "kate_chainHead_queryRows",
+		}
+	}
+}
+
+/// Limit of a token bucket.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limit {
+	/// Tokens added to the bucket every second.
+	pub tokens_per_second: u64,
+	/// Size of the bucket, which is the maximum burst.
+	pub burst: u64,
+}
+
+/// Limits of the Kate RPC. `None` disables the limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RateLimits {
+	pub per_connection: Option<Limit>,
+	pub per_ip: Option<Limit>,
+}
+
+/// Caller of a Kate RPC call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Origin {
+	pub ip: IpAddr,
+	/// Identifier of the connection, unique during the lifetime of the node.
+	pub connection: u64,
+}
+
+impl Origin {
+	/// Origin of a new connection from `ip`.
+	pub fn connect(ip: IpAddr) -> Self {
+		// Shared by every transport, so their connections never share a bucket.
+		static NEXT_CONNECTION: AtomicU64 = AtomicU64::new(0);
+		Self {
+			ip,
+			connection: NEXT_CONNECTION.fetch_add(1, Ordering::Relaxed),
+		}
+	}
+}
+
+/// Connection of a caller, whose bucket is dropped from the rate limiter when it is closed.
+pub struct Connection {
+	origin: Origin,
+	rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+impl Connection {
+	pub fn open(ip: IpAddr, rate_limiter: Option<Arc<RateLimiter>>) -> Self {
+		Self {
+			origin: Origin::connect(ip),
+			rate_limiter,
+		}
+	}
+
+	pub fn origin(&self) -> Origin {
+		self.origin
+	}
+}
+
+impl Drop for Connection {
+	fn drop(&mut self) {
+		if let Some(rate_limiter) = &self.rate_limiter {
+			rate_limiter.disconnect(self.origin.connection);
+		}
+	}
+}
+
+tokio::task_local! {
+	/// Origin of the Kate calls made by the current task.
+	pub static CALL_ORIGIN: Origin;
+}
+
+/// Origin of the Kate calls made by the current task, if the transport knows it.
+pub fn current_origin() -> Option<Origin> {
+	CALL_ORIGIN.try_with(|origin| *origin).ok()
+}
+
+/// Kind of bucket which rejected a call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scope {
+	Connection,
+	Ip,
+}
+
+impl Scope {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Connection => "connection",
+			Self::Ip => "ip",
+		}
+	}
+}
+
+/// A call exceeded the limit of `scope`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimited {
+	pub scope: Scope,
+	/// Time until the bucket holds enough tokens for the call.
+	pub retry_after: Duration,
+}
+
+struct TokenBucket {
+	tokens: f64,
+	updated: Instant,
+}
+
+impl TokenBucket {
+	fn full(limit: &Limit, now: Instant) -> Self {
+		Self {
+			tokens: limit.burst as f64,
+			updated: now,
+		}
+	}
+
+	fn refill(&mut self, limit: &Limit, now: Instant) {
+		let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+		self.tokens =
+			(self.tokens + elapsed * limit.tokens_per_second as f64).min(limit.burst as f64);
+		self.updated = now;
+	}
+
+	/// Time until the bucket holds `cost` tokens, or `None` if it already does.
+	fn wait_for(&self, limit: &Limit, cost: f64) -> Option<Duration> {
+		let missing = cost - self.tokens;
+		if missing <= 0.0 {
+			return None;
+		}
+		let secs = missing / limit.tokens_per_second.max(1) as f64;
+		Some(Duration::from_secs_f64(secs))
+	}
+
+	/// Whether the bucket has not been used for [`IDLE_TIMEOUT`] and is full again.
+	fn is_idle(&self, limit: &Limit, now: Instant) -> bool {
+		let elapsed = now.saturating_duration_since(self.updated);
+		elapsed > IDLE_TIMEOUT
+			&& self.tokens + elapsed.as_secs_f64() * limit.tokens_per_second as f64
+				>= limit.burst as f64
+	}
+}
+
+/// Token buckets of one kind, keyed by caller.
+struct Buckets<K> {
+	limit: Limit,
+	buckets: HashMap<K, TokenBucket>,
+}
+
+impl<K: Eq + Hash> Buckets<K> {
+	fn new(limit: Limit) -> Self {
+		Self {
+			limit,
+			buckets: HashMap::new(),
+		}
+	}
+
+	/// Refills the bucket of `key` and returns it.
+	fn bucket(&mut self, key: K, now: Instant) -> &mut TokenBucket {
+		if self.buckets.len() >= MAX_BUCKETS {
+			let limit = self.limit;
+			self.buckets
+				.retain(|_, bucket| !bucket.is_idle(&limit, now));
+		}
+
+		let limit = self.limit;
+		let bucket = self
+			.buckets
+			.entry(key)
+			.or_insert_with(|| TokenBucket::full(&limit, now));
+		bucket.refill(&limit, now);
+		bucket
+	}
+}
+
+/// Token buckets of the Kate RPC callers.
+pub struct RateLimiter {
+	connections: Option<Mutex<Buckets<u64>>>,
+	ips: Option<Mutex<Buckets<IpAddr>>>,
+}
+
+impl RateLimiter {
+	pub fn new(limits: RateLimits) -> Self {
+		Self {
+			connections: limits.per_connection.map(|l| Mutex::new(Buckets::new(l))),
+			ips: limits.per_ip.map(|l| Mutex::new(Buckets::new(l))),
+		}
+	}
+
+	/// Takes `cost` tokens from the buckets of `origin`, or rejects the call without taking any.
+	///
+	/// Costs bigger than the burst of a bucket are capped to it, so every call can eventually
+	/// be served.
+	pub fn check(&self, origin: Origin, cost: u64) -> Result<(), RateLimited> {
+		self.check_at(origin, cost, Instant::now())
+	}
+
+	fn check_at(&self, origin: Origin, cost: u64, now: Instant) -> Result<(), RateLimited> {
+		// Buckets are always locked in the same order: connections first.
+		let mut connections = self
+			.connections
+			.as_ref()
+			.map(|c| c.lock().expect("Poisoned lock"));
+		let mut ips = self.ips.as_ref().map(|i| i.lock().expect("Poisoned lock"));
+
+		let mut connection = connections.as_deref_mut().map(|buckets| {
+			let limit = buckets.limit;
+			(limit, buckets.bucket(origin.connection, now))
+		});
+		let mut ip = ips.as_deref_mut().map(|buckets| {
+			let limit = buckets.limit;
+			(limit, buckets.bucket(origin.ip, now))
+		});
+
+		for (scope, entry) in [(Scope::Connection, &connection), (Scope::Ip, &ip)] {
+			let Some((limit, bucket)) = entry else {
+				continue;
+			};
+			let cost = cost.min(limit.burst) as f64;
+			if let Some(retry_after) = bucket.wait_for(limit, cost) {
+				return Err(RateLimited { scope, retry_after });
+			}
+		}
+
+		for (limit, bucket) in connection.iter_mut().chain(ip.iter_mut()) {
+			bucket.tokens -= cost.min(limit.burst) as f64;
+		}
+
+		Ok(())
+	}
+
+	/// Drops the bucket of a closed connection.
+	pub fn disconnect(&self, connection: u64) {
+		if let Some(connections) = &self.connections {
+			connections
+				.lock()
+				.expect("Poisoned lock")
+				.buckets
+				.remove(&connection);
+		}
+	}
+
+	/// Number of tracked connection and IP buckets.
+	pub fn tracked(&self) -> (usize, usize) {
+		(count(&self.connections), count(&self.ips))
+	}
+}
+
+fn count<K>(buckets: &Option<Mutex<Buckets<K>>>) -> usize {
+	buckets
+		.as_ref()
+		.map_or(0, |b| b.lock().expect("Poisoned lock").buckets.len())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::net::Ipv4Addr;
+
+	use super::*;
+
+	const LIMIT: Limit = Limit {
+		tokens_per_second: 10,
+		burst: 100,
+	};
+
+	fn origin(ip: u8, connection: u64) -> Origin {
+		Origin {
+			ip: IpAddr::V4(Ipv4Addr::new(10, 0, 0, ip)),
+			connection,
+		}
+	}
+
+	fn limiter(per_connection: Option<Limit>, per_ip: Option<Limit>) -> RateLimiter {
+		RateLimiter::new(RateLimits {
+			per_connection,
+			per_ip,
+		})
+	}
+
+	#[test]
+	fn cost_grows_with_the_request() {
+		assert_eq!(KateCall::Rows(0).cost(), GRID_COST);
+		assert_eq!(KateCall::Rows(3).cost(), GRID_COST + 3 * ROW_COST);
+		assert_eq!(KateCall::Proof(4).cost(), GRID_COST + 4 * CELL_PROOF_COST);
+		assert_eq!(
+			KateCall::ChainHeadCells(4).cost(),
+			KateCall::Proof(4).cost()
+		);
+		assert_eq!(KateCall::AssembledBlob(3).cost(), 3 * DATA_PROOF_COST);
+
+		// Multiproofs never cost more than the single proofs of their cells.
+		assert_eq!(KateCall::MultiProof(4).cost(), KateCall::Proof(4).cost());
+		let tiles = 16 * 64;
+		assert_eq!(
+			KateCall::MultiProof(100_000).cost(),
+			GRID_COST + tiles * MULTIPROOF_COST
+		);
+		assert!(KateCall::MultiProof(100_000).cost() < KateCall::Proof(100_000).cost());
+		assert_eq!(KateCall::Rows(usize::MAX).cost(), u64::MAX);
+	}
+
+	#[test]
+	fn burst_is_spent_then_refilled() {
+		let limiter = limiter(Some(LIMIT), None);
+		let now = Instant::now();
+
+		assert_eq!(limiter.check_at(origin(1, 1), 60, now), Ok(()));
+		assert_eq!(limiter.check_at(origin(1, 1), 40, now), Ok(()));
+		let limited = limiter.check_at(origin(1, 1), 20, now).unwrap_err();
+		assert_eq!(limited.scope, Scope::Connection);
+		assert_eq!(limited.retry_after, Duration::from_secs(2));
+
+		// Two seconds refill the 20 missing tokens.
+		let later = now + Duration::from_secs(2);
+		assert_eq!(limiter.check_at(origin(1, 1), 20, later), Ok(()));
+		assert!(limiter.check_at(origin(1, 1), 1, later).is_err());
+	}
+
+	#[test]
+	fn refill_is_capped_to_the_burst() {
+		let limiter = limiter(Some(LIMIT), None);
+		let now = Instant::now();
+		assert_eq!(limiter.check_at(origin(1, 1), 100, now), Ok(()));
+
+		let later = now + Duration::from_secs(3600);
+		assert_eq!(limiter.check_at(origin(1, 1), 100, later), Ok(()));
+		assert!(limiter.check_at(origin(1, 1), 1, later).is_err());
+	}
+
+	#[test]
+	fn costs_over_the_burst_are_capped() {
+		let limiter = limiter(Some(LIMIT), None);
+		let now = Instant::now();
+
+		assert_eq!(limiter.check_at(origin(1, 1), 1_000, now), Ok(()));
+		let limited = limiter.check_at(origin(1, 1), 1_000, now).unwrap_err();
+		assert_eq!(limited.retry_after, Duration::from_secs(10));
+	}
+
+	#[test]
+	fn connections_have_their_own_buckets() {
+		let limiter = limiter(Some(LIMIT), None);
+		let now = Instant::now();
+
+		assert_eq!(limiter.check_at(origin(1, 1), 100, now), Ok(()));
+		assert!(limiter.check_at(origin(1, 1), 1, now).is_err());
+		// Same IP, other connection.
+		assert_eq!(limiter.check_at(origin(1, 2), 100, now), Ok(()));
+		assert_eq!(limiter.tracked(), (2, 0));
+	}
+
+	#[test]
+	fn connections_of_one_ip_share_its_bucket() {
+		let limiter = limiter(Some(LIMIT), Some(LIMIT));
+		let now = Instant::now();
+
+		assert_eq!(limiter.check_at(origin(1, 1), 60, now), Ok(()));
+		let limited = limiter.check_at(origin(1, 2), 60, now).unwrap_err();
+		assert_eq!(limited.scope, Scope::Ip);
+		// Other IPs are not affected.
+		assert_eq!(limiter.check_at(origin(2, 3), 60, now), Ok(()));
+		assert_eq!(limiter.tracked(), (3, 2));
+	}
+
+	#[test]
+	fn rejected_calls_take_no_tokens() {
+		let limiter = limiter(Some(LIMIT), Some(Limit { burst: 50, ..LIMIT }));
+		let now = Instant::now();
+
+		assert_eq!(limiter.check_at(origin(1, 1), 30, now), Ok(()));
+		// The IP bucket rejects the call, the connection bucket must not be charged.
+		let limited = limiter.check_at(origin(1, 1), 30, now).unwrap_err();
+		assert_eq!(limited.scope, Scope::Ip);
+
+		// The connection still holds 70 tokens, from another IP.
+		assert_eq!(limiter.check_at(origin(2, 1), 70, now), Ok(()));
+		let limited = limiter.check_at(origin(2, 1), 1, now).unwrap_err();
+		assert_eq!(limited.scope, Scope::Connection);
+	}
+
+	#[test]
+	fn disconnected_connections_are_dropped() {
+		let limiter = limiter(Some(LIMIT), Some(LIMIT));
+		let now = Instant::now();
+		assert_eq!(limiter.check_at(origin(1, 1), 100, now), Ok(()));
+
+		limiter.disconnect(1);
+		assert_eq!(limiter.tracked(), (0, 1));
+	}
+
+	#[test]
+	fn closed_connections_are_dropped() {
+		let limiter = Arc::new(limiter(Some(LIMIT), None));
+		let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+		let first = Connection::open(ip, Some(limiter.clone()));
+		let second = Connection::open(ip, Some(limiter.clone()));
+		assert_ne!(first.origin().connection, second.origin().connection);
+
+		assert_eq!(limiter.check(first.origin(), 1), Ok(()));
+		assert_eq!(limiter.check(second.origin(), 1), Ok(()));
+		drop(first);
+		assert_eq!(limiter.tracked(), (1, 0));
+	}
+
+	#[test]
+	fn idle_buckets_are_cleaned_up() {
+		let limiter = limiter(Some(LIMIT), None);
+		let now = Instant::now();
+		for connection in 0..MAX_BUCKETS as u64 {
+			assert_eq!(limiter.check_at(origin(1, connection), 1, now), Ok(()));
+		}
+		assert_eq!(limiter.tracked(), (MAX_BUCKETS, 0));
+
+		let later = now + IDLE_TIMEOUT + Duration::from_secs(1);
+		assert_eq!(limiter.check_at(origin(1, u64::MAX), 1, later), Ok(()));
+		assert_eq!(limiter.tracked(), (1, 0));
+	}
+}
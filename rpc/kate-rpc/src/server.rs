@@ -0,0 +1,453 @@
+//! JSON-RPC server of the Kate RPC, which knows the caller of every call.
+//!
+//! The node JSON-RPC server does not give the connection or the address of the caller to the
+//! method handlers, so the buckets of [`crate::rate_limit`] cannot be applied to its calls. This
+//! server serves the Kate RPC over HTTP and WebSocket, and runs every call inside
+//! [`CALL_ORIGIN`], so it is charged to the buckets of its connection and IP address.
+//!
+//! Calls, single or batched, are dispatched to the same method handlers as the node server.
+//! Subscriptions are only supported over WebSocket.
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+
+use futures::{
+	channel::mpsc,
+	future::{self, Either},
+	io::{BufReader, BufWriter},
+	stream::FuturesUnordered,
+	StreamExt,
+};
+use hyper::{
+	header::{self, HeaderValue},
+	server::conn::AddrStream,
+	service::{make_service_fn, service_fn},
+	upgrade::Upgraded,
+	Body, Method, Request, Response, StatusCode,
+};
+use jsonrpsee::{
+	core::{
+		id_providers::RandomIntegerIdProvider,
+		server::{
+			helpers::{
+				prepare_error, BatchResponseBuilder, BoundedSubscriptions, MethodResponse,
+				MethodSink,
+			},
+			rpc_module::{ConnState, MethodKind, Methods},
+		},
+		JsonRawValue,
+	},
+	types::{
+		error::{reject_too_big_request, reject_too_many_subscriptions, ErrorCode},
+		ErrorObject, Id, Params, Request as RpcRequest,
+	},
+};
+use soketto::{connection::Error as WsError, handshake::http::is_upgrade_request};
+use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
+
+use crate::rate_limit::{Connection, RateLimiter, CALL_ORIGIN};
+
+const LOG_TARGET: &str = "avail::kate::rpc::server";
+
+/// Maximum size of a request, in bytes.
+const MAX_REQUEST_SIZE: u32 = 15 * 1024 * 1024;
+/// Maximum size of a response, in bytes.
+const MAX_RESPONSE_SIZE: u32 = 15 * 1024 * 1024;
+/// Maximum number of subscriptions of a WebSocket connection.
+const MAX_SUBSCRIPTIONS_PER_CONNECTION: u32 = 1024;
+/// Maximum length of the requests and responses written to the logs.
+const MAX_LOG_LENGTH: u32 = 4096;
+
+type WsStream = BufReader<BufWriter<Compat<Upgraded>>>;
+
+/// Serves `methods` at `addr` until the node stops.
+pub async fn run(
+	methods: impl Into<Methods>,
+	rate_limiter: Option<Arc<RateLimiter>>,
+	addr: SocketAddr,
+) {
+	let methods = methods.into();
+	let make_service = make_service_fn(move |stream: &AddrStream| {
+		let methods = methods.clone();
+		let connection = Arc::new(Connection::open(
+			stream.remote_addr().ip(),
+			rate_limiter.clone(),
+		));
+
+		async move {
+			Ok::<_, Infallible>(service_fn(move |request| {
+				let (methods, connection) = (methods.clone(), connection.clone());
+				async move { Ok::<_, Infallible>(handle(methods, connection, request).await) }
+			}))
+		}
+	});
+
+	let server = match hyper::Server::try_bind(&addr) {
+		Ok(builder) => builder.serve(make_service),
+		Err(e) => {
+			log::error!(target: LOG_TARGET, "Kate RPC server cannot bind {addr}: {e:?}");
+			return;
+		},
+	};
+
+	log::info!(target: LOG_TARGET, "Kate RPC server listening on {addr}");
+	if let Err(e) = server.await {
+		log::error!(target: LOG_TARGET, "Kate RPC server stopped: {e:?}");
+	}
+}
+
+async fn handle(
+	methods: Methods,
+	connection: Arc<Connection>,
+	request: Request<Body>,
+) -> Response<Body> {
+	if is_upgrade_request(&request) {
+		return upgrade(methods, connection, request);
+	}
+	if request.method() != Method::POST {
+		return error(StatusCode::METHOD_NOT_ALLOWED, "Only POST is supported");
+	}
+	let Some(length) = request
+		.headers()
+		.get(header::CONTENT_LENGTH)
+		.and_then(|value| value.to_str().ok()?.parse::<u64>().ok())
+	else {
+		return error(StatusCode::LENGTH_REQUIRED, "Missing Content-Length");
+	};
+	if length > u64::from(MAX_REQUEST_SIZE) {
+		return error(StatusCode::PAYLOAD_TOO_LARGE, "Request is too large");
+	}
+	let Ok(body) = hyper::body::to_bytes(request.into_body()).await else {
+		return error(StatusCode::BAD_REQUEST, "Invalid body");
+	};
+
+	let calls = Calls {
+		methods: &methods,
+		connection: &connection,
+		subscriptions: None,
+	};
+	let response = CALL_ORIGIN
+		.scope(connection.origin(), calls.process(&body))
+		.await;
+
+	let mut response = Response::new(Body::from(response.unwrap_or_default()));
+	response.headers_mut().insert(
+		header::CONTENT_TYPE,
+		HeaderValue::from_static("application/json"),
+	);
+	response
+}
+
+/// Accepts the WebSocket upgrade of `request` and serves the connection in its own task.
+fn upgrade(
+	methods: Methods,
+	connection: Arc<Connection>,
+	request: Request<Body>,
+) -> Response<Body> {
+	let mut server = soketto::handshake::http::Server::new();
+	let response = match server.receive_request(&request) {
+		Ok(response) => response,
+		Err(e) => return error(StatusCode::BAD_REQUEST, &format!("Invalid upgrade: {e}")),
+	};
+
+	tokio::spawn(async move {
+		let upgraded = match hyper::upgrade::on(request).await {
+			Ok(upgraded) => upgraded,
+			Err(e) => {
+				log::debug!(target: LOG_TARGET, "WebSocket upgrade failed: {e:?}");
+				return;
+			},
+		};
+		let stream = BufReader::new(BufWriter::new(upgraded.compat()));
+		let mut builder = server.into_builder(stream);
+		builder.set_max_message_size(MAX_REQUEST_SIZE as usize);
+		let (sender, receiver) = builder.finish();
+		serve_ws(&methods, &connection, sender, receiver).await;
+	});
+
+	response.map(|()| Body::empty())
+}
+
+/// Answers the calls of a WebSocket connection until it is closed.
+async fn serve_ws(
+	methods: &Methods,
+	connection: &Connection,
+	mut sender: soketto::Sender<WsStream>,
+	mut receiver: soketto::Receiver<WsStream>,
+) {
+	let (tx, mut rx) = mpsc::unbounded::<String>();
+	let sink = MethodSink::new_with_limit(tx, MAX_RESPONSE_SIZE, MAX_LOG_LENGTH);
+	let subscriptions = BoundedSubscriptions::new(MAX_SUBSCRIPTIONS_PER_CONNECTION);
+
+	// Responses and notifications are written by their own task, so slow readers do not hold the
+	// calls back.
+	tokio::spawn(async move {
+		while let Some(message) = rx.next().await {
+			if sender.send_text_owned(message).await.is_err() || sender.flush().await.is_err() {
+				break;
+			}
+		}
+		let _ = sender.close().await;
+	});
+
+	let calls = Calls {
+		methods,
+		connection,
+		subscriptions: Some((&sink, &subscriptions)),
+	};
+	let origin = connection.origin();
+	let mut pending = FuturesUnordered::new();
+	let mut message = Vec::new();
+
+	loop {
+		message.clear();
+		// Calls in flight are driven while the next message is received.
+		let received = {
+			let mut receive = Box::pin(receiver.receive_data(&mut message));
+			loop {
+				if pending.is_empty() {
+					break receive.await;
+				}
+				match future::select(receive, pending.next()).await {
+					Either::Left((received, _)) => break received,
+					Either::Right((_, next_receive)) => receive = next_receive,
+				}
+			}
+		};
+
+		match received {
+			Ok(_) => {
+				let request = std::mem::take(&mut message);
+				let (calls, sink) = (&calls, &sink);
+				pending.push(CALL_ORIGIN.scope(origin, async move {
+					if let Some(response) = calls.process(&request).await {
+						let _ = sink.send_raw(response);
+					}
+				}));
+			},
+			Err(WsError::Closed) => break,
+			Err(WsError::MessageTooLarge { .. }) => {
+				sink.send_error(Id::Null, reject_too_big_request(MAX_REQUEST_SIZE));
+			},
+			Err(e) => {
+				log::debug!(target: LOG_TARGET, "WebSocket connection failed: {e:?}");
+				break;
+			},
+		}
+	}
+
+	// Calls in flight are answered before the subscriptions are closed.
+	while pending.next().await.is_some() {}
+	sink.close();
+	subscriptions.close();
+}
+
+/// Calls of one connection.
+struct Calls<'a> {
+	methods: &'a Methods,
+	connection: &'a Connection,
+	/// Sink and subscription permits of WebSocket connections, `None` over HTTP.
+	subscriptions: Option<(&'a MethodSink, &'a BoundedSubscriptions)>,
+}
+
+impl Calls<'_> {
+	/// Answers a single or batched JSON-RPC request. Returns `None` if there is nothing to
+	/// answer, e.g. for a subscription, which answers through the sink.
+	async fn process(&self, request: &[u8]) -> Option<String> {
+		match request.iter().find(|byte| !byte.is_ascii_whitespace()) {
+			Some(b'{') => match serde_json::from_slice::<RpcRequest>(request) {
+				Ok(call) => self.execute(call).await.map(|response| response.result),
+				Err(_) => Some(invalid(request).result),
+			},
+			Some(b'[') => self.process_batch(request).await,
+			_ => Some(
+				MethodResponse::error(Id::Null, ErrorObject::from(ErrorCode::ParseError)).result,
+			),
+		}
+	}
+
+	async fn process_batch(&self, request: &[u8]) -> Option<String> {
+		let batch = match serde_json::from_slice::<Vec<&JsonRawValue>>(request) {
+			Ok(batch) if !batch.is_empty() => batch,
+			Ok(_) => {
+				let response =
+					MethodResponse::error(Id::Null, ErrorObject::from(ErrorCode::InvalidRequest));
+				return Some(response.result);
+			},
+			Err(_) => {
+				let response =
+					MethodResponse::error(Id::Null, ErrorObject::from(ErrorCode::ParseError));
+				return Some(response.result);
+			},
+		};
+
+		// Calls are executed in order, so they are charged in order.
+		let mut responses = BatchResponseBuilder::new_with_limit(MAX_RESPONSE_SIZE as usize);
+		for call in batch {
+			let response = match serde_json::from_str::<RpcRequest>(call.get()) {
+				Ok(call) => self.execute(call).await,
+				Err(_) => Some(invalid(call.get().as_bytes())),
+			};
+			if let Some(response) = response {
+				if let Err(too_large) = responses.append(&response) {
+					return Some(too_large.result);
+				}
+			}
+		}
+
+		(!responses.is_empty()).then(|| responses.finish().result)
+	}
+
+	async fn execute(&self, call: RpcRequest<'_>) -> Option<MethodResponse> {
+		let params = Params::new(call.params.map(|params| params.get()));
+		let id = call.id;
+		let conn_id = self.connection.origin().connection as usize;
+		let max_response_size = MAX_RESPONSE_SIZE as usize;
+
+		let Some(method) = self.methods.method(&call.method) else {
+			return Some(MethodResponse::error(
+				id,
+				ErrorObject::from(ErrorCode::MethodNotFound),
+			));
+		};
+		let response = match method.inner() {
+			MethodKind::Sync(callback) => (callback)(id, params, max_response_size),
+			MethodKind::Async(callback) => {
+				let (id, params) = (id.into_owned(), params.into_owned());
+				(callback)(id, params, conn_id, max_response_size, None).await
+			},
+			MethodKind::Subscription(callback) => {
+				let Some((sink, subscriptions)) = self.subscriptions else {
+					let error = ErrorObject::owned(
+						ErrorCode::MethodNotFound.code(),
+						"Subscriptions are only supported over WebSocket",
+						None::<()>,
+					);
+					return Some(MethodResponse::error(id, error));
+				};
+				let Some(close_notify) = subscriptions.acquire() else {
+					let error = reject_too_many_subscriptions(MAX_SUBSCRIPTIONS_PER_CONNECTION);
+					return Some(MethodResponse::error(id, error));
+				};
+				let conn_state = ConnState {
+					conn_id,
+					close_notify,
+					id_provider: &RandomIntegerIdProvider,
+				};
+				// The subscription answers the call through the sink.
+				let _ = (callback)(id, params, sink.clone(), conn_state, None).await;
+				return None;
+			},
+			MethodKind::Unsubscription(callback) => {
+				(callback)(id, params, conn_id, max_response_size)
+			},
+		};
+
+		Some(response)
+	}
+}
+
+/// Response to a request which is not a valid call.
+fn invalid(request: &[u8]) -> MethodResponse {
+	let (id, code) = prepare_error(request);
+	MethodResponse::error(id, ErrorObject::from(code))
+}
+
+fn error(status: StatusCode, message: &str) -> Response<Body> {
+	let mut response = Response::new(Body::from(message.to_string()));
+	*response.status_mut() = status;
+	response
+}
+
+#[cfg(test)]
+mod tests {
+	use std::net::{IpAddr, Ipv4Addr};
+
+	use futures::executor::block_on;
+	use jsonrpsee::{core::Error as JsonRpseeError, RpcModule};
+
+	use super::*;
+	use crate::rate_limit::current_origin;
+
+	fn methods() -> Methods {
+		let mut module = RpcModule::new(());
+		module
+			.register_async_method("kate_connection", |_, _| async move {
+				Ok::<_, JsonRpseeError>(current_origin().map(|origin| origin.connection))
+			})
+			.unwrap();
+		module
+			.register_subscription(
+				"kate_follow",
+				"kate_follow",
+				"kate_unfollow",
+				|_, mut sink, _| {
+					sink.accept()?;
+					Ok(())
+				},
+			)
+			.unwrap();
+		module.into()
+	}
+
+	fn process(connection: &Connection, request: &str) -> Option<String> {
+		let methods = methods();
+		let calls = Calls {
+			methods: &methods,
+			connection,
+			subscriptions: None,
+		};
+		block_on(CALL_ORIGIN.scope(connection.origin(), calls.process(request.as_bytes())))
+	}
+
+	#[test]
+	fn calls_know_their_connection() {
+		let connection = Connection::open(IpAddr::V4(Ipv4Addr::LOCALHOST), None);
+		let id = connection.origin().connection;
+
+		let response = process(
+			&connection,
+			r#"{"jsonrpc":"2.0","id":1,"method":"kate_connection"}"#,
+		);
+		assert_eq!(
+			response.unwrap(),
+			format!(r#"{{"jsonrpc":"2.0","result":{id},"id":1}}"#)
+		);
+
+		let response = process(
+			&connection,
+			r#"[{"jsonrpc":"2.0","id":1,"method":"kate_connection"},{"jsonrpc":"2.0","id":2,"method":"kate_missing"}]"#,
+		);
+		assert_eq!(
+			response.unwrap(),
+			format!(
+				r#"[{{"jsonrpc":"2.0","result":{id},"id":1}},{{"jsonrpc":"2.0","error":{{"code":-32601,"message":"Method not found"}},"id":2}}]"#
+			)
+		);
+	}
+
+	#[test]
+	fn invalid_requests_are_rejected() {
+		let connection = Connection::open(IpAddr::V4(Ipv4Addr::LOCALHOST), None);
+		let parse_error =
+			r#"{"jsonrpc":"2.0","error":{"code":-32700,"message":"Parse error"},"id":null}"#;
+
+		assert_eq!(process(&connection, "kate").unwrap(), parse_error);
+		assert_eq!(process(&connection, "[").unwrap(), parse_error);
+		assert_eq!(
+			process(&connection, "[]").unwrap(),
+			r#"{"jsonrpc":"2.0","error":{"code":-32600,"message":"Invalid request"},"id":null}"#
+		);
+	}
+
+	#[test]
+	fn subscriptions_need_websocket() {
+		let connection = Connection::open(IpAddr::V4(Ipv4Addr::LOCALHOST), None);
+
+		let response = process(
+			&connection,
+			r#"{"jsonrpc":"2.0","id":1,"method":"kate_follow"}"#,
+		);
+		assert!(response
+			.unwrap()
+			.contains("Subscriptions are only supported over WebSocket"));
+	}
+}
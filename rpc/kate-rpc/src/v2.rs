@@ -0,0 +1,103 @@
+//! Typed responses of `kate_queryRowsV2` and `kate_queryProofV2`.
+//!
+//! The original methods return flat byte arrays whose layout clients must know. These responses
+//! name every field and carry a schema version, so the layout can evolve without breaking clients.
+use serde::{Deserialize, Serialize};
+
+use crate::multiproof::{COMMITMENT_SIZE, SCALAR_SIZE};
+
+/// Schema version of the responses of this module.
+pub const SCHEMA_VERSION: u8 = 2;
+
+/// Position of a cell in the extended grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GCellPosition {
+	pub row: u32,
+	pub col: u32,
+}
+
+/// Proof of one cell of the extended grid.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GCellProof {
+	pub cell: GCellPosition,
+	/// Serialized proof (48 bytes).
+	pub proof: Vec<u8>,
+	/// Serialized scalar (32 bytes).
+	pub scalar: Vec<u8>,
+}
+
+/// Response of `kate_queryProofV2`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofsResponse {
+	pub version: u8,
+	/// Proofs in the order of the requested cells.
+	pub proofs: Vec<GCellProof>,
+}
+
+impl ProofsResponse {
+	/// Splits a `kate_queryProof` response into the proofs of `cells`.
+	pub fn new(cells: &[GCellPosition], flat: &[u8]) -> Option<Self> {
+		let cell_size = COMMITMENT_SIZE + SCALAR_SIZE;
+		if flat.len() != cells.len() * cell_size {
+			return None;
+		}
+
+		let proofs = cells
+			.iter()
+			.zip(flat.chunks_exact(cell_size))
+			.map(|(cell, chunk)| {
+				let (proof, scalar) = chunk.split_at(COMMITMENT_SIZE);
+				GCellProof {
+					cell: *cell,
+					proof: proof.to_vec(),
+					scalar: scalar.to_vec(),
+				}
+			})
+			.collect();
+
+		Some(Self {
+			version: SCHEMA_VERSION,
+			proofs,
+		})
+	}
+}
+
+/// One row of the extended grid.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GRow {
+	pub row_index: u32,
+	/// Serialized scalars of the row (32 bytes each), in column order.
+	pub scalars: Vec<Vec<u8>>,
+}
+
+/// Response of `kate_queryRowsV2`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RowsResponse {
+	pub version: u8,
+	/// Rows in the order of the requested indexes.
+	pub rows: Vec<GRow>,
+}
+
+impl RowsResponse {
+	/// Labels the rows of a `kate_queryRows` response with their `indexes`.
+	pub fn new(indexes: &[u32], rows: Vec<Vec<u8>>) -> Self {
+		let rows = indexes
+			.iter()
+			.zip(rows)
+			.map(|(&row_index, row)| GRow {
+				row_index,
+				scalars: row.chunks(SCALAR_SIZE).map(<[u8]>::to_vec).collect(),
+			})
+			.collect();
+
+		Self {
+			version: SCHEMA_VERSION,
+			rows,
+		}
+	}
+}
@@ -0,0 +1,61 @@
+//! Inclusion proofs of extrinsics against the `extrinsics_root` of the header.
+//!
+//! The extrinsics root is the root of the ordered trie of the encoded extrinsics of the block,
+//! keyed by their compact encoded index, built with `BlakeTwo256` and state version `V0`. A proof
+//! is the compact trie proof of one key, as returned by `kate_queryExtrinsicProof`.
+use codec::{Compact, Encode};
+use sp_core::{Blake2Hasher, H256};
+use sp_std::vec::Vec;
+use sp_trie::{
+	generate_trie_proof, verify_trie_proof, LayoutV0, MemoryDB, TrieDBMutBuilder, TrieMut,
+	VerifyError,
+};
+
+use crate::Error;
+
+/// Trie layout of the extrinsics root.
+type Layout = LayoutV0<Blake2Hasher>;
+
+/// Key of the extrinsic `index` in the extrinsics trie.
+fn key(index: u32) -> Vec<u8> {
+	Compact(index).encode()
+}
+
+/// Builds the proof of the extrinsic `index` of `extrinsics`, which are SCALE encoded.
+///
+/// Returns `None` if `index` is out of range.
+pub fn extrinsic_proof(extrinsics: &[Vec<u8>], index: u32) -> Option<Vec<Vec<u8>>> {
+	if index as usize >= extrinsics.len() {
+		return None;
+	}
+
+	let mut db = MemoryDB::<Blake2Hasher>::default();
+	let mut root = H256::default();
+	{
+		let mut trie = TrieDBMutBuilder::<Layout>::new(&mut db, &mut root).build();
+		for (i, extrinsic) in extrinsics.iter().enumerate() {
+			let i = u32::try_from(i).ok()?;
+			trie.insert(&key(i), extrinsic).ok()?;
+		}
+	}
+
+	generate_trie_proof::<Layout, _, _, _>(&db, root, &[key(index)]).ok()
+}
+
+/// Verifies that `extrinsic`, SCALE encoded, is the extrinsic `index` of the block of
+/// `extrinsics_root`.
+///
+/// Returns `Ok(false)` if the proof is well formed but proves another root or another value.
+pub fn verify_extrinsic_proof(
+	extrinsics_root: H256,
+	index: u32,
+	extrinsic: &[u8],
+	proof: &[Vec<u8>],
+) -> Result<bool, Error> {
+	let items = [(key(index), Some(extrinsic))];
+	match verify_trie_proof::<Layout, _, _, _>(&extrinsics_root, proof, &items) {
+		Ok(()) => Ok(true),
+		Err(VerifyError::RootMismatch(_) | VerifyError::ValueMismatch(_)) => Ok(false),
+		Err(_) => Err(Error::InvalidProof),
+	}
+}
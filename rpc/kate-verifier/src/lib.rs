@@ -0,0 +1,355 @@
+//! Verification of the Kate RPC responses.
+//!
+//! Light clients and bridges can use this crate to check the responses of `kate_queryProof`,
+//! `kate_queryRows`, `kate_queryMultiProof`, `kate_queryDataProofV2` and
+//! `kate_queryExtrinsicProof` against the commitments, the data root and the extrinsics root of the
+//! block header, without depending on the node crates.
+//!
+//! The crate is `no_std` friendly, the `std` feature is enabled by default.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use avail_core::{data_proof_v2::ProofResponse, header::HeaderExtension};
+use binary_merkle_tree::{verify_proof, Leaf};
+use kate::{
+	gridgen::{domain_points, AsBytes},
+	pmp::{
+		m1_blst::{Fr, M1NoPrecomp},
+		traits::{Committer, PolyMultiProofNoPrecomp},
+	},
+};
+use kate_recovery::{data::Cell, matrix::Dimensions};
+use sp_core::{keccak_256, H256};
+use sp_runtime::traits::Keccak256;
+use sp_std::vec::Vec;
+
+pub mod extrinsics;
+#[cfg(feature = "std")]
+pub mod reconstruction;
+#[cfg(test)]
+mod tests;
+
+/// Size in bytes of a serialized KZG commitment or proof.
+pub const COMMITMENT_SIZE: usize = 48;
+/// Size in bytes of a serialized scalar.
+pub const SCALAR_SIZE: usize = 32;
+/// Size in bytes of one cell of the `kate_queryProof` response, the proof followed by the scalar.
+pub const CELL_SIZE: usize = COMMITMENT_SIZE + SCALAR_SIZE;
+/// Extension factor applied to the rows of the header grid.
+pub const ROW_EXTENSION: u16 = 2;
+
+/// Verification errors.
+///
+/// Errors are only returned for malformed inputs, a well formed proof which does not match the
+/// commitments is reported as `Ok(false)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+	/// The dimensions of the header extension are invalid.
+	InvalidDimensions,
+	/// The cell position does not fit into the extended grid.
+	InvalidCell,
+	/// The row index does not fit into the extended grid, or the row has an invalid length.
+	InvalidRow,
+	/// The cell block does not fit into the extended grid.
+	InvalidBlock,
+	/// The commitments do not cover the requested rows.
+	MissingCommitments,
+	/// A commitment could not be deserialized.
+	InvalidCommitment,
+	/// The proof could not be deserialized.
+	InvalidProof,
+	/// The evaluations do not match the cell block or could not be deserialized.
+	InvalidEvals,
+	/// The domain points of the extended grid could not be generated.
+	InvalidDomain,
+	/// The response length does not match the number of requested cells or rows.
+	InvalidResponseLength,
+	/// The leaf index is out of the range of the merkle tree.
+	InvalidLeafIndex,
+}
+
+/// Commitments and dimensions of the extended grid of a block.
+pub struct GridCommitments<'a> {
+	/// One 48-byte commitment per row of the extended grid.
+	pub commitments: &'a [u8],
+	/// Dimensions of the extended grid.
+	pub extended_dims: Dimensions,
+}
+
+impl<'a> GridCommitments<'a> {
+	/// Loads the commitments of the header extension.
+	///
+	/// Header extension stores the dimensions of the original grid, rows are extended by
+	/// [`ROW_EXTENSION`].
+	pub fn from_extension(extension: &'a HeaderExtension) -> Result<Self, Error> {
+		let (rows, cols, commitments) = match extension {
+			HeaderExtension::V1(ext) => (
+				ext.commitment.rows,
+				ext.commitment.cols,
+				ext.commitment.commitment.as_slice(),
+			),
+			HeaderExtension::V2(ext) => (
+				ext.commitment.rows,
+				ext.commitment.cols,
+				ext.commitment.commitment.as_slice(),
+			),
+		};
+		let extended_rows = rows
+			.checked_mul(ROW_EXTENSION)
+			.ok_or(Error::InvalidDimensions)?;
+		let extended_dims = Dimensions::new(extended_rows, cols).ok_or(Error::InvalidDimensions)?;
+
+		Ok(Self {
+			commitments,
+			extended_dims,
+		})
+	}
+
+	/// Returns the commitment of the extended `row`.
+	pub fn row_commitment(&self, row: u32) -> Result<&'a [u8; COMMITMENT_SIZE], Error> {
+		let start = (row as usize)
+			.checked_mul(COMMITMENT_SIZE)
+			.ok_or(Error::MissingCommitments)?;
+		self.commitments
+			.get(start..start + COMMITMENT_SIZE)
+			.ok_or(Error::MissingCommitments)?
+			.try_into()
+			.map_err(|_| Error::MissingCommitments)
+	}
+
+	fn commitments<C: AsBytes<COMMITMENT_SIZE>>(
+		&self,
+		start_row: u32,
+		end_row: u32,
+	) -> Result<Vec<C>, Error> {
+		(start_row..end_row)
+			.map(|row| {
+				let raw = self.row_commitment(row)?;
+				AsBytes::from_bytes(raw).map_err(|_| Error::InvalidCommitment)
+			})
+			.collect()
+	}
+}
+
+/// Verifies one cell of the `kate_queryProof` response.
+///
+/// The cell position uses extended grid coordinates and its content is the 48-byte proof
+/// followed by the 32-byte scalar.
+pub fn verify_cell(
+	srs: &M1NoPrecomp,
+	commitments: &GridCommitments,
+	cell: &Cell,
+) -> Result<bool, Error> {
+	let dims = commitments.extended_dims;
+	let (row, col) = (cell.position.row, usize::from(cell.position.col));
+	if row >= dims.height() as u32 || col >= dims.width() {
+		return Err(Error::InvalidCell);
+	}
+
+	let commits = commitments.commitments(row, row + 1)?;
+	let (proof, scalar) = cell.content.split_at(COMMITMENT_SIZE);
+	let proof: &[u8; COMMITMENT_SIZE] = proof.try_into().map_err(|_| Error::InvalidProof)?;
+	let proof = AsBytes::from_bytes(proof).map_err(|_| Error::InvalidProof)?;
+	let scalar: &[u8; SCALAR_SIZE] = scalar.try_into().map_err(|_| Error::InvalidEvals)?;
+	let scalar: Fr = AsBytes::from_bytes(scalar).map_err(|_| Error::InvalidEvals)?;
+
+	let points = domain_points(dims.width()).map_err(|_| Error::InvalidDomain)?;
+	let points = &points[col..=col];
+
+	srs.verify(&commits, points, &[[scalar]], &proof)
+		.map_err(|_| Error::InvalidProof)
+}
+
+/// Verifies the flat `kate_queryProof` response of `positions`.
+///
+/// Returns `Ok(false)` as soon as one of the cells does not match the commitments.
+pub fn verify_cells(
+	srs: &M1NoPrecomp,
+	commitments: &GridCommitments,
+	positions: &[kate_recovery::matrix::Position],
+	response: &[u8],
+) -> Result<bool, Error> {
+	if response.len() != positions.len() * CELL_SIZE {
+		return Err(Error::InvalidResponseLength);
+	}
+
+	for (position, content) in positions.iter().zip(response.chunks_exact(CELL_SIZE)) {
+		let cell = Cell {
+			position: *position,
+			content: content.try_into().expect("Chunks are exact; qed"),
+		};
+		if !verify_cell(srs, commitments, &cell)? {
+			return Ok(false);
+		}
+	}
+
+	Ok(true)
+}
+
+/// Verifies one extended row of the `kate_queryRows` response.
+///
+/// The row is interpolated and its commitment is compared against the header one.
+pub fn verify_row(
+	srs: &M1NoPrecomp,
+	commitments: &GridCommitments,
+	row: u32,
+	data: &[u8],
+) -> Result<bool, Error> {
+	let dims = commitments.extended_dims;
+	if row >= dims.height() as u32 || data.len() != dims.width() * SCALAR_SIZE {
+		return Err(Error::InvalidRow);
+	}
+	let expected = commitments.row_commitment(row)?;
+
+	let evals = data
+		.chunks_exact(SCALAR_SIZE)
+		.map(|raw| {
+			let raw: &[u8; SCALAR_SIZE] = raw.try_into().expect("Chunks are exact; qed");
+			AsBytes::from_bytes(raw).map_err(|_| Error::InvalidEvals)
+		})
+		.collect::<Result<Vec<Fr>, _>>()?;
+
+	let domain = GeneralEvaluationDomain::<Fr>::new(dims.width()).ok_or(Error::InvalidDomain)?;
+	let coeffs = domain.ifft(&evals);
+	let commitment = srs.commit(coeffs).map_err(|_| Error::InvalidCommitment)?;
+	let commitment = commitment
+		.to_bytes()
+		.map_err(|_| Error::InvalidCommitment)?;
+
+	Ok(&commitment == expected)
+}
+
+/// Verifies the `kate_queryRows` response of `rows`.
+pub fn verify_rows(
+	srs: &M1NoPrecomp,
+	commitments: &GridCommitments,
+	rows: &[u32],
+	response: &[Vec<u8>],
+) -> Result<bool, Error> {
+	if rows.len() != response.len() {
+		return Err(Error::InvalidResponseLength);
+	}
+
+	for (&row, data) in rows.iter().zip(response) {
+		if !verify_row(srs, commitments, row, data)? {
+			return Ok(false);
+		}
+	}
+
+	Ok(true)
+}
+
+/// Cell block covered by a multiproof, using extended grid coordinates.
+///
+/// `start_*` are inclusive and `end_*` are exclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CellBlock {
+	pub start_x: u32,
+	pub start_y: u32,
+	pub end_x: u32,
+	pub end_y: u32,
+}
+
+impl CellBlock {
+	pub fn width(&self) -> usize {
+		self.end_x.saturating_sub(self.start_x) as usize
+	}
+
+	pub fn height(&self) -> usize {
+		self.end_y.saturating_sub(self.start_y) as usize
+	}
+}
+
+/// Verifies one multiproof of the `kate_queryMultiProof` response.
+///
+/// `evals` contains one entry per row of the block, with 32 bytes per scalar.
+pub fn verify_multiproof(
+	srs: &M1NoPrecomp,
+	commitments: &GridCommitments,
+	block: CellBlock,
+	proof: &[u8],
+	evals: &[Vec<u8>],
+) -> Result<bool, Error> {
+	let dims = commitments.extended_dims;
+	if block.start_x >= block.end_x
+		|| block.start_y >= block.end_y
+		|| block.end_x as usize > dims.width()
+		|| block.end_y as usize > dims.height()
+	{
+		return Err(Error::InvalidBlock);
+	}
+
+	let commits = commitments.commitments(block.start_y, block.end_y)?;
+
+	let proof: &[u8; COMMITMENT_SIZE] = proof.try_into().map_err(|_| Error::InvalidProof)?;
+	let proof = AsBytes::from_bytes(proof).map_err(|_| Error::InvalidProof)?;
+
+	if evals.len() != block.height() {
+		return Err(Error::InvalidEvals);
+	}
+	let evals = evals
+		.iter()
+		.map(|row| {
+			if row.len() != block.width() * SCALAR_SIZE {
+				return Err(Error::InvalidEvals);
+			}
+			row.chunks_exact(SCALAR_SIZE)
+				.map(|raw| {
+					let raw: &[u8; SCALAR_SIZE] = raw.try_into().expect("Chunks are exact; qed");
+					AsBytes::from_bytes(raw).map_err(|_| Error::InvalidEvals)
+				})
+				.collect::<Result<Vec<Fr>, _>>()
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let points = domain_points(dims.width()).map_err(|_| Error::InvalidDomain)?;
+	let points = &points[block.start_x as usize..block.end_x as usize];
+
+	srs.verify(&commits, points, &evals, &proof)
+		.map_err(|_| Error::InvalidProof)
+}
+
+/// Verifies the `kate_queryDataProofV2` response against the `data_root` of the header.
+///
+/// Bridge message proofs (responses with a `message`) are checked against the bridge root,
+/// blob proofs against the blob root.
+pub fn verify_data_proof_v2(data_root: H256, response: &ProofResponse) -> Result<bool, Error> {
+	let proof = &response.data_proof;
+	if proof.leaf_index >= proof.number_of_leaves {
+		return Err(Error::InvalidLeafIndex);
+	}
+
+	let mut roots = [0u8; 64];
+	roots[..32].copy_from_slice(proof.blob_root.as_bytes());
+	roots[32..].copy_from_slice(proof.bridge_root.as_bytes());
+	if H256(keccak_256(&roots)) != proof.data_root || proof.data_root != data_root {
+		return Ok(false);
+	}
+
+	let sub_root = if response.message.is_some() {
+		proof.bridge_root
+	} else {
+		proof.blob_root
+	};
+
+	Ok(verify_proof::<Keccak256, _, _>(
+		&sub_root,
+		proof.proof.iter().cloned(),
+		proof.number_of_leaves as usize,
+		proof.leaf_index as usize,
+		Leaf::Hash(proof.leaf),
+	))
+}
+
+/// Verifies that `data` is the blob proven by the `kate_queryDataProofV2` response.
+///
+/// Blob leaves contain the `keccak_256` of the data, so the proven leaf hash is the hash of the
+/// data hash.
+pub fn verify_blob(data_root: H256, response: &ProofResponse, data: &[u8]) -> Result<bool, Error> {
+	let leaf = H256(keccak_256(&keccak_256(data)));
+	if response.message.is_some() || response.data_proof.leaf != leaf {
+		return Ok(false);
+	}
+
+	verify_data_proof_v2(data_root, response)
+}
@@ -0,0 +1,98 @@
+//! Reconstruction of the original grid rows from a subset of the extended cells.
+//!
+//! Each column of the extended grid can be rebuilt from any half of its cells. The rebuilt rows
+//! are checked against the header commitments before they are returned.
+use avail_core::{header::HeaderExtension, AppId};
+use kate::pmp::m1_blst::M1NoPrecomp;
+use kate_recovery::{
+	com::{reconstruct_columns, ReconstructionError},
+	data::DataCell,
+	matrix::Dimensions,
+};
+
+use crate::{verify_row, Error, GridCommitments, ROW_EXTENSION};
+
+/// Reconstruction errors.
+#[derive(Debug)]
+pub enum RecoveryError {
+	/// The cells are not enough to rebuild the columns, or they are out of the grid.
+	Reconstruction(ReconstructionError),
+	/// There are no cells of the column.
+	MissingColumn(u16),
+	/// The rebuilt original row does not match its commitment.
+	CommitmentMismatch(u32),
+	/// The commitments could not be checked.
+	Verification(Error),
+}
+
+impl From<Error> for RecoveryError {
+	fn from(e: Error) -> Self {
+		Self::Verification(e)
+	}
+}
+
+/// Rebuilds every row of the original grid from `cells`, which use extended grid coordinates.
+///
+/// At least half of the cells of each extended column are required.
+pub fn reconstruct_rows(
+	srs: &M1NoPrecomp,
+	commitments: &GridCommitments,
+	cells: &[DataCell],
+) -> Result<Vec<Vec<u8>>, RecoveryError> {
+	let extended_dims = commitments.extended_dims;
+	let cols = extended_dims.cols().get();
+	let rows = extended_dims.rows().get() / ROW_EXTENSION;
+	let dims = Dimensions::new(rows, cols).ok_or(Error::InvalidDimensions)?;
+
+	let columns = reconstruct_columns(dims, cells).map_err(RecoveryError::Reconstruction)?;
+
+	(0..rows)
+		.map(|row| {
+			let data = (0..cols)
+				.map(|col| {
+					columns
+						.get(&col)
+						.and_then(|column| column.get(usize::from(row)))
+						.ok_or(RecoveryError::MissingColumn(col))
+				})
+				.collect::<Result<Vec<_>, _>>()?
+				.concat();
+
+			// Original rows are the even rows of the extended grid.
+			let extended_row = u32::from(row) * u32::from(ROW_EXTENSION);
+			if !verify_row(srs, commitments, extended_row, &data)? {
+				return Err(RecoveryError::CommitmentMismatch(row.into()));
+			}
+			Ok(data)
+		})
+		.collect()
+}
+
+/// Rebuilds the original rows of `app_id`, using the layout of `kate_queryAppData`: rows which
+/// do not contain data of `app_id` are `None`.
+pub fn reconstruct_app_rows(
+	srs: &M1NoPrecomp,
+	extension: &HeaderExtension,
+	app_id: AppId,
+	cells: &[DataCell],
+) -> Result<Vec<Option<Vec<u8>>>, RecoveryError> {
+	let commitments = GridCommitments::from_extension(extension)?;
+	let lookup = match extension {
+		HeaderExtension::V1(ext) => &ext.app_lookup,
+		HeaderExtension::V2(ext) => &ext.app_lookup,
+	};
+	let cols = u32::from(commitments.extended_dims.cols().get());
+
+	let Some(range) = lookup.range_of(app_id).filter(|range| !range.is_empty()) else {
+		let rows = commitments.extended_dims.height() / usize::from(ROW_EXTENSION);
+		return Ok(vec![None; rows]);
+	};
+	let app_rows = (range.start / cols)..((range.end + cols - 1) / cols);
+
+	let rows = reconstruct_rows(srs, &commitments, cells)?;
+	Ok(rows
+		.into_iter()
+		.enumerate()
+		.map(|(row, data)| app_rows.contains(&(row as u32)).then_some(data))
+		.collect())
+}
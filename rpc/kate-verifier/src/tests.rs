@@ -0,0 +1,363 @@
+use core::num::NonZeroU16;
+
+use avail_core::{
+	data_proof_v2::{ProofResponse, SubTrie},
+	AppExtrinsic, AppId, DataProofV2,
+};
+use binary_merkle_tree::merkle_proof;
+use hex_literal::hex;
+use kate::{
+	com::Cell as KCell,
+	gridgen::{AsBytes, EvaluationGrid},
+	pmp::m1_blst::M1NoPrecomp,
+};
+use kate_recovery::matrix::{Dimensions, Position};
+use sp_core::{keccak_256, H256};
+
+use super::*;
+
+/// Data of the `e2e` Kate RPC tests.
+const EXAMPLE_DATA: &[u8] = b"ExampleData";
+const EXAMPLE_DATA_HASH: [u8; 32] =
+	hex!("729afe29f4e9fee2624d7ed311bcf57d24683fb78938bcb4e2a6a22c4968795e");
+/// `kate_queryDataProofV2` response of the `e2e` `rpc_query_data_proof_v2_test` block, which
+/// holds one `ExampleData` submission and no bridge message.
+const EXAMPLE_BLOB_ROOT: [u8; 32] =
+	hex!("8b40fa8b722bb4d74f60999f9ec2ada4d01557ccae1498da59a116e86b9b3070");
+const EXAMPLE_DATA_ROOT: [u8; 32] =
+	hex!("6718396890964fa91d1623f0e9db0894f6b860a6e07c67995881e6615a8fcce9");
+
+struct Fixture {
+	srs: M1NoPrecomp,
+	commitments: Vec<u8>,
+	extended_grid: EvaluationGrid,
+}
+
+impl Fixture {
+	/// Grid of one `ExampleData` submission, built with the node parameters.
+	///
+	/// Signed extrinsics differ from one e2e run to the next, so the KZG vectors are generated
+	/// here. The `e2e` `rpc_kate_verifier_test` checks the verifier against the node responses.
+	fn new() -> Self {
+		let srs = kate::couscous::multiproof_params();
+		let extrinsics = vec![AppExtrinsic::new(AppId(0), EXAMPLE_DATA.to_vec())];
+		let grid = EvaluationGrid::from_extrinsics(extrinsics, 4, 256, 256, [0u8; 32]).unwrap();
+		let commitments = grid
+			.make_polynomial_grid()
+			.unwrap()
+			.extended_commitments(&srs, 2)
+			.unwrap()
+			.iter()
+			.flat_map(|c| c.to_bytes().unwrap())
+			.collect();
+		let extended_grid = grid.extend_columns(NonZeroU16::new(2).unwrap()).unwrap();
+		assert_eq!(extended_grid.dims(), Dimensions::new(2, 8).unwrap());
+
+		Self {
+			srs,
+			commitments,
+			extended_grid,
+		}
+	}
+
+	fn commitments(&self) -> GridCommitments {
+		GridCommitments {
+			commitments: &self.commitments,
+			extended_dims: self.extended_grid.dims(),
+		}
+	}
+
+	fn row(&self, row: usize) -> Vec<u8> {
+		self.extended_grid
+			.row(row)
+			.unwrap()
+			.iter()
+			.flat_map(|s| s.to_bytes().unwrap())
+			.collect()
+	}
+
+	/// Builds the `kate_queryProof` response of `positions`.
+	fn cells_response(&self, positions: &[Position]) -> Vec<u8> {
+		let poly_grid = self.extended_grid.make_polynomial_grid().unwrap();
+		positions
+			.iter()
+			.flat_map(|position| {
+				let cell = KCell::new(
+					avail_core::BlockLengthRows(position.row),
+					avail_core::BlockLengthColumns(position.col.into()),
+				);
+				let (row, col) = (position.row as usize, usize::from(position.col));
+				let data = self.extended_grid.get::<usize, usize>(row, col).unwrap();
+				let proof = poly_grid.proof(&self.srs, &cell).unwrap();
+				[
+					proof.to_bytes().unwrap().to_vec(),
+					data.to_bytes().unwrap().to_vec(),
+				]
+				.concat()
+			})
+			.collect()
+	}
+}
+
+fn positions() -> Vec<Position> {
+	(0..2)
+		.flat_map(|row| (0..8).map(move |col| Position { row, col }))
+		.collect()
+}
+
+#[test]
+fn verify_cells_works() {
+	let fixture = Fixture::new();
+	let positions = positions();
+	let response = fixture.cells_response(&positions);
+
+	assert_eq!(response.len(), positions.len() * CELL_SIZE);
+	assert_eq!(
+		verify_cells(&fixture.srs, &fixture.commitments(), &positions, &response),
+		Ok(true)
+	);
+}
+
+#[test]
+fn verify_cells_detects_invalid_cells() {
+	let fixture = Fixture::new();
+	let positions = positions();
+	let commitments = fixture.commitments();
+	let mut response = fixture.cells_response(&positions);
+
+	// Proof of another cell.
+	let mut swapped = positions.clone();
+	swapped.swap(0, 1);
+	assert_eq!(
+		verify_cells(&fixture.srs, &commitments, &swapped, &response),
+		Ok(false)
+	);
+
+	// Wrong response length.
+	assert_eq!(
+		verify_cells(&fixture.srs, &commitments, &positions[1..], &response),
+		Err(Error::InvalidResponseLength)
+	);
+
+	// Cell out of the extended grid.
+	let outside = [Position { row: 2, col: 0 }];
+	assert_eq!(
+		verify_cells(&fixture.srs, &commitments, &outside, &response[..CELL_SIZE]),
+		Err(Error::InvalidCell)
+	);
+
+	// Corrupted scalar.
+	response[COMMITMENT_SIZE] ^= 1;
+	assert_ne!(
+		verify_cells(&fixture.srs, &commitments, &positions, &response),
+		Ok(true)
+	);
+}
+
+#[test]
+fn verify_rows_works() {
+	let fixture = Fixture::new();
+	let commitments = fixture.commitments();
+	let rows = vec![fixture.row(0), fixture.row(1)];
+
+	assert_eq!(
+		verify_rows(&fixture.srs, &commitments, &[0, 1], &rows),
+		Ok(true)
+	);
+	assert_eq!(
+		verify_rows(&fixture.srs, &commitments, &[2], &rows[..1]),
+		Err(Error::InvalidRow)
+	);
+	assert_eq!(
+		verify_rows(&fixture.srs, &commitments, &[0], &rows),
+		Err(Error::InvalidResponseLength)
+	);
+
+	let mut corrupted = fixture.row(0);
+	corrupted[0] ^= 1;
+	assert_eq!(
+		verify_row(&fixture.srs, &commitments, 0, &corrupted),
+		Ok(false)
+	);
+}
+
+#[test]
+fn verify_multiproof_works() {
+	let fixture = Fixture::new();
+	let commitments = fixture.commitments();
+	let poly_grid = fixture.extended_grid.make_polynomial_grid().unwrap();
+	let target = Dimensions::new(16, 64).unwrap();
+
+	let cell = KCell::new(
+		avail_core::BlockLengthRows(1),
+		avail_core::BlockLengthColumns(3),
+	);
+	let mp = poly_grid
+		.multiproof(&fixture.srs, &cell, &fixture.extended_grid, target)
+		.unwrap();
+
+	let block = CellBlock {
+		start_x: mp.block.start_x as u32,
+		start_y: mp.block.start_y as u32,
+		end_x: mp.block.end_x as u32,
+		end_y: mp.block.end_y as u32,
+	};
+	let proof = mp.proof.to_bytes().unwrap().to_vec();
+	let evals = mp
+		.evals
+		.iter()
+		.map(|row| row.iter().flat_map(|s| s.to_bytes().unwrap()).collect())
+		.collect::<Vec<Vec<u8>>>();
+
+	assert_eq!(
+		verify_multiproof(&fixture.srs, &commitments, block, &proof, &evals),
+		Ok(true)
+	);
+
+	let mut corrupted = evals.clone();
+	corrupted[0][0] ^= 1;
+	assert_ne!(
+		verify_multiproof(&fixture.srs, &commitments, block, &proof, &corrupted),
+		Ok(true)
+	);
+
+	let outside = CellBlock { end_y: 3, ..block };
+	assert_eq!(
+		verify_multiproof(&fixture.srs, &commitments, outside, &proof, &evals),
+		Err(Error::InvalidBlock)
+	);
+}
+
+fn data_proof_response() -> ProofResponse {
+	let leaf = keccak_256(EXAMPLE_DATA);
+	assert_eq!(leaf, EXAMPLE_DATA_HASH);
+
+	// Single blob and no bridge messages, so the bridge root is zero.
+	let proof = merkle_proof::<Keccak256, _, _>(vec![leaf.to_vec()], 0);
+	let data_proof = DataProofV2::try_from((&proof, H256::zero(), SubTrie::Right)).unwrap();
+	assert_eq!(data_proof.data_root, H256(EXAMPLE_DATA_ROOT));
+	assert_eq!(data_proof.blob_root, H256(EXAMPLE_BLOB_ROOT));
+	assert_eq!(data_proof.leaf, H256(EXAMPLE_BLOB_ROOT));
+	assert!(data_proof.proof.is_empty());
+
+	ProofResponse {
+		data_proof,
+		message: None,
+	}
+}
+
+#[test]
+fn verify_data_proof_v2_works() {
+	let response = data_proof_response();
+	let data_root = H256(EXAMPLE_DATA_ROOT);
+
+	assert_eq!(verify_data_proof_v2(data_root, &response), Ok(true));
+	assert_eq!(verify_blob(data_root, &response, EXAMPLE_DATA), Ok(true));
+	assert_eq!(verify_blob(data_root, &response, b"OtherData"), Ok(false));
+	assert_eq!(verify_data_proof_v2(H256::zero(), &response), Ok(false));
+}
+
+#[test]
+fn verify_data_proof_v2_detects_invalid_proofs() {
+	let response = data_proof_response();
+	let data_root = response.data_proof.data_root;
+
+	let mut invalid = response.clone();
+	invalid.data_proof.blob_root = H256::repeat_byte(1);
+	assert_eq!(verify_data_proof_v2(data_root, &invalid), Ok(false));
+
+	let mut invalid = response.clone();
+	invalid.data_proof.leaf = H256::repeat_byte(1);
+	assert_eq!(verify_data_proof_v2(data_root, &invalid), Ok(false));
+
+	let mut invalid = response;
+	invalid.data_proof.leaf_index = invalid.data_proof.number_of_leaves;
+	assert_eq!(
+		verify_data_proof_v2(data_root, &invalid),
+		Err(Error::InvalidLeafIndex)
+	);
+}
+
+fn data_cells(fixture: &Fixture, rows: &[u32], cols: u16) -> Vec<kate_recovery::data::DataCell> {
+	rows.iter()
+		.flat_map(|&row| (0..cols).map(move |col| Position { row, col }))
+		.map(|position| {
+			let (row, col) = (position.row as usize, usize::from(position.col));
+			let scalar = fixture.extended_grid.get::<usize, usize>(row, col).unwrap();
+			kate_recovery::data::DataCell {
+				position,
+				data: scalar.to_bytes().unwrap(),
+			}
+		})
+		.collect()
+}
+
+#[test]
+fn reconstruct_rows_from_half_of_each_column() {
+	use crate::reconstruction::{reconstruct_rows, RecoveryError};
+
+	let fixture = Fixture::new();
+	let commitments = fixture.commitments();
+
+	// Only the extended (odd) row is given, which is half of each column.
+	let cells = data_cells(&fixture, &[1], 8);
+	let rows = reconstruct_rows(&fixture.srs, &commitments, &cells).unwrap();
+	assert_eq!(rows, vec![fixture.row(0)]);
+
+	// Missing columns cannot be rebuilt.
+	let cells = data_cells(&fixture, &[1], 4);
+	assert!(matches!(
+		reconstruct_rows(&fixture.srs, &commitments, &cells),
+		Err(RecoveryError::MissingColumn(4))
+	));
+
+	// Rebuilt rows must match the commitments.
+	let mut cells = data_cells(&fixture, &[1], 8);
+	cells[0].data = fixture
+		.extended_grid
+		.get::<usize, usize>(1, 1)
+		.unwrap()
+		.to_bytes()
+		.unwrap();
+	assert!(matches!(
+		reconstruct_rows(&fixture.srs, &commitments, &cells),
+		Err(RecoveryError::CommitmentMismatch(0))
+	));
+}
+
+#[test]
+fn verify_extrinsic_proof_works() {
+	use crate::extrinsics::{extrinsic_proof, verify_extrinsic_proof};
+	use sp_core::storage::StateVersion;
+	use sp_runtime::traits::{BlakeTwo256, Hash};
+
+	let extrinsics = (0u8..20)
+		.map(|i| vec![i; 1 + usize::from(i) * 3])
+		.collect::<Vec<_>>();
+	// Same root as `frame_system::extrinsics_data_root`.
+	let root = BlakeTwo256::ordered_trie_root(extrinsics.clone(), StateVersion::V0);
+
+	for (index, extrinsic) in extrinsics.iter().enumerate() {
+		let index = index as u32;
+		let proof = extrinsic_proof(&extrinsics, index).unwrap();
+		assert_eq!(
+			verify_extrinsic_proof(root, index, extrinsic, &proof),
+			Ok(true)
+		);
+	}
+
+	let proof = extrinsic_proof(&extrinsics, 3).unwrap();
+	assert_eq!(
+		verify_extrinsic_proof(root, 3, &extrinsics[4], &proof),
+		Ok(false)
+	);
+	assert_eq!(
+		verify_extrinsic_proof(H256::zero(), 3, &extrinsics[3], &proof),
+		Ok(false)
+	);
+	assert_eq!(
+		verify_extrinsic_proof(root, 3, &extrinsics[3], &[vec![0xff; 4]]),
+		Err(Error::InvalidProof)
+	);
+	assert_eq!(extrinsic_proof(&extrinsics, 20), None);
+}
@@ -40,6 +40,15 @@ decl_runtime_apis! {
 		fn head() -> u64;
 		fn headers(slot: u64) -> H256;
 		fn successful_extrinsic_indices() -> Vec<u32>;
+
+		#[api_version(2)]
+		fn block_space_usage() -> Vec<da_control::AppBlockSpaceUsage>;
+
+		#[api_version(3)]
+		fn blob_manifest(blob_root: H256) -> Option<da_control::BlobManifestFor<Runtime>>;
+
+		#[api_version(4)]
+		fn scheduled_block_lengths() -> Vec<da_control::ScheduledBlockLengthFor<Runtime>>;
 	}
 
 	pub trait ExtensionBuilder {
@@ -339,6 +348,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(4)]
 	impl crate::apis::DataAvailApi<Block> for Runtime {
 		fn block_length() -> frame_system::limits::BlockLength {
 			frame_system::Pallet::<Runtime>::block_length()
@@ -374,6 +384,18 @@ impl_runtime_apis! {
 		fn successful_extrinsic_indices() -> Vec<u32> {
 			frame_system::Pallet::<Runtime>::successful_extrinsic_indices()
 		}
+
+		fn block_space_usage() -> Vec<da_control::AppBlockSpaceUsage> {
+			da_control::Pallet::<Runtime>::block_space_usage()
+		}
+
+		fn blob_manifest(blob_root: H256) -> Option<da_control::BlobManifestFor<Runtime>> {
+			da_control::Pallet::<Runtime>::blob_manifest(blob_root)
+		}
+
+		fn scheduled_block_lengths() -> Vec<da_control::ScheduledBlockLengthFor<Runtime>> {
+			da_control::Pallet::<Runtime>::scheduled_block_lengths().into_inner()
+		}
 	}
 
 
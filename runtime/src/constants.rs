@@ -383,9 +383,20 @@ pub mod da {
 		pub const MaxBlockRows: BlockLengthRows = BlockLengthRows(1024);
 		pub const MinBlockCols: BlockLengthColumns = BlockLengthColumns(32);
 		pub const MaxBlockCols: BlockLengthColumns = kate::config::MAX_BLOCK_COLUMNS;
+		pub const AppKeyDeposit: Balance = 10 * AVL;
+		pub const MetadataDepositBase: Balance = currency::deposit(1, 0);
+		pub const MetadataDepositPerByte: Balance = currency::deposit(0, 1);
+		/// A pending blob, and then its manifest, is one item of up to 44 bytes per chunk.
+		pub const BlobDepositBase: Balance = currency::deposit(1, 0);
+		pub const BlobDepositPerChunk: Balance = currency::deposit(0, 44);
 	}
 	pub type MaxAppKeyLength = ConstU32<64>;
 	pub type MaxAppDataLength = ConstU32<524_288>; // 512 Kb
+	pub type MaxAppMetadataLength = ConstU32<256>;
+	pub type MaxAppSubmitters = ConstU32<64>;
+	pub type MaxReservedLanes = ConstU32<16>;
+	pub type MaxBlobChunks = ConstU32<64>;
+	pub type MaxScheduledBlockLengths = ConstU32<16>;
 }
 
 /// Macro to set a value (e.g. when using the `parameter_types` macro) to either a production value
@@ -1,6 +1,6 @@
 use avail_core::asdr::AppUncheckedExtrinsic;
 use avail_core::OpaqueExtrinsic;
-use codec::Decode;
+use codec::{Decode, Encode};
 use da_control::{Call as DaCall, CheckAppId};
 use frame_election_provider_support::BoundedVec;
 use frame_support::traits::DefensiveTruncateFrom;
@@ -26,6 +26,13 @@ fn send_message() -> Vec<u8> {
 	hex!("fd028400d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d0178ece77c33dbecbf1eff04371af093ab55f7a7b102b0d38760f1506468e78556761e1a1fc9ca57dcfad69f551306fc86d16191a094d9d1ce61ee70aa421339884400040000270301000000000000000000000000000000000000000000000000000000000000000108010100000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000000").to_vec()
 }
 
+fn submit_blob_chunk_call() -> Vec<u8> {
+	let call = RuntimeCall::DataAvailability(DaCall::submit_data_chunk {
+		data: b"Test submit data".to_vec().try_into().unwrap(),
+	});
+	super::UncheckedExtrinsic::new_unsigned(call).encode()
+}
+
 fn expected_blob_root() -> H256 {
 	// data = "Test submit data"
 	// leaf is keccak256(data) -> keccak256(root)
@@ -128,6 +135,7 @@ fn decode_submit_blob_call() {
 }
 
 #[test_case([submit_blob_call()].into() => submit_blob_call_expected(); "Test submit blob extrinsic")]
+#[test_case([submit_blob_chunk_call()].into() => submit_blob_call_expected(); "Test submit blob chunk extrinsic")]
 #[test_case([send_message()].into() => send_message_expected(); "Test submit bridge extrinsic")]
 #[test_case([send_message(), submit_blob_call()].into() => expect_sending_blob_and_bridge_extrinsic(); "Test send message and bridge extrinsic")]
 fn data_root_filter(extrinsics: Vec<Vec<u8>>) -> H256 {
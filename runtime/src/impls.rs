@@ -54,7 +54,9 @@ use pallet_election_provider_multi_phase::{GeometricDepositBase, SolutionAccurac
 use pallet_identity::legacy::IdentityInfo;
 use pallet_transaction_payment::CurrencyAdapter;
 use pallet_transaction_payment::Multiplier;
-use pallet_transaction_payment::{LengthFeeAdjustment, TargetedFeeAdjustment};
+use pallet_transaction_payment::{
+	BlobFeeAdjustment, GetBlobLength, LengthFeeAdjustment, TargetedFeeAdjustment,
+};
 use pallet_tx_pause::RuntimeCallNameOf;
 use sp_core::crypto::KeyTypeId;
 use sp_core::ConstU64;
@@ -153,13 +155,24 @@ impl pallet_identity::Config for Runtime {
 }
 
 impl da_control::Config for Runtime {
+	type AppKeyDeposit = constants::da::AppKeyDeposit;
 	type BlockLenProposalId = u32;
+	type Currency = Balances;
 	type MaxAppDataLength = constants::da::MaxAppDataLength;
 	type MaxAppKeyLength = constants::da::MaxAppKeyLength;
+	type MaxAppMetadataLength = constants::da::MaxAppMetadataLength;
+	type MaxAppSubmitters = constants::da::MaxAppSubmitters;
+	type MaxReservedLanes = constants::da::MaxReservedLanes;
+	type MaxBlobChunks = constants::da::MaxBlobChunks;
+	type MaxScheduledBlockLengths = constants::da::MaxScheduledBlockLengths;
 	type MaxBlockCols = constants::da::MaxBlockCols;
 	type MaxBlockRows = constants::da::MaxBlockRows;
 	type MinBlockCols = constants::da::MinBlockCols;
 	type MinBlockRows = constants::da::MinBlockRows;
+	type MetadataDepositBase = constants::da::MetadataDepositBase;
+	type MetadataDepositPerByte = constants::da::MetadataDepositPerByte;
+	type BlobDepositBase = constants::da::BlobDepositBase;
+	type BlobDepositPerChunk = constants::da::BlobDepositPerChunk;
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = weights::pallet_dactr::WeightInfo<Runtime>;
 }
@@ -248,9 +261,22 @@ parameter_types! {
 	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000_000u128);
 	pub MinLenMultiplier: Multiplier = Multiplier::from_u32(1);
 	pub MaximumMultiplier: Multiplier = Bounded::max_value();
+	pub const BlobByteFee: Balance = 10 * NANO_AVL; // 10 nanoAVL
+	pub const TargetGridUtilization: Perquintill = Perquintill::from_percent(50); // target_utilization 50%
+	pub BlobAdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(2, 1000); // 0.002 to double the blob_multiplier in one epoch
+	pub MinBlobMultiplier: Multiplier = Multiplier::from_u32(1);
 }
 
 impl pallet_transaction_payment::Config for Runtime {
+	type BlobLength = SubmittedDataLength;
+	type BlobLengthToFee = ConstantMultiplier<Balance, BlobByteFee>;
+	type BlobMultiplierUpdate = BlobFeeAdjustment<
+		Self,
+		TargetGridUtilization,
+		BlobAdjustmentVariable,
+		MinBlobMultiplier,
+		MaximumMultiplier,
+	>;
 	type FeeMultiplierUpdate = TargetedFeeAdjustment<
 		Self,
 		TargetBlockFullness,
@@ -874,14 +900,17 @@ parameter_types! {
 		BlockLength::max_with_normal_ratio(5 * 1024 * 1024, NORMAL_DISPATCH_RATIO);
 }
 
-/// Filters and extracts `data` from `call` if it is a `DataAvailability::submit_data` type.
+/// Filters and extracts `data` from `call` if it is a `DataAvailability::submit_data` or
+/// `DataAvailability::submit_data_chunk` type.
 impl submitted_data::Filter<RuntimeCall> for Runtime {
 	fn filter(call: RuntimeCall, metrics: submitted_data::RcMetrics) -> Vec<Vec<u8>> {
 		metrics.borrow_mut().total_extrinsics += 1;
 
 		match call {
-			RuntimeCall::DataAvailability(da_control::Call::submit_data { data })
-				if !data.is_empty() =>
+			RuntimeCall::DataAvailability(
+				da_control::Call::submit_data { data }
+				| da_control::Call::submit_data_chunk { data },
+			) if !data.is_empty() =>
 			{
 				let mut metrics = metrics.borrow_mut();
 				metrics.data_submit_leaves += 1;
@@ -948,8 +977,10 @@ impl submitted_data::Filter<RuntimeCall> for Runtime {
 				};
 				(vec![], vec![message])
 			},
-			RuntimeCall::DataAvailability(da_control::Call::submit_data { data })
-				if !data.is_empty() =>
+			RuntimeCall::DataAvailability(
+				da_control::Call::submit_data { data }
+				| da_control::Call::submit_data_chunk { data },
+			) if !data.is_empty() =>
 			{
 				let mut metrics = metrics.borrow_mut();
 				metrics.data_submit_leaves += 1;
@@ -1016,6 +1047,28 @@ impl submitted_data::Extractor for Runtime {
 	}
 }
 
+/// Length of the data submitted by `DataAvailability::submit_data` and
+/// `DataAvailability::submit_data_chunk` calls, including the batched ones.
+pub struct SubmittedDataLength;
+
+impl GetBlobLength<RuntimeCall> for SubmittedDataLength {
+	fn blob_len(call: &RuntimeCall) -> u32 {
+		match call {
+			RuntimeCall::DataAvailability(
+				da_control::Call::submit_data { data }
+				| da_control::Call::submit_data_chunk { data },
+			) => data.len() as u32,
+			RuntimeCall::Utility(pallet_utility::Call::batch { calls })
+			| RuntimeCall::Utility(pallet_utility::Call::batch_all { calls })
+			| RuntimeCall::Utility(pallet_utility::Call::force_batch { calls }) => calls
+				.iter()
+				.map(Self::blob_len)
+				.fold(0u32, |total, len| total.saturating_add(len)),
+			_ => 0,
+		}
+	}
+}
+
 // Configure FRAME pallets to include in runtime.
 impl frame_system::Config for Runtime {
 	/// The data to be stored in an account.